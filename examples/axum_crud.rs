@@ -0,0 +1,123 @@
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use serde_hash::axum::HashPath;
+use serde_hash::cursor::Cursor;
+use serde_hash::hashids::SerdeHashOptions;
+use serde_hash::serde_hash;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+// An item as the API sees it: `id` goes over the wire as a hash, never as the row number.
+#[serde_hash]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Item {
+    #[serde(hash)]
+    pub id: u64,
+    pub name: String,
+}
+
+// The body of create and update requests.
+#[derive(Deserialize)]
+pub struct ItemInput {
+    pub name: String,
+}
+
+// `?cursor=...&limit=...`; the cursor is the hash handed out as `next` by the last page.
+#[derive(Deserialize)]
+pub struct ListQuery {
+    pub cursor: Option<Cursor<u64>>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Page {
+    pub items: Vec<Item>,
+    pub next: Option<Cursor<u64>>,
+}
+
+// An in-memory table standing in for a database.
+#[derive(Clone, Default)]
+pub struct Store {
+    items: Arc<Mutex<BTreeMap<u64, Item>>>,
+}
+
+const DEFAULT_LIMIT: usize = 20;
+
+pub fn app(store: Store) -> Router {
+    Router::new()
+        .route("/items", get(list_items).post(create_item))
+        .route("/items/{id}", get(get_item).put(update_item).delete(delete_item))
+        .with_state(store)
+}
+
+async fn create_item(
+    State(store): State<Store>,
+    Json(input): Json<ItemInput>,
+) -> (StatusCode, Json<Item>) {
+    let mut items = store.items.lock().unwrap();
+    let id = items.keys().next_back().map_or(1, |id| id + 1);
+    let item = Item { id, name: input.name };
+    items.insert(id, item.clone());
+    (StatusCode::CREATED, Json(item))
+}
+
+// An id that doesn't decode never reaches the handler: `HashPath` answers it with a 404.
+async fn get_item(
+    State(store): State<Store>,
+    HashPath(id): HashPath<u64>,
+) -> Result<Json<Item>, StatusCode> {
+    let items = store.items.lock().unwrap();
+    items.get(&id).cloned().map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn update_item(
+    State(store): State<Store>,
+    HashPath(id): HashPath<u64>,
+    Json(input): Json<ItemInput>,
+) -> Result<Json<Item>, StatusCode> {
+    let mut items = store.items.lock().unwrap();
+    let item = items.get_mut(&id).ok_or(StatusCode::NOT_FOUND)?;
+    item.name = input.name;
+    Ok(Json(item.clone()))
+}
+
+async fn delete_item(State(store): State<Store>, HashPath(id): HashPath<u64>) -> StatusCode {
+    match store.items.lock().unwrap().remove(&id) {
+        Some(_) => StatusCode::NO_CONTENT,
+        None => StatusCode::NOT_FOUND,
+    }
+}
+
+// Pages start after the last id of the previous page, which the cursor carries.
+async fn list_items(State(store): State<Store>, Query(query): Query<ListQuery>) -> Json<Page> {
+    let items = store.items.lock().unwrap();
+    let after = query.cursor.map_or(0, |cursor| cursor.position);
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    let page: Vec<Item> =
+        items.range(after + 1..).take(limit).map(|(_, item)| item.clone()).collect();
+    let next = page
+        .last()
+        .filter(|last| items.range(last.id + 1..).next().is_some())
+        .map(|last| Cursor::forward(last.id));
+    Json(Page { items: page, next })
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> anyhow::Result<()> {
+    SerdeHashOptions::new()
+        .with_salt("replace-with-a-random-salt")
+        .with_min_length(10)
+        .build();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000").await?;
+    println!("listening on http://{}", listener.local_addr()?);
+    // Try:
+    //   curl -X POST localhost:3000/items -H 'content-type: application/json' -d '{"name":"first"}'
+    //   curl localhost:3000/items/<id from the response>
+    //   curl 'localhost:3000/items?limit=1'
+    axum::serve(listener, app(Store::default())).await?;
+    Ok(())
+}
@@ -24,7 +24,7 @@ fn main() {
     // All of these fields are optional.
     SerdeHashOptions::new()
         // Set the cryptographic salt for the hash algorithm
-        .with_salt("hello world")
+        .with_salt("replace-with-a-random-salt")
         // Ensure generated hashes are at least 10 characters long
         .with_min_length(10)
         // Define the character set used in the hash encoding (alphanumeric in this case)
@@ -0,0 +1,64 @@
+use serde_hash::bulk::{BulkDecoder, BulkFormat};
+use serde_hash::hashids::SerdeHashOptions;
+use std::io::{self, BufWriter};
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: bulk <ndjson|csv> [--keep-invalid] <column>... < input > output
+
+Decodes the hashed columns of an NDJSON or CSV stream read from stdin and writes the
+result to stdout. Columns are globs such as `id` or `*_id`.
+
+The options are read from the environment:
+  SERDE_HASH_SALT        the salt the hashes were encoded with (required)
+  SERDE_HASH_MIN_LENGTH  the minimum hash length (default 0)
+  SERDE_HASH_ALPHABET    the alphabet (default: the library's default alphabet)";
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let format = match args.next().as_deref() {
+        Some("ndjson") => BulkFormat::Ndjson,
+        Some("csv") => BulkFormat::Csv,
+        _ => anyhow::bail!("{}", USAGE),
+    };
+    let (flags, columns): (Vec<String>, Vec<String>) = args.partition(|arg| arg.starts_with("--"));
+    let keep_invalid = match flags.as_slice() {
+        [] => false,
+        [flag] if flag == "--keep-invalid" => true,
+        _ => anyhow::bail!("{}", USAGE),
+    };
+    if columns.is_empty() {
+        anyhow::bail!("{}", USAGE);
+    }
+
+    // Configure the options the hashes were encoded with
+    let salt = std::env::var("SERDE_HASH_SALT")
+        .map_err(|_| anyhow::anyhow!("SERDE_HASH_SALT is not set\n\n{}", USAGE))?;
+    let mut options = SerdeHashOptions::new().with_salt(salt);
+    if let Ok(min_length) = std::env::var("SERDE_HASH_MIN_LENGTH") {
+        options = options.with_min_length(min_length.parse::<usize>()?);
+    }
+    if let Ok(alphabet) = std::env::var("SERDE_HASH_ALPHABET") {
+        options = options.with_alphabet(alphabet);
+    }
+    options.try_build()?;
+
+    // Stream stdin to stdout one record at a time
+    let decoder = BulkDecoder::new(&columns)?.with_keep_invalid(keep_invalid);
+    let stdout = BufWriter::new(io::stdout().lock());
+    let stats = decoder.decode(format, io::stdin().lock(), stdout)?;
+    eprintln!(
+        "{} records, {} hashes decoded, {} invalid values kept",
+        stats.records, stats.decoded, stats.invalid
+    );
+    Ok(())
+}
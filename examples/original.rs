@@ -3,7 +3,7 @@
 use serde::de::Visitor;
 use serde::ser::SerializeMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use serde_hash::hashids::{decode_single, encode_single, SerdeHashOptions};
+use serde_hash::hashids::{current_encoder, SerdeHashOptions};
 
 #[derive(Debug)]
 pub struct TestData {
@@ -14,7 +14,7 @@ pub struct TestData {
 }
 
 fn main() {
-    SerdeHashOptions::new().with_salt("hello world").build();
+    SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
     let data = TestData {
         id: 158674,
         name: "Dan Smith".to_string(),
@@ -32,13 +32,14 @@ impl Serialize for TestData {
     where
         S: Serializer,
     {
+        let encoder = current_encoder();
         let mut map = serializer.serialize_map(Some(3))?;
-        map.serialize_entry("id", &encode_single(self.id))?;
+        map.serialize_entry("id", &encoder.encode_single(self.id))?;
         map.serialize_entry("name", &self.name)?;
         map.serialize_entry("age", &self.age)?;
         let mut vec_str = vec![];
         for v in self.vec.iter() {
-            vec_str.push(encode_single(*v as u64));
+            vec_str.push(encoder.encode_single(*v as u64));
         }
         map.serialize_entry("vec", &vec_str)?;
 
@@ -76,6 +77,7 @@ impl<'de> Deserialize<'de> for TestData {
             where
                 V: serde::de::MapAccess<'de>,
             {
+                let encoder = current_encoder();
                 let mut id: Option<u64> = None;
                 let mut name: Option<String> = None;
                 let mut age: Option<u8> = None;
@@ -86,10 +88,10 @@ impl<'de> Deserialize<'de> for TestData {
                         Field::Id => {
                             if id.is_some() {
                                 return Err(serde::de::Error::duplicate_field("id"));
-                            } else if let Ok(string_value) = map.next_value::<String>() {
-                                if let Ok(u64_value) = decode_single(string_value) {
-                                    id = Some(u64_value);
-                                }
+                            } else if let Ok(string_value) = map.next_value::<String>()
+                                && let Ok(u64_value) = encoder.decode_single(string_value)
+                            {
+                                id = Some(u64_value);
                             }
                         }
                         Field::Vec => {
@@ -98,7 +100,7 @@ impl<'de> Deserialize<'de> for TestData {
                             } else if let Ok(vec_string) = map.next_value::<Vec<String>>() {
                                 let mut tmp: Vec<u8> = vec![];
                                 for v in vec_string {
-                                    if let Ok(u64_value) = decode_single(v) {
+                                    if let Ok(u64_value) = encoder.decode_single(v) {
                                         tmp.push(u64_value as u8);
                                     }
                                 }
@@ -25,7 +25,7 @@ pub struct TestData {
 fn main() {
     // Configure the hash ID generator with custom settings
     SerdeHashOptions::new()
-        .with_salt("hello world")
+        .with_salt("replace-with-a-random-salt")
         .with_min_length(10)
         .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
         .build();
@@ -0,0 +1,36 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use serde::{Deserialize, Serialize};
+use serde_hash::hashids::SerdeHashOptions;
+use serde_hash::serde_hash;
+use std::sync::Once;
+
+#[serde_hash]
+#[derive(Serialize, Deserialize)]
+struct User {
+    #[serde(hash)]
+    id: u64,
+    #[serde(hash)]
+    friends: Vec<u64>,
+}
+
+static SETUP: Once = Once::new();
+
+fuzz_target!(|data: &[u8]| {
+    SETUP.call_once(|| {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_min_length(10)
+            .with_checksum(true)
+            .build();
+    });
+
+    let encoder = serde_hash::hashids::current_encoder();
+    if let Ok(hash) = std::str::from_utf8(data) {
+        // Decoding may fail, but must never panic.
+        let _ = encoder.decode(hash);
+        let _ = encoder.decode_single(hash);
+    }
+    let _ = serde_json::from_slice::<User>(data);
+});
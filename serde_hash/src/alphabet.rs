@@ -0,0 +1,138 @@
+use crate::error::SerdeHashError;
+use std::collections::HashSet;
+#[cfg(feature = "unicode")]
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The fewest unique characters hashids can work with.
+pub const MIN_ALPHABET_LENGTH: usize = 16;
+
+/// Ready-made alphabets for [`SerdeHashOptions::with_alphabet_preset`].
+///
+/// [`SerdeHashOptions::with_alphabet_preset`]: crate::hashids::SerdeHashOptions::with_alphabet_preset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alphabet {
+    /// Lowercase and uppercase letters plus digits. This is the default.
+    Base62,
+    /// The Bitcoin base58 alphabet, which leaves out `0`, `O`, `I` and `l`.
+    Base58NoLookalikes,
+    /// Digits and the letters `a` to `f`.
+    LowercaseHex,
+    /// The URL- and filename-safe base64 alphabet (`A-Z`, `a-z`, `0-9`, `-` and `_`).
+    UrlSafe,
+    /// Crockford's Base32 alphabet, which leaves out `I`, `L`, `O` and `U`. As a preset it
+    /// also enables Crockford decoding, which reads lowercase letters, `O`, `I` and `L`.
+    Crockford32,
+    /// A custom alphabet, checked when the options are built. With the `alphabet-macro`
+    /// feature, `serde_hash::alphabet!("...")` checks it at compile time instead.
+    Custom(&'static str),
+}
+
+impl Alphabet {
+    /// Returns the characters of this alphabet.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Base62 => "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890",
+            Self::Base58NoLookalikes => {
+                "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+            }
+            Self::LowercaseHex => "0123456789abcdef",
+            Self::UrlSafe => "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+            Self::Crockford32 => "0123456789ABCDEFGHJKMNPQRSTVWXYZ",
+            Self::Custom(alphabet) => alphabet,
+        }
+    }
+}
+
+impl AsRef<str> for Alphabet {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// Checks that an alphabet can be used for hash generation.
+///
+/// # Arguments
+///
+/// * `alphabet` - The candidate alphabet.
+///
+/// # Returns
+///
+/// * `Ok(())` if the alphabet has at least 16 characters, no duplicates and no spaces, and
+///   no two of its characters combine into a single grapheme.
+/// * The first problem found otherwise.
+///
+/// Characters are Unicode scalar values, so alphabets of Cyrillic letters or emoji work
+/// like ASCII ones. What they can't contain is combining accents, skin tone modifiers,
+/// joiners or flag halves: hashes put alphabet characters next to each other in any order,
+/// and such pairs would render as one symbol. Telling them apart takes the `unicode`
+/// feature; without it, alphabets must be ASCII.
+pub fn validate_alphabet(alphabet: &str) -> Result<(), SerdeHashError> {
+    let mut seen = HashSet::new();
+    for c in alphabet.chars() {
+        if c == ' ' {
+            return Err(SerdeHashError::AlphabetContainsSpace);
+        }
+        if !seen.insert(c) {
+            return Err(SerdeHashError::AlphabetDuplicate(c));
+        }
+    }
+    if seen.len() < MIN_ALPHABET_LENGTH {
+        return Err(SerdeHashError::AlphabetTooShort { unique: seen.len() });
+    }
+    #[cfg(not(feature = "unicode"))]
+    if let Some(c) = alphabet.chars().find(|c| !c.is_ascii()) {
+        return Err(SerdeHashError::AlphabetNotAscii(c));
+    }
+    if let Some((first, second)) = combining_pair(alphabet) {
+        return Err(SerdeHashError::AlphabetCombiningCharacters { first, second });
+    }
+    Ok(())
+}
+
+/// Returns the first pair of characters of `alphabet` that form a single grapheme when
+/// written in that order.
+#[cfg(feature = "unicode")]
+fn combining_pair(alphabet: &str) -> Option<(char, char)> {
+    let chars: Vec<char> = alphabet.chars().collect();
+    let mut pair = String::new();
+    for &first in &chars {
+        for &second in &chars {
+            // Of two ASCII characters, only CR LF is a single grapheme.
+            if first.is_ascii() && second.is_ascii() && (first, second) != ('\r', '\n') {
+                continue;
+            }
+            pair.clear();
+            pair.push(first);
+            pair.push(second);
+            if pair.graphemes(true).nth(1).is_none() {
+                return Some((first, second));
+            }
+        }
+    }
+    None
+}
+
+/// Returns the first pair of characters of an ASCII `alphabet` that form a single
+/// grapheme: only CR LF does.
+#[cfg(not(feature = "unicode"))]
+fn combining_pair(alphabet: &str) -> Option<(char, char)> {
+    (alphabet.contains('\r') && alphabet.contains('\n')).then_some(('\r', '\n'))
+}
+
+/// Whether `c` is an unreserved URL character (`A-Z`, `a-z`, `0-9`, `-`, `.`, `_` and `~`),
+/// which appears as-is in paths and query strings without percent-encoding.
+pub fn is_url_safe(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~')
+}
+
+/// Maps a character of a hash typed or read by a person onto Crockford's Base32 symbols:
+/// letters are uppercased, `O` becomes `0` and `I` and `L` become `1`. Returns `None` for
+/// hyphens, which may be written between symbols for readability.
+pub fn crockford_symbol(c: char) -> Option<char> {
+    match c.to_ascii_uppercase() {
+        '-' => None,
+        'O' => Some('0'),
+        'I' | 'L' => Some('1'),
+        c => Some(c),
+    }
+}
@@ -0,0 +1,87 @@
+//! Extracting hashed ids in [axum](https://docs.rs/axum) handlers.
+//!
+//! [`HashPath`] decodes a path segment such as the `{id}` in `/items/{id}` with the active
+//! options. A segment that isn't a valid hash is answered with `404 Not Found`, the same
+//! as an id that doesn't exist, so clients can't tell the two apart. Everything else
+//! needs no glue: structs with hashed fields and [`Cursor`](crate::cursor::Cursor)s
+//! serialize as hashes through `axum::Json`, and deserialize from `Json` and `Query`.
+//!
+//! # Example
+//! ```
+//! use axum::Router;
+//! use axum::routing::get;
+//! use serde_hash::axum::HashPath;
+//!
+//! async fn get_item(HashPath(id): HashPath<u64>) -> String {
+//!     format!("item {}", id)
+//! }
+//!
+//! let app: Router = Router::new().route("/items/{id}", get(get_item));
+//! ```
+//!
+//! The `axum_crud` example is a complete service with create, read, update, delete and
+//! cursor pagination.
+
+use crate::error::SerdeHashError;
+use crate::serde_impl::{HashNumeric, decode_field_values, numeric_from_values};
+use axum::extract::rejection::PathRejection;
+use axum::extract::{FromRequestParts, Path};
+use axum::http::StatusCode;
+use axum::http::request::Parts;
+use axum::response::{IntoResponse, Response};
+use std::fmt;
+
+/// Extracts the id hashed in a route's only path parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HashPath<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for HashPath<T>
+where
+    T: HashNumeric + Send,
+    S: Send + Sync,
+{
+    type Rejection = HashPathRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(hash) = Path::<String>::from_request_parts(parts, state)
+            .await
+            .map_err(HashPathRejection::Path)?;
+        let values = decode_field_values(&hash).map_err(HashPathRejection::InvalidHash)?;
+        numeric_from_values::<T, serde::de::value::Error>(&values, None)
+            .map(HashPath)
+            .map_err(|e| SerdeHashError::InvalidHash(e.to_string()))
+            .map_err(HashPathRejection::InvalidHash)
+    }
+}
+
+/// Why [`HashPath`] couldn't extract an id.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum HashPathRejection {
+    /// The route doesn't have exactly one path parameter. Answered the way axum's `Path`
+    /// answers it.
+    Path(PathRejection),
+    /// The segment isn't a valid hash, or its value doesn't fit the id type. Answered with
+    /// `404 Not Found`.
+    InvalidHash(SerdeHashError),
+}
+
+impl fmt::Display for HashPathRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Path(rejection) => write!(f, "{}", rejection),
+            Self::InvalidHash(e) => write!(f, "invalid hashed id in path: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for HashPathRejection {}
+
+impl IntoResponse for HashPathRejection {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Path(rejection) => rejection.into_response(),
+            Self::InvalidHash(_) => StatusCode::NOT_FOUND.into_response(),
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Streaming decode of hashed columns in NDJSON and CSV exports.
+//!
+//! Reprocessing historical exports or logs means turning hashes back into ids across
+//! files far larger than memory. A [`BulkDecoder`] reads one record at a time, decodes
+//! the values of the configured columns and writes the record out before reading the
+//! next, so memory use is bounded by the largest record rather than the file.
+//!
+//! Columns are named with the same patterns as
+//! [`SerdeHashOptions::with_field_patterns`](crate::hashids::SerdeHashOptions::with_field_patterns):
+//!
+//! * In NDJSON, every line is a JSON document and matching keys are decoded at any depth,
+//!   like [`crate::value::decode_ids`] does.
+//! * In CSV (with the `csv` feature), the first row holds the headers and every cell of a
+//!   matching column is decoded. Cells may hold several hashes separated by
+//!   [`crate::csv::SEPARATOR`], as written by [`crate::csv::vec_numeric`].
+//!
+//! The `bulk` example wraps this in a command-line tool.
+//!
+//! # Example
+//! ```
+//! use serde_hash::bulk::{BulkDecoder, BulkFormat};
+//! use serde_hash::hashids::{SerdeHashOptions, encode_single};
+//!
+//! SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+//!
+//! let input = format!("{{\"id\":\"{}\",\"event\":\"login\"}}\n", encode_single(42));
+//! let mut output = Vec::new();
+//! let stats = BulkDecoder::new(["id"])
+//!     .unwrap()
+//!     .decode(BulkFormat::Ndjson, input.as_bytes(), &mut output)
+//!     .unwrap();
+//!
+//! assert_eq!(String::from_utf8(output).unwrap(), "{\"event\":\"login\",\"id\":42}\n");
+//! assert_eq!(stats.decoded, 1);
+//! ```
+
+use crate::error::{Result, SerdeHashError};
+use crate::patterns::FieldPatterns;
+use crate::serde_impl::decode_field;
+use crate::value::rewrite;
+use serde_json::Value;
+use std::cell::Cell;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// The layout of the records being decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkFormat {
+    /// One JSON document per line.
+    Ndjson,
+    /// Comma-separated values with a header row.
+    #[cfg(feature = "csv")]
+    Csv,
+}
+
+/// Counts reported once a bulk decode finishes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BulkStats {
+    /// Records read, not counting the CSV header row.
+    pub records: u64,
+    /// Hashes decoded into ids.
+    pub decoded: u64,
+    /// Values left as they were with [`BulkDecoder::with_keep_invalid`].
+    pub invalid: u64,
+}
+
+/// Decodes the hashed columns of NDJSON or CSV records, see the
+/// [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct BulkDecoder {
+    columns: FieldPatterns,
+    keep_invalid: bool,
+}
+
+impl BulkDecoder {
+    /// Creates a decoder for the columns matching `columns`.
+    ///
+    /// # Returns
+    ///
+    /// * The decoder.
+    /// * [`SerdeHashError::InvalidFieldPattern`] for the first pattern that doesn't compile.
+    pub fn new<I, S>(columns: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Ok(Self {
+            columns: FieldPatterns::new(columns)?,
+            keep_invalid: false,
+        })
+    }
+
+    /// Leaves values that don't decode as they were, counting them in
+    /// [`BulkStats::invalid`], instead of stopping at the first one.
+    ///
+    /// Historical exports often contain the odd corrupted or foreign value; keeping them
+    /// lets the rest of the file be processed.
+    pub fn with_keep_invalid(mut self, keep_invalid: bool) -> Self {
+        self.keep_invalid = keep_invalid;
+        self
+    }
+
+    /// Decodes every record read from `reader` and writes it to `writer`.
+    ///
+    /// # Returns
+    ///
+    /// * The counts of records and values processed.
+    /// * An error naming the record if it can't be parsed, or if a value fails to decode
+    ///   and invalid values aren't kept. Records before it have already been written.
+    pub fn decode(
+        &self,
+        format: BulkFormat,
+        reader: impl Read,
+        writer: impl Write,
+    ) -> Result<BulkStats> {
+        match format {
+            BulkFormat::Ndjson => self.decode_ndjson(BufReader::new(reader), writer),
+            #[cfg(feature = "csv")]
+            BulkFormat::Csv => self.decode_csv(reader, writer),
+        }
+    }
+
+    /// Like [`BulkDecoder::decode`], reading from the file at `input` and replacing the
+    /// file at `output`.
+    pub fn decode_file(
+        &self,
+        format: BulkFormat,
+        input: impl AsRef<Path>,
+        output: impl AsRef<Path>,
+    ) -> Result<BulkStats> {
+        let (input, output) = (input.as_ref(), output.as_ref());
+        let reader = File::open(input).map_err(|e| {
+            SerdeHashError::from(e).context(format!("opening {}", input.display()))
+        })?;
+        let writer = File::create(output).map_err(|e| {
+            SerdeHashError::from(e).context(format!("creating {}", output.display()))
+        })?;
+        self.decode(format, reader, BufWriter::new(writer))
+    }
+
+    /// Decodes one value, or keeps it and counts it as invalid if allowed.
+    fn decode_value(&self, hash: &str, stats: &Cell<BulkStats>) -> Result<Option<u64>> {
+        let mut counts = stats.get();
+        let decoded = match decode_field(hash) {
+            Ok(id) => {
+                counts.decoded += 1;
+                Some(id)
+            }
+            Err(_) if self.keep_invalid => {
+                counts.invalid += 1;
+                None
+            }
+            Err(e) => return Err(e),
+        };
+        stats.set(counts);
+        Ok(decoded)
+    }
+
+    fn decode_ndjson(&self, reader: impl BufRead, mut writer: impl Write) -> Result<BulkStats> {
+        let stats = Cell::new(BulkStats::default());
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                writeln!(writer)?;
+                continue;
+            }
+            let context = |e: SerdeHashError| e.context(format!("in record {}", index + 1));
+            let mut record: Value =
+                serde_json::from_str(&line).map_err(|e| context(e.into()))?;
+            rewrite(&mut record, &self.columns, &|value| match value {
+                Value::String(hash) => Ok(match self.decode_value(hash, &stats)? {
+                    Some(id) => Value::from(id),
+                    None => value.clone(),
+                }),
+                other => Err(SerdeHashError::InvalidHash(format!(
+                    "expected a hash, found {}",
+                    other
+                ))),
+            })
+            .map_err(context)?;
+            serde_json::to_writer(&mut writer, &record)?;
+            writeln!(writer)?;
+            let mut counts = stats.get();
+            counts.records += 1;
+            stats.set(counts);
+        }
+        writer.flush()?;
+        Ok(stats.get())
+    }
+
+    #[cfg(feature = "csv")]
+    fn decode_csv(&self, reader: impl Read, writer: impl Write) -> Result<BulkStats> {
+        use crate::csv::SEPARATOR;
+
+        let mut reader = ::csv::Reader::from_reader(reader);
+        let mut writer = ::csv::Writer::from_writer(writer);
+        let headers = reader.headers()?.clone();
+        let matching: Vec<bool> = headers.iter().map(|header| self.columns.matches(header)).collect();
+        writer.write_record(&headers)?;
+
+        let stats = Cell::new(BulkStats::default());
+        let mut record = ::csv::StringRecord::new();
+        let mut output = ::csv::StringRecord::new();
+        let mut index = 0;
+        while reader.read_record(&mut record)? {
+            index += 1;
+            output.clear();
+            for (cell, &matches) in record.iter().zip(&matching) {
+                if !matches {
+                    output.push_field(cell);
+                    continue;
+                }
+                let ids = cell
+                    .split(SEPARATOR)
+                    .filter(|hash| !hash.is_empty())
+                    .map(|hash| {
+                        Ok(match self.decode_value(hash, &stats)? {
+                            Some(id) => id.to_string(),
+                            None => hash.to_string(),
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()
+                    .map_err(|e| e.context(format!("in record {}", index)))?;
+                output.push_field(&ids.join(&SEPARATOR.to_string()));
+            }
+            writer.write_record(&output)?;
+            let mut counts = stats.get();
+            counts.records += 1;
+            stats.set(counts);
+        }
+        writer.flush()?;
+        Ok(stats.get())
+    }
+}
@@ -0,0 +1,285 @@
+//! The hashids algorithm, producing the same hashes as the `hash_ids` crate.
+//!
+//! `hash_ids::HashIds::decode` accumulates each value with unchecked arithmetic, so a
+//! long run of valid characters overflows: it panics with overflow checks enabled and
+//! silently wraps to the wrong value without them. This module rebuilds the same
+//! character layout `hash_ids` derives from the alphabet and salt, and decodes with
+//! checked arithmetic instead. Owning the layout also lets the salt be wiped on drop
+//! (see [`crate::secret`]), which a `hash_ids::HashIds` can't do.
+//!
+//! With `js_compat`, the layout follows hashids.js instead, which differs from `hash_ids`
+//! only in when the separators are shuffled.
+
+use crate::secret::wipe_chars;
+use std::collections::VecDeque;
+use std::fmt;
+
+const DEFAULT_SEPARATORS: &str = "cfhistuCFHISTU";
+const SEPARATOR_DIV: f32 = 3.5;
+const GUARD_DIV: f32 = 12.0;
+
+/// Why [`Codec::decode`] rejected a hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Error {
+    /// The hash has characters outside the alphabet or a value that doesn't fit a `u64`.
+    InvalidHash,
+    /// The hash has no lottery character to seed the shuffle with.
+    MissingLotteryChar,
+}
+
+/// The alphabet, separators and guards hashids derives from an alphabet and salt.
+pub(crate) struct Codec {
+    /// Every character a hash may contain.
+    charset: Vec<char>,
+    salt: Vec<char>,
+    alphabet: Vec<char>,
+    separators: Vec<char>,
+    guards: Vec<char>,
+}
+
+impl Codec {
+    /// Builds the layout for `alphabet`, which must already be validated.
+    ///
+    /// `hash_ids` shuffles the separators after topping them up from the alphabet, while
+    /// hashids.js shuffles them before, so `js_compat` picks the hashids.js order.
+    pub(crate) fn new(alphabet: &str, salt: &str, js_compat: bool) -> Self {
+        let salt: Vec<char> = salt.chars().collect();
+        let charset: Vec<char> = alphabet.chars().collect();
+        let mut alphabet = charset.clone();
+        let mut separators: Vec<char> = DEFAULT_SEPARATORS
+            .chars()
+            .filter(|c| alphabet.contains(c))
+            .collect();
+        alphabet.retain(|c| !separators.contains(c));
+        if js_compat {
+            separators = reorder(&separators, &salt);
+        }
+
+        let min_separators = index_from_ratio(alphabet.len(), SEPARATOR_DIV);
+        if let Some(missing) = min_separators.checked_sub(separators.len())
+            && missing > 0
+        {
+            let rest = alphabet.split_off(missing);
+            separators.extend(std::mem::replace(&mut alphabet, rest));
+        }
+
+        let mut alphabet = reorder(&alphabet, &salt);
+        let mut separators = if js_compat {
+            separators
+        } else {
+            reorder(&separators, &salt)
+        };
+
+        let num_guards = index_from_ratio(alphabet.len(), GUARD_DIV);
+        let guards = if alphabet.len() < 3 {
+            let rest = separators.split_off(num_guards);
+            std::mem::replace(&mut separators, rest)
+        } else {
+            let rest = alphabet.split_off(num_guards);
+            std::mem::replace(&mut alphabet, rest)
+        };
+
+        Self {
+            charset,
+            salt,
+            alphabet,
+            separators,
+            guards,
+        }
+    }
+
+    /// Encodes `values` like `hash_ids::HashIds::encode`, padding to `min_length`.
+    pub(crate) fn encode(&self, values: &[u64], min_length: usize) -> String {
+        if values.is_empty() {
+            return String::new();
+        }
+
+        let values_hash = values
+            .iter()
+            .enumerate()
+            .fold(0u64, |acc, (i, v)| acc.wrapping_add(v % (i as u64 + 100)));
+        let lottery = self.alphabet[(values_hash % self.alphabet.len() as u64) as usize];
+        let mut encoded = vec![lottery];
+
+        let mut alphabet = self.alphabet.clone();
+        for (i, &value) in values.iter().enumerate() {
+            let mut alphabet_salt = self.alphabet_salt(lottery, &alphabet);
+            alphabet = reorder(&alphabet, &alphabet_salt);
+            wipe_chars(&mut alphabet_salt);
+            let mut part = hash(value, &alphabet);
+            let separator = value % (u64::from(u32::from(part[0])) + i as u64);
+            encoded.append(&mut part);
+            encoded.push(self.separators[(separator % self.separators.len() as u64) as usize]);
+        }
+        encoded.pop();
+
+        if encoded.len() < min_length {
+            let mut padded: VecDeque<char> = encoded.into();
+            let guard = |c: char| {
+                let index = values_hash.wrapping_add(u64::from(u32::from(c)));
+                self.guards[(index % self.guards.len() as u64) as usize]
+            };
+            padded.push_front(guard(padded[0]));
+            if padded.len() < min_length {
+                padded.push_back(guard(padded[2]));
+            }
+
+            let split_at = alphabet.len() / 2;
+            while padded.len() < min_length {
+                alphabet = reorder(&alphabet, &alphabet);
+                for &c in alphabet[split_at..].iter().rev() {
+                    padded.push_front(c);
+                }
+                padded.extend(&alphabet[..split_at]);
+                let excess = padded.len().saturating_sub(min_length);
+                if excess > 0 {
+                    let from = excess / 2;
+                    padded = padded.drain(from..from + min_length).collect();
+                }
+            }
+            encoded = padded.into();
+        }
+        wipe_chars(&mut alphabet);
+        encoded.into_iter().collect()
+    }
+
+    /// The number of values `hash` holds if it is valid: separators only ever appear
+    /// between two values. Counting them is cheap, so oversized hashes can be turned away
+    /// before they are decoded.
+    pub(crate) fn count_values(&self, hash: &str) -> usize {
+        hash.chars().filter(|c| self.separators.contains(c)).count() + 1
+    }
+
+    /// Decodes `hash` like `hash_ids::HashIds::decode`, but reports values that don't fit
+    /// in a `u64` as invalid instead of overflowing.
+    ///
+    /// Unlike `hash_ids`, characters outside the alphabet are rejected anywhere in the
+    /// hash, including the padding that decoding otherwise skips.
+    pub(crate) fn decode(&self, hash: &str) -> Result<Vec<u64>, Error> {
+        if hash.is_empty() {
+            return Ok(vec![]);
+        }
+        if !hash.chars().all(|c| self.charset.contains(&c)) {
+            return Err(Error::InvalidHash);
+        }
+
+        let mut parts = split(hash.chars(), &self.guards);
+        let mut hash = if (2..=3).contains(&parts.len()) {
+            parts.remove(1)
+        } else {
+            parts.remove(0)
+        };
+        if hash.is_empty() {
+            return Err(Error::MissingLotteryChar);
+        }
+        let lottery = hash.remove(0);
+
+        let parts = split(hash.into_iter(), &self.separators);
+        let mut alphabet = self.alphabet.clone();
+        let mut out = Vec::with_capacity(parts.len());
+        let mut result = Ok(());
+        for part in parts {
+            let mut alphabet_salt = self.alphabet_salt(lottery, &alphabet);
+            alphabet = reorder(&alphabet, &alphabet_salt);
+            wipe_chars(&mut alphabet_salt);
+            match unhash(&part, &alphabet) {
+                Some(value) => out.push(value),
+                None => {
+                    result = Err(Error::InvalidHash);
+                    break;
+                }
+            }
+        }
+        wipe_chars(&mut alphabet);
+        result.map(|()| out)
+    }
+
+    /// The salt the working alphabet is shuffled with before each value.
+    fn alphabet_salt(&self, lottery: char, alphabet: &[char]) -> Vec<char> {
+        std::iter::once(lottery)
+            .chain(self.salt.iter().copied())
+            .chain(alphabet.iter().copied())
+            .take(alphabet.len())
+            .collect()
+    }
+}
+
+impl fmt::Debug for Codec {
+    /// Shows only the charset; everything else is derived from the salt.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Codec")
+            .field("charset", &self.charset.iter().collect::<String>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for Codec {
+    fn drop(&mut self) {
+        wipe_chars(&mut self.charset);
+        wipe_chars(&mut self.salt);
+        wipe_chars(&mut self.alphabet);
+        wipe_chars(&mut self.separators);
+        wipe_chars(&mut self.guards);
+    }
+}
+
+fn index_from_ratio(dividend: usize, divisor: f32) -> usize {
+    (dividend as f32 / divisor).ceil() as usize
+}
+
+/// The salted shuffle hashids applies to its alphabets.
+fn reorder(string: &[char], salt: &[char]) -> Vec<char> {
+    let mut out = string.to_vec();
+    if salt.is_empty() {
+        return out;
+    }
+
+    let mut int_sum = 0;
+    let mut index = 0;
+    for i in (1..string.len()).rev() {
+        let int = u32::from(salt[index]) as usize;
+        int_sum += int;
+        let j = (int + index + int_sum) % i;
+        out.swap(i, j);
+        index = (index + 1) % salt.len();
+    }
+    out
+}
+
+fn split(string: impl Iterator<Item = char>, splitters: &[char]) -> Vec<Vec<char>> {
+    let mut parts = Vec::new();
+    let mut buf = Vec::new();
+    for c in string {
+        if splitters.contains(&c) {
+            parts.push(std::mem::take(&mut buf));
+        } else {
+            buf.push(c);
+        }
+    }
+    parts.push(buf);
+    parts
+}
+
+/// Converts `number` into characters of `alphabet`, most significant first.
+fn hash(mut number: u64, alphabet: &[char]) -> Vec<char> {
+    let base = alphabet.len() as u64;
+    let mut hashed = VecDeque::new();
+    loop {
+        hashed.push_front(alphabet[(number % base) as usize]);
+        number /= base;
+        if number == 0 {
+            break;
+        }
+    }
+    hashed.into()
+}
+
+/// Converts one part back into its value, or `None` if it contains a character outside
+/// `alphabet` or doesn't fit in a `u64`.
+fn unhash(part: &[char], alphabet: &[char]) -> Option<u64> {
+    let base = alphabet.len() as u64;
+    part.iter().try_fold(0u64, |number, c| {
+        let position = alphabet.iter().position(|a| a == c)? as u64;
+        number.checked_mul(base)?.checked_add(position)
+    })
+}
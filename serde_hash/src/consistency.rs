@@ -0,0 +1,130 @@
+//! Tokens that tell whether two processes produce and accept the same hashes.
+//!
+//! A service deployed with a stale salt or another minimum length still starts, and only
+//! fails once it receives ids from the rest of the fleet. [`consistency_token`] digests
+//! every setting that changes the hashes, so services can compare tokens at startup or in
+//! health checks. The token is keyed with the salt, so it can be logged and shared without
+//! revealing it.
+//!
+//! # Example
+//! ```
+//! use serde_hash::hashids::SerdeHashOptions;
+//!
+//! SerdeHashOptions::new().with_salt("replace-with-a-random-salt").with_min_length(10).build();
+//!
+//! // E.g. read from a config service that every instance reports to.
+//! let expected = serde_hash::consistency_token();
+//! serde_hash::verify_consistency_token(&expected).unwrap();
+//! ```
+
+use crate::error::{Result, SerdeHashError};
+use crate::hashids::{SerdeHashOptions, get_hash_options};
+use crate::provider::active_context;
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::Sha256;
+
+/// Version of the hash layout, bumped whenever an update changes the hashes produced for
+/// the same options.
+const BACKEND_VERSION: u32 = 1;
+
+/// Returns the consistency token of the active options, see
+/// [`SerdeHashOptions::consistency_token`].
+pub fn consistency_token() -> String {
+    match active_context() {
+        Some(context) => context.options().consistency_token(),
+        None => get_hash_options().consistency_token(),
+    }
+}
+
+/// Checks `token` against the consistency token of the active options.
+///
+/// # Arguments
+///
+/// * `token` - A token from [`consistency_token`], e.g. reported by another service.
+///
+/// # Returns
+///
+/// * `Ok(())` if the active options produce and accept the same hashes as the options the
+///   token came from.
+/// * [`SerdeHashError::ConsistencyMismatch`] with both tokens otherwise.
+pub fn verify_consistency_token(token: impl AsRef<str>) -> Result<()> {
+    let (expected, actual) = (token.as_ref(), consistency_token());
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(SerdeHashError::ConsistencyMismatch {
+            expected: expected.to_string(),
+            actual,
+        })
+    }
+}
+
+impl SerdeHashOptions {
+    /// Returns a digest of the settings that change the hashes, such as the salt, alphabet,
+    /// length rule and hashing backend, as well as the field patterns and MessagePack
+    /// extension type that decide which fields are hashed and how they're written.
+    ///
+    /// Options with the same token encode every value to the same hash and decode the same
+    /// hashes. The token is the same across processes, platforms and releases, unless a
+    /// release changes the hashes themselves. Settings that only reject input, such as the
+    /// maximum length or [`SerdeHashOptions::with_reject_zero`], don't change the token.
+    ///
+    /// # Returns
+    ///
+    /// * A string like `"v1-"` followed by 32 hex characters.
+    pub fn consistency_token(&self) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.salt().as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(&BACKEND_VERSION.to_be_bytes());
+        update_str(&mut mac, &self.backend_name());
+        update_str(&mut mac, self.alphabet());
+        // The exact length replaces the minimum length, so only the rule in effect counts.
+        let (rule, length) = match self.exact_length {
+            Some(length) => (1, length),
+            None => (0, self.min_length),
+        };
+        mac.update(&[rule]);
+        mac.update(&(length as u64).to_be_bytes());
+        mac.update(&[
+            self.checksum as u8,
+            self.case_insensitive_decode as u8,
+            self.crockford_decode as u8,
+        ]);
+        match self.msgpack_ext {
+            Some(tag) => mac.update(&[1, tag as u8]),
+            None => mac.update(&[0]),
+        }
+        // The patterns pick which keys are hashed, whatever order they were given in.
+        let mut patterns: Vec<&str> = self.field_patterns.iter().map(String::as_str).collect();
+        patterns.sort_unstable();
+        patterns.dedup();
+        mac.update(&(patterns.len() as u64).to_be_bytes());
+        for pattern in patterns {
+            update_str(&mut mac, pattern);
+        }
+        let digest = mac.finalize().into_bytes();
+        let hex: String = digest[..16].iter().map(|b| format!("{:02x}", b)).collect();
+        format!("v{}-{}", BACKEND_VERSION, hex)
+    }
+
+    /// Names the algorithm producing the hashes, like [`crate::encoder::DecodeBackend`].
+    fn backend_name(&self) -> String {
+        #[cfg(feature = "testing")]
+        if let Some(mock) = &self.mock_codec {
+            return format!("mock:{}", mock.encode(&[]));
+        }
+        #[cfg(feature = "testing")]
+        if self.placeholders {
+            return "placeholders".to_string();
+        }
+        let name = if self.js_compat { "hashids.js" } else { "hashids" };
+        name.to_string()
+    }
+}
+
+/// Feeds `value` to `mac` with its length in front, so adjacent strings can't run into
+/// each other.
+fn update_str(mac: &mut Hmac<Sha256>, value: &str) {
+    mac.update(&(value.len() as u64).to_be_bytes());
+    mac.update(value.as_bytes());
+}
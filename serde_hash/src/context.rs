@@ -0,0 +1,138 @@
+//! Validated hash configurations that can be stored and shared.
+//!
+//! With the `tokio` feature, a context can also be scoped to a task with [`with_context`].
+//! Every encode and decode inside the task, including the ones serde runs for `hash`
+//! fields, then uses the task's context instead of the global options. This lets a
+//! multi-tenant server hash each request with its tenant's salt without threading the
+//! context through serialization.
+//!
+//! # Example
+//! ```ignore
+//! use serde_hash::context::with_context;
+//!
+//! let tenant = tenants.context_for(&request);
+//! let body = with_context(tenant, async { serde_json::to_string(&user) }).await?;
+//! ```
+
+use crate::encoder::HashIdsEncoder;
+use crate::error::{Result, SerdeHashError};
+use crate::hashids::SerdeHashOptions;
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// A validated configuration together with the encoder built from it.
+///
+/// Unlike the global options, any number of contexts can exist at once, e.g. one per
+/// tenant held in application state. Cloning a context is cheap and shares the encoder.
+/// Create one with [`SerdeHashOptions::into_context`].
+///
+/// # Example
+/// ```
+/// use serde_hash::hashids::SerdeHashOptions;
+///
+/// let base = SerdeHashOptions::new().with_min_length(10);
+/// let acme = base.clone().with_salt("acme").into_context().unwrap();
+/// let globex = base.with_salt("globex").into_context().unwrap();
+///
+/// let hash = acme.encode_single(42);
+/// assert_eq!(acme.decode_single(&hash).unwrap(), 42);
+/// assert_ne!(globex.encode_single(42), hash);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HashContext {
+    options: Arc<SerdeHashOptions>,
+    encoder: Arc<HashIdsEncoder>,
+    /// Encoders for `min_length` overrides, keyed by minimum length.
+    overrides: Arc<RwLock<HashMap<usize, Arc<HashIdsEncoder>>>>,
+}
+
+impl HashContext {
+    pub(crate) fn new(options: SerdeHashOptions) -> Result<Self, SerdeHashError> {
+        let encoder = HashIdsEncoder::new(&options)?;
+        Ok(Self {
+            options: Arc::new(options),
+            encoder: Arc::new(encoder),
+            overrides: Default::default(),
+        })
+    }
+
+    /// Returns the encoder for these options at a different minimum length.
+    pub(crate) fn encoder_with_min_length(&self, min_length: usize) -> Arc<HashIdsEncoder> {
+        if min_length == self.options.min_length {
+            return self.encoder.clone();
+        }
+        let overrides = self.overrides.read().unwrap_or_else(PoisonError::into_inner);
+        if let Some(encoder) = overrides.get(&min_length) {
+            return encoder.clone();
+        }
+        drop(overrides);
+        let encoder = HashIdsEncoder::with_min_length(&self.options, min_length)
+            .expect("context options are validated when the context is created");
+        self.overrides
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(min_length)
+            .or_insert_with(|| Arc::new(encoder))
+            .clone()
+    }
+
+    /// Returns the options this context was built from.
+    pub fn options(&self) -> &SerdeHashOptions {
+        &self.options
+    }
+
+    /// Returns the encoder built from the options.
+    pub fn encoder(&self) -> &Arc<HashIdsEncoder> {
+        &self.encoder
+    }
+
+    /// Encodes a slice of `u64` integers into a hash string.
+    pub fn encode(&self, data: &[u64]) -> String {
+        self.encoder.encode(data)
+    }
+
+    /// Decodes a hash string into a vector of `u64` integers.
+    pub fn decode(&self, hash: impl AsRef<str>) -> Result<Vec<u64>> {
+        self.encoder.decode(hash)
+    }
+
+    /// Encodes a single `u64` value into a hash string.
+    pub fn encode_single(&self, data: u64) -> String {
+        self.encoder.encode_single(data)
+    }
+
+    /// Decodes a hash string into a single `u64` value.
+    pub fn decode_single(&self, hash: impl AsRef<str>) -> Result<u64> {
+        self.encoder.decode_single(hash)
+    }
+}
+
+#[cfg(feature = "tokio")]
+::tokio::task_local! {
+    static CONTEXT: HashContext;
+}
+
+/// Runs `future` with `context` in place of the global options.
+///
+/// Encodes and decodes that go through the global options, including the ones serde runs
+/// for `hash` fields, use `context` while `future` is polled. Contexts nest; the innermost
+/// one applies.
+#[cfg(feature = "tokio")]
+pub async fn with_context<F: std::future::Future>(context: HashContext, future: F) -> F::Output {
+    CONTEXT.scope(context, future).await
+}
+
+/// Runs `f` with `context` in place of the global options, like [`with_context`] for
+/// synchronous code, e.g. inside `spawn_blocking`.
+#[cfg(feature = "tokio")]
+pub fn with_context_sync<R>(context: HashContext, f: impl FnOnce() -> R) -> R {
+    CONTEXT.sync_scope(context, f)
+}
+
+/// Returns the context scoped to the current task, if any.
+pub fn current_context() -> Option<HashContext> {
+    #[cfg(feature = "tokio")]
+    return CONTEXT.try_with(HashContext::clone).ok();
+    #[cfg(not(feature = "tokio"))]
+    None
+}
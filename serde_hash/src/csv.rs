@@ -0,0 +1,152 @@
+//! Helpers for writing and reading hashed records with the [`csv`](https://docs.rs/csv) crate.
+//!
+//! CSV records are flat, so a field can't hold a nested sequence. The plain `numeric` and
+//! `option_numeric` modules in [`crate::serde_impl`] already produce a single string per
+//! field and work with `csv` as-is. Vector fields need one of the modules below, which pack
+//! every hash of the vector into one cell separated by [`SEPARATOR`].
+//!
+//! # Example
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use serde_hash::hashids::SerdeHashOptions;
+//! use serde_hash::serde_hash;
+//!
+//! #[serde_hash]
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! pub struct Export {
+//!     #[serde(hash)]
+//!     pub id: u64,
+//!     #[serde(with = "serde_hash::csv::vec_numeric")]
+//!     pub friends: Vec<u64>,
+//! }
+//!
+//! SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+//!
+//! let rows = vec![Export { id: 1, friends: vec![2, 3] }];
+//! let mut buffer = Vec::new();
+//! serde_hash::csv::to_writer(&mut buffer, &rows).unwrap();
+//!
+//! let decoded: Vec<Export> = serde_hash::csv::from_reader(buffer.as_slice()).unwrap();
+//! assert_eq!(decoded, rows);
+//! ```
+
+use crate::hashids::encode;
+use crate::serde_impl::{
+    HashNumeric, check_max_length, decode_field_values, encode_numeric, numeric_from_values,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::io;
+
+/// Separator placed between the hashes of a vector field.
+///
+/// Hash alphabets may never contain spaces, so a space can't collide with an encoded value.
+pub const SEPARATOR: char = ' ';
+
+fn join<T: HashNumeric, E: serde::ser::Error>(values: &[T]) -> Result<String, E> {
+    let hashes = values
+        .iter()
+        .map(|v| {
+            let hash = encode_numeric(*v, encode);
+            check_max_length(&hash).map(|()| hash)
+        })
+        .collect::<Result<Vec<_>, E>>()?;
+    Ok(hashes.join(&SEPARATOR.to_string()))
+}
+
+fn split<T: HashNumeric, E: serde::de::Error>(cell: &str) -> Result<Vec<T>, E> {
+    cell.split(SEPARATOR)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let decoded = decode_field_values(s).map_err(E::custom)?;
+            numeric_from_values(&decoded, None)
+        })
+        .collect()
+}
+
+/// Serde `with` module for `Vec<T>` fields stored in a single CSV cell.
+///
+/// Usage: `#[serde(with = "serde_hash::csv::vec_numeric")]`
+pub mod vec_numeric {
+    use super::*;
+
+    pub fn serialize<T: HashNumeric, S: Serializer>(
+        value: &[T],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&join(value)?)
+    }
+
+    pub fn deserialize<'de, T: HashNumeric, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<T>, D::Error> {
+        let cell = String::deserialize(deserializer)?;
+        split(&cell)
+    }
+}
+
+/// Serde `with` module for `Option<Vec<T>>` fields stored in a single CSV cell.
+///
+/// `None` is written as an empty cell. Note that an empty cell reads back as `None`,
+/// so `Some(vec![])` does not survive a round trip.
+///
+/// Usage: `#[serde(with = "serde_hash::csv::option_vec_numeric")]`
+pub mod option_vec_numeric {
+    use super::*;
+
+    pub fn serialize<T: HashNumeric, S: Serializer>(
+        value: &Option<Vec<T>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(vec) => serializer.serialize_some(&join(vec)?),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, T: HashNumeric, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<T>>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(cell) => Ok(Some(split(&cell)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Writes `records` to `writer` as CSV with a header row.
+///
+/// # Arguments
+///
+/// * `writer` - The destination for the CSV output.
+/// * `records` - The records to write; hash fields are encoded on the way out.
+pub fn to_writer<W, T, I>(writer: W, records: I) -> ::csv::Result<()>
+where
+    W: io::Write,
+    T: Serialize,
+    I: IntoIterator<Item = T>,
+{
+    let mut writer = ::csv::Writer::from_writer(writer);
+    for record in records {
+        writer.serialize(record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads every record of a CSV document with a header row.
+///
+/// # Arguments
+///
+/// * `reader` - The source of the CSV input.
+///
+/// # Returns
+///
+/// The decoded records, or the first error encountered (including hash decode failures).
+pub fn from_reader<R, T>(reader: R) -> ::csv::Result<Vec<T>>
+where
+    R: io::Read,
+    T: DeserializeOwned,
+{
+    ::csv::Reader::from_reader(reader).deserialize().collect()
+}
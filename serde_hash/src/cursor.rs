@@ -0,0 +1,184 @@
+//! Opaque pagination cursors.
+//!
+//! A [`Cursor`] packs a position (an offset or the last id seen), a timestamp and a
+//! direction into one hash string using the global options, so APIs can hand out
+//! `next`/`prev` tokens without exposing how pagination works underneath.
+//!
+//! # Example
+//! ```
+//! use serde_hash::cursor::{Cursor, Direction};
+//! use serde_hash::hashids::SerdeHashOptions;
+//!
+//! SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+//!
+//! let cursor = Cursor::<u64>::with_timestamp(158674, 1_700_000_000, Direction::Forward);
+//! let token = cursor.encode();
+//! assert_eq!(Cursor::<u64>::decode(&token).unwrap(), cursor);
+//! ```
+
+use crate::entity::PublicId;
+use crate::error::{Result, SerdeHashError};
+use crate::hashids::{decode, encode};
+use crate::hook::report_decode_error;
+use crate::serde_impl::{HashNumeric, join_limbs, numeric_from_u128, serialize_hash};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The direction a cursor pages in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Items after the position.
+    Forward,
+    /// Items before the position.
+    Backward,
+}
+
+impl Direction {
+    fn to_u64(self) -> u64 {
+        match self {
+            Self::Forward => 0,
+            Self::Backward => 1,
+        }
+    }
+
+    fn from_u64(value: u64) -> Option<Self> {
+        match value {
+            0 => Some(Self::Forward),
+            1 => Some(Self::Backward),
+            _ => None,
+        }
+    }
+}
+
+/// A pagination position that serializes as a single hash string.
+///
+/// `T` is the type of the position, usually a `u64` offset or the id of the last item
+/// on the previous page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cursor<T> {
+    /// The offset or last id the next page starts from.
+    pub position: T,
+    /// Seconds since the Unix epoch when the cursor was created.
+    pub timestamp: u64,
+    /// Which way the next page goes.
+    pub direction: Direction,
+}
+
+impl<T: HashNumeric> Cursor<T> {
+    /// Creates a cursor at `position`, timestamped with the current time.
+    pub fn new(position: T, direction: Direction) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        Self::with_timestamp(position, timestamp, direction)
+    }
+
+    /// Creates a cursor at `position` pointing forward, timestamped with the current time.
+    pub fn forward(position: T) -> Self {
+        Self::new(position, Direction::Forward)
+    }
+
+    /// Creates a cursor at `position` pointing backward, timestamped with the current time.
+    pub fn backward(position: T) -> Self {
+        Self::new(position, Direction::Backward)
+    }
+
+    /// Creates a cursor with an explicit timestamp, in seconds since the Unix epoch.
+    pub fn with_timestamp(position: T, timestamp: u64, direction: Direction) -> Self {
+        Self {
+            position,
+            timestamp,
+            direction,
+        }
+    }
+
+    /// Encodes the cursor into an opaque hash string.
+    ///
+    /// A `u128` position above `u64::MAX` takes both of its limbs, so the hash holds four
+    /// values instead of three.
+    pub fn encode(&self) -> String {
+        let (timestamp, direction) = (self.timestamp, self.direction.to_u64());
+        match self.position.to_limbs() {
+            [0, low] => encode(&[low, timestamp, direction]),
+            [high, low] => encode(&[high, low, timestamp, direction]),
+        }
+    }
+
+    /// Decodes a cursor produced by [`Cursor::encode`].
+    ///
+    /// # Returns
+    ///
+    /// An error if the hash is invalid or doesn't hold a position, timestamp and
+    /// direction.
+    pub fn decode(hash: impl AsRef<str>) -> Result<Self> {
+        let values = decode(hash)?;
+        let (position, timestamp, direction) = match values[..] {
+            [position, timestamp, direction] => (position as u128, timestamp, direction),
+            [high, low, timestamp, direction] if high != 0 => {
+                (join_limbs(high, low), timestamp, direction)
+            }
+            _ => {
+                return Err(SerdeHashError::InvalidHash(format!(
+                    "expected 3 values in cursor, found {}",
+                    values.len()
+                )));
+            }
+        };
+        let Some(direction) = Direction::from_u64(direction) else {
+            return Err(SerdeHashError::InvalidHash(format!(
+                "invalid cursor direction {}",
+                direction
+            )));
+        };
+        let Some(position) = numeric_from_u128(position) else {
+            return Err(SerdeHashError::InvalidHash(format!(
+                "cursor position {} does not fit in {}",
+                position,
+                std::any::type_name::<T>()
+            )));
+        };
+        Ok(Self::with_timestamp(position, timestamp, direction))
+    }
+}
+
+impl<T: HashNumeric> fmt::Display for Cursor<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl<T: HashNumeric> FromStr for Cursor<T> {
+    type Err = SerdeHashError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::decode(s)
+    }
+}
+
+impl<T: HashNumeric> PublicId for Cursor<T> {
+    type Id = Self;
+
+    fn as_public_id(&self) -> String {
+        self.encode()
+    }
+
+    fn parse_public_id(public_id: &str) -> Result<Self> {
+        Self::decode(public_id)
+    }
+}
+
+impl<T: HashNumeric> Serialize for Cursor<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_hash(&self.encode(), serializer)
+    }
+}
+
+impl<'de, T: HashNumeric> Deserialize<'de> for Cursor<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hash = String::deserialize(deserializer)?;
+        report_decode_error(&hash, Self::decode(&hash)).map_err(serde::de::Error::custom)
+    }
+}
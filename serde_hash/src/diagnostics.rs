@@ -0,0 +1,128 @@
+//! Explanations of why a hash does or doesn't decode.
+//!
+//! "Invalid hash" errors from production rarely say enough to tell a truncated id from
+//! one produced with another salt. [`explain`] checks a hash against the active options
+//! step by step and reports what it found.
+//!
+//! # Example
+//! ```
+//! use serde_hash::hashids::SerdeHashOptions;
+//!
+//! SerdeHashOptions::new().with_salt("replace-with-a-random-salt").with_min_length(10).build();
+//!
+//! let explanation = serde_hash::explain("not-a-hash!");
+//! assert!(!explanation.matches_alphabet());
+//! println!("{}", explanation);
+//! ```
+
+use crate::hashids::{current_encoder, get_hash_options};
+use crate::provider::active_context;
+use std::fmt;
+
+/// What [`explain`] found out about a hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    /// The hash that was explained.
+    pub hash: String,
+    /// The length of the hash, in characters.
+    pub length: usize,
+    /// The minimum length the active options produce.
+    pub min_length: usize,
+    /// Characters of the hash that aren't in the alphabet, each listed once.
+    pub invalid_chars: Vec<char>,
+    /// The decoded values, if the hash decoded.
+    pub values: Option<Vec<u64>>,
+    /// Why decoding failed, if it did.
+    pub error: Option<String>,
+}
+
+impl Explanation {
+    /// Returns `true` if every character of the hash is in the alphabet.
+    pub fn matches_alphabet(&self) -> bool {
+        self.invalid_chars.is_empty()
+    }
+
+    /// Returns `true` if the hash is at least as long as the minimum length.
+    pub fn meets_min_length(&self) -> bool {
+        self.length >= self.min_length
+    }
+
+    /// Returns how many values the hash decodes to, or `None` if it doesn't decode.
+    pub fn value_count(&self) -> Option<usize> {
+        self.values.as_ref().map(Vec::len)
+    }
+
+    /// Returns `true` if the hash decodes with the active options.
+    pub fn is_valid(&self) -> bool {
+        self.values.is_some()
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "hash {:?}", self.hash)?;
+        if self.matches_alphabet() {
+            writeln!(f, "  alphabet:   ok")?;
+        } else {
+            writeln!(f, "  alphabet:   invalid characters {:?}", self.invalid_chars)?;
+        }
+        let verdict = if self.meets_min_length() { "ok" } else { "too short" };
+        writeln!(
+            f,
+            "  length:     {} (minimum {}, {})",
+            self.length, self.min_length, verdict
+        )?;
+        match (&self.values, &self.error) {
+            (Some(values), _) => write!(f, "  decoded:    {} value(s) {:?}", values.len(), values),
+            (None, Some(error)) => write!(f, "  decoded:    failed: {}", error),
+            (None, None) => write!(f, "  decoded:    failed"),
+        }
+    }
+}
+
+/// Explains how `hash` fares against the active options: whether its characters are in
+/// the alphabet, whether it meets the minimum length, and what it decodes to.
+///
+/// The active options are the task's context, with the `tokio` feature, or the global
+/// options. Unlike deserialization, explaining a hash never calls the decode error hook.
+///
+/// # Arguments
+///
+/// * `hash` - The hash to explain, e.g. one taken from a failing request.
+///
+/// # Returns
+///
+/// An [`Explanation`], whose `Display` output is a short multi-line report.
+pub fn explain(hash: impl AsRef<str>) -> Explanation {
+    let hash = hash.as_ref();
+    let context = active_context();
+    let options = match &context {
+        Some(context) => context.options(),
+        None => get_hash_options(),
+    };
+    let in_alphabet = |c: char| {
+        if options.case_insensitive_decode {
+            options.alphabet().chars().any(|a| a.eq_ignore_ascii_case(&c))
+        } else {
+            options.alphabet().contains(c)
+        }
+    };
+    let mut invalid_chars = Vec::new();
+    for c in hash.chars() {
+        if !in_alphabet(c) && !invalid_chars.contains(&c) {
+            invalid_chars.push(c);
+        }
+    }
+    let (values, error) = match current_encoder().decode(hash) {
+        Ok(values) => (Some(values), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    Explanation {
+        hash: hash.to_string(),
+        length: hash.chars().count(),
+        min_length: options.min_length,
+        invalid_chars,
+        values,
+        error,
+    }
+}
@@ -0,0 +1,60 @@
+//! Rendering hashed ids with `Display`, e.g. in templates.
+//!
+//! Template engines such as askama and tera format values with `Display` (or serialize
+//! them into their own context), so wrapping an id in [`HashDisplay`] is enough to render
+//! its hash in a link without going through `serde_json` first. The hash uses the same
+//! options as `hash` fields.
+//!
+//! # Example
+//! ```
+//! use serde_hash::HashDisplay;
+//! use serde_hash::hashids::{SerdeHashOptions, decode_single};
+//!
+//! SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+//!
+//! let link = format!("/users/{}", HashDisplay(42));
+//! assert_eq!(decode_single(link.trim_start_matches("/users/")).unwrap(), 42);
+//! ```
+
+use crate::entity::PublicId;
+use crate::hashids::{decode_single, encode_single};
+use crate::serde_impl::numeric;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+/// An id that displays and serializes as its hash.
+///
+/// With the `rkyv` feature it can be archived with rkyv, which stores the id unhashed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct HashDisplay(pub u64);
+
+impl From<u64> for HashDisplay {
+    fn from(id: u64) -> Self {
+        Self(id)
+    }
+}
+
+impl fmt::Display for HashDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&encode_single(self.0))
+    }
+}
+
+impl Serialize for HashDisplay {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        numeric::serialize(&self.0, serializer)
+    }
+}
+
+impl PublicId for HashDisplay {
+    type Id = Self;
+
+    fn as_public_id(&self) -> String {
+        encode_single(self.0)
+    }
+
+    fn parse_public_id(public_id: &str) -> crate::Result<Self> {
+        decode_single(public_id).map(Self)
+    }
+}
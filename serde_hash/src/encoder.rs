@@ -0,0 +1,588 @@
+use crate::alphabet::crockford_symbol;
+use crate::codec::Codec;
+use crate::error::{Result, SerdeHashError};
+use crate::hashids::SerdeHashOptions;
+use crate::patterns::FieldPatterns;
+use crate::secret::wipe_string;
+use crate::telemetry::{record_decode, record_encode};
+#[cfg(feature = "log")]
+use log::{debug, warn};
+
+/// How a hash was decoded, returned by [`HashIdsEncoder::decode_single_with_report`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DecodeReport {
+    /// The algorithm that decoded the hash.
+    pub backend: DecodeBackend,
+    /// The length of the hash in characters, including any check character.
+    pub length: usize,
+}
+
+/// The algorithm behind a [`DecodeReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeBackend {
+    /// The `hash_ids` layout, the default.
+    Hashids,
+    /// The hashids.js layout, see [`SerdeHashOptions::with_js_compat`].
+    HashidsJs,
+    /// Readable placeholders, see [`SerdeHashOptions::with_placeholders`].
+    #[cfg(feature = "testing")]
+    Placeholder,
+    /// A mock codec, see [`SerdeHashOptions::with_mock_codec`].
+    #[cfg(feature = "testing")]
+    Mock,
+}
+
+/// A hash encoder/decoder bound to one configuration.
+///
+/// The free functions in [`crate::hashids`] use an encoder built from the global options.
+/// Build one yourself when you need hashes from a configuration other than the global one.
+#[derive(Debug)]
+pub struct HashIdsEncoder {
+    codec: Codec,
+    alphabet: String,
+    min_length: usize,
+    case_insensitive_decode: bool,
+    crockford_decode: bool,
+    constant_time_decode: bool,
+    checksum: bool,
+    js_compat: bool,
+    field_patterns: FieldPatterns,
+    exact_length: Option<usize>,
+    reject_zero: bool,
+    msgpack_ext: Option<i8>,
+    max_length: Option<usize>,
+    max_decoded_values: usize,
+    #[cfg(feature = "testing")]
+    placeholders: bool,
+    #[cfg(feature = "testing")]
+    mock_codec: Option<crate::testing::MockCodec>,
+}
+
+impl HashIdsEncoder {
+    /// Creates an encoder from the given options.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The configuration to encode and decode with.
+    ///
+    /// # Returns
+    ///
+    /// * The encoder, or the validation error if the options are unusable.
+    pub fn new(options: &SerdeHashOptions) -> Result<Self, SerdeHashError> {
+        let Some(exact_length) = options.exact_length else {
+            return Self::with_min_length(options, options.min_length);
+        };
+        // The check character comes on top of the padded hash.
+        let min_length = exact_length.saturating_sub(usize::from(options.checksum));
+        let mut encoder = Self::with_min_length(options, min_length)?;
+        encoder.exact_length = Some(exact_length);
+        Ok(encoder)
+    }
+
+    /// Creates an encoder from the given options, but with a different minimum length.
+    pub(crate) fn with_min_length(
+        options: &SerdeHashOptions,
+        min_length: usize,
+    ) -> Result<Self, SerdeHashError> {
+        options.validate()?;
+        let field_patterns = FieldPatterns::new(&options.field_patterns)?;
+        Ok(Self {
+            codec: Codec::new(options.alphabet(), options.salt(), options.js_compat),
+            alphabet: options.alphabet().to_string(),
+            min_length,
+            case_insensitive_decode: options.case_insensitive_decode,
+            crockford_decode: options.crockford_decode,
+            constant_time_decode: options.constant_time_decode,
+            checksum: options.checksum,
+            js_compat: options.js_compat,
+            field_patterns,
+            exact_length: None,
+            reject_zero: options.reject_zero,
+            msgpack_ext: options.msgpack_ext,
+            max_length: options.max_length,
+            max_decoded_values: options.max_decoded_values,
+            #[cfg(feature = "testing")]
+            placeholders: options.placeholders,
+            #[cfg(feature = "testing")]
+            mock_codec: options.mock_codec.clone(),
+        })
+    }
+
+    /// Returns the compiled [`SerdeHashOptions::field_patterns`], for code that decides
+    /// itself which keys of a payload to hash.
+    pub fn field_patterns(&self) -> &FieldPatterns {
+        &self.field_patterns
+    }
+
+    /// Whether serializing a hash field of 0 fails, see
+    /// [`SerdeHashOptions::with_reject_zero`].
+    pub fn rejects_zero(&self) -> bool {
+        self.reject_zero
+    }
+
+    /// The MessagePack extension type hash fields are written as, see
+    /// [`SerdeHashOptions::with_msgpack_ext`].
+    pub fn msgpack_ext(&self) -> Option<i8> {
+        self.msgpack_ext
+    }
+
+    /// Encodes a slice of `u64` integers into a hash string.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - A slice of `u64` integers to be encoded.
+    ///
+    /// # Returns
+    ///
+    /// A string that represents the encoded hash of the input data.
+    pub fn encode(&self, data: &[u64]) -> String {
+        let encode = record_encode(|| {
+            #[cfg(feature = "testing")]
+            if let Some(mock) = &self.mock_codec {
+                return mock.encode(data);
+            }
+            #[cfg(feature = "testing")]
+            if self.placeholders {
+                return encode_placeholder(data);
+            }
+            let mut encode = self.codec.encode(data, self.min_length);
+            if self.checksum {
+                encode.push(self.check_char(&encode).0);
+            }
+            encode
+        });
+        #[cfg(feature = "log")]
+        {
+            debug!("Encoding: {:?} -> {}", data, encode);
+            if let Some(exact) = self.exact_length
+                && encode.chars().count() > exact
+            {
+                warn!("Hash of {:?} doesn't fit the exact length of {}: {}", data, exact, encode);
+            }
+            if let Err(e) = self.check_max_length(&encode) {
+                warn!("Hash of {:?} is too long: {}", data, e);
+            }
+        }
+        encode
+    }
+
+    /// Like [`HashIdsEncoder::encode`], but fails instead of returning a hash longer than
+    /// the [`SerdeHashOptions::with_exact_length`] or
+    /// [`SerdeHashOptions::with_max_length`] setting.
+    ///
+    /// # Returns
+    ///
+    /// * The hash string.
+    /// * [`SerdeHashError::ExactLengthExceeded`] if the values don't fit the exact length.
+    /// * [`SerdeHashError::MaxLengthExceeded`] if the hash is longer than the maximum.
+    pub fn try_encode(&self, data: &[u64]) -> Result<String> {
+        let encode = self.encode(data);
+        let length = encode.chars().count();
+        match self.exact_length {
+            Some(exact) if length > exact => {
+                Err(SerdeHashError::ExactLengthExceeded { length, exact })
+            }
+            _ => self.check_max_length(&encode).map(|()| encode),
+        }
+    }
+
+    /// Fails for a hash longer than the [`SerdeHashOptions::with_max_length`] setting.
+    pub(crate) fn check_max_length(&self, hash: &str) -> Result<()> {
+        match self.max_length {
+            Some(max) if hash.chars().count() > max => Err(SerdeHashError::MaxLengthExceeded {
+                length: hash.chars().count(),
+                max,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Decodes a given hash string into a vector of `u64` integers.
+    ///
+    /// # Arguments
+    ///
+    /// * `hash` - A string slice that holds the hash to be decoded.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `u64` integers that were encoded in the given hash string.
+    pub fn decode(&self, hash: impl AsRef<str>) -> Result<Vec<u64>> {
+        record_decode(self.decode_inner(hash.as_ref(), self.max_decoded_values))
+    }
+
+    /// Decodes `hash`, failing if it holds more than `max_values` values.
+    fn decode_inner(&self, hash: &str, max_values: usize) -> Result<Vec<u64>> {
+        #[cfg(feature = "testing")]
+        if let Some(mock) = &self.mock_codec {
+            return mock.decode(hash);
+        }
+        #[cfg(feature = "testing")]
+        if self.placeholders {
+            return decode_placeholder(hash);
+        }
+        let symbols;
+        let hash = if self.crockford_decode {
+            symbols = hash.chars().filter_map(crockford_symbol).collect::<String>();
+            symbols.as_str()
+        } else {
+            hash
+        };
+        let normalized;
+        let hash = if self.case_insensitive_decode {
+            normalized = normalize_case(hash, &self.alphabet);
+            normalized.as_str()
+        } else {
+            hash
+        };
+        let (body, checksum_ok) = if self.checksum {
+            self.split_checksum(hash)
+        } else {
+            (hash, true)
+        };
+        let count = self.codec.count_values(body);
+        if count > max_values {
+            return Err(SerdeHashError::TooManyValues { count, max: max_values });
+        }
+        let decode = if self.constant_time_decode {
+            self.decode_constant_time(hash, body, checksum_ok)?
+        } else if !checksum_ok {
+            return Err(SerdeHashError::InvalidHash(format!("{} (wrong checksum)", hash)));
+        } else {
+            let decode = self.codec.decode(body).map_err(|_| invalid_hash(hash))?;
+            // hashids.js only accepts hashes it would have produced itself.
+            if self.js_compat && self.codec.encode(&decode, self.min_length) != body {
+                return Err(invalid_hash(hash));
+            }
+            decode
+        };
+        #[cfg(feature = "log")]
+        debug!("Decoding: {} -> {:?}", hash, decode);
+        Ok(decode)
+    }
+
+    /// Splits the check character off `hash`, returning the rest and whether the check
+    /// character matches it.
+    fn split_checksum<'a>(&self, hash: &'a str) -> (&'a str, bool) {
+        let Some((index, check)) = hash.char_indices().next_back() else {
+            return ("", false);
+        };
+        let body = &hash[..index];
+        let (expected, valid) = self.check_char(body);
+        (body, valid & (check == expected))
+    }
+
+    /// Computes the Luhn mod N check character of `body` over the alphabet, along with
+    /// whether every character of `body` belongs to the alphabet.
+    ///
+    /// Luhn mod N catches every single-character substitution and most transpositions of
+    /// adjacent characters, the usual mistakes when an ID is typed or read aloud.
+    fn check_char(&self, body: &str) -> (char, bool) {
+        let alphabet: Vec<char> = self.alphabet.chars().collect();
+        let n = alphabet.len();
+        let mut valid = true;
+        let mut sum = 0;
+        for (i, c) in body.chars().rev().enumerate() {
+            let position = alphabet.iter().position(|a| *a == c);
+            valid &= position.is_some();
+            let mut addend = position.unwrap_or(0) * if i % 2 == 0 { 2 } else { 1 };
+            addend = addend / n + addend % n;
+            sum += addend;
+        }
+        (alphabet[(n - sum % n) % n], valid)
+    }
+
+    /// Decodes `body`, the part of `hash` before any check character, running every check
+    /// even after one has failed.
+    fn decode_constant_time(&self, hash: &str, body: &str, checksum_ok: bool) -> Result<Vec<u64>> {
+        let in_alphabet = body
+            .chars()
+            .fold(true, |valid, c| valid & self.alphabet.contains(c));
+        let long_enough = body.chars().count() >= self.min_length;
+        let decoded = self.codec.decode(body).unwrap_or_default();
+        // Encode something even when decoding failed, so both paths do the same work.
+        let encoded = if decoded.is_empty() {
+            self.codec.encode(&[0], self.min_length)
+        } else {
+            self.codec.encode(&decoded, self.min_length)
+        };
+        let round_trips = constant_time_eq(encoded.as_bytes(), body.as_bytes());
+
+        if checksum_ok & in_alphabet & long_enough & round_trips & !decoded.is_empty() {
+            Ok(decoded)
+        } else {
+            Err(invalid_hash(hash))
+        }
+    }
+
+    /// Encodes a single `u64` value into a hash string.
+    pub fn encode_single(&self, data: u64) -> String {
+        self.encode(&[data])
+    }
+
+    /// Decodes a hash string into a single `u64` value.
+    ///
+    /// # Returns
+    ///
+    /// * On success, returns a single `u64` value that was encoded in the hash.
+    /// * On failure, returns an error if the hash does not decode to exactly one `u64` value,
+    ///   or if an error occurs during decoding.
+    pub fn decode_single(&self, hash: impl AsRef<str>) -> Result<u64> {
+        let hash = hash.as_ref();
+        let max_values = self.max_decoded_values.min(1);
+        record_decode(self.decode_inner(hash, max_values).and_then(|decode| match decode[..] {
+            [value] => Ok(value),
+            _ => Err(invalid_hash(hash)),
+        }))
+    }
+
+    /// Like [`HashIdsEncoder::decode_single`], also reporting how the hash was decoded, so
+    /// services can log which configuration incoming ids were minted with.
+    ///
+    /// # Returns
+    ///
+    /// * The value and the [`DecodeReport`].
+    /// * The same errors as [`HashIdsEncoder::decode_single`].
+    pub fn decode_single_with_report(
+        &self,
+        hash: impl AsRef<str>,
+    ) -> Result<(u64, DecodeReport)> {
+        let hash = hash.as_ref();
+        let value = self.decode_single(hash)?;
+        let report = DecodeReport {
+            backend: self.backend(),
+            length: hash.chars().count(),
+        };
+        Ok((value, report))
+    }
+
+    fn backend(&self) -> DecodeBackend {
+        #[cfg(feature = "testing")]
+        if self.mock_codec.is_some() {
+            return DecodeBackend::Mock;
+        }
+        #[cfg(feature = "testing")]
+        if self.placeholders {
+            return DecodeBackend::Placeholder;
+        }
+        if self.js_compat {
+            DecodeBackend::HashidsJs
+        } else {
+            DecodeBackend::Hashids
+        }
+    }
+
+    /// Encodes a byte slice into a hash string, see [`crate::hashids::encode_bytes`].
+    pub fn encode_bytes(&self, data: &[u8]) -> String {
+        let mut values = Vec::with_capacity(1 + data.len().div_ceil(8));
+        values.push(data.len() as u64);
+        values.extend(data.chunks(8).map(|chunk| {
+            let mut limb = [0; 8];
+            limb[..chunk.len()].copy_from_slice(chunk);
+            u64::from_be_bytes(limb)
+        }));
+        self.encode(&values)
+    }
+
+    /// Decodes a hash string produced by [`HashIdsEncoder::encode_bytes`].
+    ///
+    /// # Returns
+    ///
+    /// * On success, the original bytes.
+    /// * On failure, an error if the hash is invalid, or if its length prefix doesn't match
+    ///   the values that follow it.
+    pub fn decode_bytes(&self, hash: impl AsRef<str>) -> Result<Vec<u8>> {
+        let hash = hash.as_ref();
+        // Byte strings take a value per 8 bytes, so they aren't held to the values limit.
+        let values = record_decode(self.decode_inner(hash, usize::MAX))?;
+        let invalid = || SerdeHashError::InvalidHash(format!("{} (not a byte string)", hash));
+        let (&length, limbs) = values.split_first().ok_or_else(invalid)?;
+        let length = usize::try_from(length).map_err(|_| invalid())?;
+        if length.div_ceil(8) != limbs.len() {
+            return Err(invalid());
+        }
+        let mut bytes: Vec<u8> = limbs.iter().flat_map(|limb| limb.to_be_bytes()).collect();
+        // Padding must be zero, so every byte string has exactly one hash.
+        if bytes[length..].iter().any(|&b| b != 0) {
+            return Err(invalid());
+        }
+        bytes.truncate(length);
+        Ok(bytes)
+    }
+
+    /// Decodes a hash holding one numeric value: a single `u64`, or the two limbs of a
+    /// `u128` above `u64::MAX`, see [`HashNumeric::to_limbs`](crate::serde_impl::HashNumeric::to_limbs).
+    pub(crate) fn decode_numeric(&self, hash: impl AsRef<str>) -> Result<Vec<u64>> {
+        let hash = hash.as_ref();
+        let max_values = self.max_decoded_values.min(2);
+        record_decode(self.decode_inner(hash, max_values).and_then(|decode| match decode[..] {
+            [_] => Ok(decode),
+            [high, _] if high != 0 => Ok(decode),
+            _ => Err(invalid_hash(hash)),
+        }))
+    }
+}
+
+/// The error for a hash that doesn't decode, naming the hash.
+pub(crate) fn invalid_hash(hash: &str) -> SerdeHashError {
+    SerdeHashError::InvalidHash(hash.to_string())
+}
+
+impl Drop for HashIdsEncoder {
+    fn drop(&mut self) {
+        wipe_string(&mut self.alphabet);
+    }
+}
+
+/// Writes `data` as a placeholder like `hash(1,2)`, see
+/// [`SerdeHashOptions::with_placeholders`].
+#[cfg(feature = "testing")]
+fn encode_placeholder(data: &[u64]) -> String {
+    let values: Vec<String> = data.iter().map(u64::to_string).collect();
+    format!("hash({})", values.join(","))
+}
+
+/// Reads the values back out of a placeholder written by [`encode_placeholder`].
+#[cfg(feature = "testing")]
+fn decode_placeholder(hash: &str) -> Result<Vec<u64>> {
+    let invalid = || SerdeHashError::InvalidHash(format!("{} (not a placeholder)", hash));
+    let values = hash
+        .strip_prefix("hash(")
+        .and_then(|rest| rest.strip_suffix(')'))
+        .ok_or_else(invalid)?;
+    if values.is_empty() {
+        return Ok(Vec::new());
+    }
+    values
+        .split(',')
+        .map(|value| value.parse().map_err(|_| invalid()))
+        .collect()
+}
+
+/// Compares two byte strings without exiting early on the first difference.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    let mut diff = a.len() ^ b.len();
+    for i in 0..a.len().max(b.len()) {
+        let x = a.get(i).copied().unwrap_or(0);
+        let y = b.get(i).copied().unwrap_or(0);
+        diff |= usize::from(x ^ y);
+    }
+    std::hint::black_box(diff) == 0
+}
+
+/// Rewrites characters that aren't part of `alphabet` into the case the alphabet uses.
+fn normalize_case(hash: &str, alphabet: &str) -> String {
+    hash.chars()
+        .map(|c| {
+            if alphabet.contains(c) {
+                return c;
+            }
+            c.to_lowercase()
+                .chain(c.to_uppercase())
+                .find(|candidate| alphabet.contains(*candidate))
+                .unwrap_or(c)
+        })
+        .collect()
+}
+
+/// Generates a set of serde `with` modules bound to their own salt, independent of the
+/// global [`SerdeHashOptions`].
+///
+/// This is useful when a binary needs several hash configurations at once, or when you
+/// prefer plain `#[serde(with = "...")]` attributes over the `serde_hash` macros.
+/// `min_length` and `alphabet` are optional and default to the usual option defaults.
+///
+/// The generated module contains `numeric`, `vec_numeric`, `option_numeric`,
+/// `option_vec_numeric`, `vec_vec_numeric` and `nested`, mirroring [`crate::serde_impl`],
+/// plus an `encoder()` function returning the underlying [`HashIdsEncoder`]. The
+/// configuration is validated the first time it is used: `encoder()` panics if it is
+/// invalid, while `try_encoder()` returns the error, and so do the generated `with`
+/// modules, as a serialization or deserialization error.
+///
+/// # Example
+/// ```
+/// use serde::{Deserialize, Serialize};
+///
+/// serde_hash::hash_with_salt!(pub mod user_ids, salt = "users", min_length = 10);
+/// serde_hash::hash_with_salt!(pub mod order_ids, salt = "orders");
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct Order {
+///     #[serde(with = "order_ids::numeric")]
+///     pub id: u64,
+///     #[serde(with = "user_ids::numeric")]
+///     pub customer: u64,
+/// }
+///
+/// assert_eq!(user_ids::encoder().encode_single(1).len(), 10);
+/// ```
+#[macro_export]
+macro_rules! hash_with_salt {
+    (@with $module:ident) => {
+        pub mod $module {
+            pub fn serialize<T, S>(value: &T, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                T: $crate::serde_impl::HashShape,
+                S: $crate::__private::Serializer,
+            {
+                let encoder = super::try_encoder().map_err($crate::serde::ser::Error::custom)?;
+                $crate::serde_impl::serialize_with_encoder(value, encoder, serializer)
+            }
+
+            pub fn deserialize<'de, T, D>(deserializer: D) -> ::std::result::Result<T, D::Error>
+            where
+                T: $crate::serde_impl::HashShape,
+                D: $crate::__private::Deserializer<'de>,
+            {
+                let encoder = super::try_encoder().map_err($crate::serde::de::Error::custom)?;
+                $crate::serde_impl::deserialize_with_encoder(encoder, deserializer)
+            }
+        }
+    };
+    (
+        $vis:vis mod $name:ident,
+        salt = $salt:expr
+        $(, min_length = $min_length:expr)?
+        $(, alphabet = $alphabet:expr)?
+        $(,)?
+    ) => {
+        $vis mod $name {
+            /// Returns the encoder this module hashes with.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the module's options are invalid, see `try_encoder`.
+            pub fn encoder() -> &'static $crate::encoder::HashIdsEncoder {
+                try_encoder().unwrap_or_else(|e| {
+                    panic!(concat!("Invalid hash options for `", stringify!($name), "`: {}"), e)
+                })
+            }
+
+            /// Returns the encoder this module hashes with, or the error if the module's
+            /// options are invalid.
+            pub fn try_encoder() -> $crate::Result<&'static $crate::encoder::HashIdsEncoder> {
+                static ENCODER: ::std::sync::OnceLock<
+                    $crate::Result<$crate::encoder::HashIdsEncoder>,
+                > = ::std::sync::OnceLock::new();
+                ENCODER
+                    .get_or_init(|| {
+                        let options = $crate::hashids::SerdeHashOptions::new()
+                            .with_salt($salt)
+                            $(.with_min_length($min_length))?
+                            $(.with_alphabet($alphabet))?;
+                        $crate::encoder::HashIdsEncoder::new(&options)
+                    })
+                    .as_ref()
+                    .map_err(Clone::clone)
+            }
+
+            $crate::hash_with_salt!(@with numeric);
+            $crate::hash_with_salt!(@with vec_numeric);
+            $crate::hash_with_salt!(@with option_numeric);
+            $crate::hash_with_salt!(@with option_vec_numeric);
+            $crate::hash_with_salt!(@with vec_vec_numeric);
+            $crate::hash_with_salt!(@with nested);
+        }
+    };
+}
@@ -0,0 +1,200 @@
+//! Public ids of entity types.
+
+use crate::error::{Result, SerdeHashError};
+use std::collections::BTreeMap;
+
+/// Types with a primary hashed id.
+///
+/// `#[derive(HashIds)]` implements this for the field marked `#[hash(primary)]`, or, if
+/// none is, the first plain numeric `#[hash]` field. The id is encoded exactly as the field
+/// is when serialized, so service layers can hand out an entity's public id without
+/// serializing the whole struct. `#[hash(prefix = "usr_")]` on the struct prepends a type
+/// prefix to the id, which [`GlobalIdRegistry`] uses to tell types apart.
+///
+/// # Example
+/// ```
+/// use serde_hash::hashids::SerdeHashOptions;
+/// use serde_hash::{HasHashId, HashIds};
+///
+/// #[derive(HashIds)]
+/// pub struct User {
+///     #[hash]
+///     pub id: u64,
+///     pub name: String,
+/// }
+///
+/// SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+///
+/// let user = User { id: 42, name: "Dan".to_string() };
+/// let id = user.hash_id();
+/// assert_eq!(User::from_hash_id(&id).unwrap(), 42);
+/// ```
+pub trait HasHashId {
+    /// The prefix of every id of this type, e.g. `usr_`. Empty by default.
+    const PREFIX: &'static str = "";
+
+    /// The name [`GlobalIdRegistry::resolve`] reports for this type.
+    fn type_name() -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Returns the encoded primary id, including the prefix.
+    fn hash_id(&self) -> String;
+
+    /// Decodes a primary id produced by [`HasHashId::hash_id`].
+    fn from_hash_id(hash: &str) -> Result<u64>;
+}
+
+/// Values with a canonical public form, for code that needs the public id of any entity
+/// or id type in the same way, such as logging, URL builders and cache keys.
+///
+/// `#[derive(HashIds)]` implements this alongside [`HasHashId`], with the struct's
+/// prefixed primary id as the public form. [`HashedId`](crate::HashedId),
+/// [`OrderedHash`](crate::ordered::OrderedHash), [`HashDisplay`](crate::HashDisplay) and
+/// [`Cursor`](crate::cursor::Cursor) implement it with their hash.
+///
+/// # Example
+/// ```
+/// use serde_hash::entity::PublicId;
+/// use serde_hash::hashids::SerdeHashOptions;
+/// use serde_hash::{HashDisplay, HashIds};
+///
+/// #[derive(HashIds)]
+/// #[hash(prefix = "usr_")]
+/// pub struct User {
+///     #[hash]
+///     pub id: u64,
+/// }
+///
+/// fn cache_key(value: &impl PublicId) -> String {
+///     format!("cache:{}", value.as_public_id())
+/// }
+///
+/// SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+///
+/// let key = cache_key(&User { id: 42 });
+/// assert_eq!(User::parse_public_id(key.trim_start_matches("cache:")).unwrap(), 42);
+/// assert_eq!(cache_key(&HashDisplay(42)), key.replacen("usr_", "", 1));
+/// ```
+pub trait PublicId {
+    /// What a public id parses to: the numeric id for entities, the value itself for id
+    /// types.
+    type Id;
+
+    /// Returns the canonical public form.
+    fn as_public_id(&self) -> String;
+
+    /// Parses a public id produced by [`PublicId::as_public_id`].
+    fn parse_public_id(public_id: &str) -> Result<Self::Id>;
+}
+
+/// Removes `prefix` from the start of `hash`, failing if it isn't there.
+#[doc(hidden)]
+pub fn strip_prefix<'a>(hash: &'a str, prefix: &str) -> Result<&'a str> {
+    hash.strip_prefix(prefix).ok_or_else(|| {
+        let reason = format!("expected an id starting with '{}': {}", prefix, hash);
+        SerdeHashError::InvalidHash(reason)
+    })
+}
+
+/// A registered type: its name and how to decode its ids.
+struct Registration {
+    type_name: &'static str,
+    decode: fn(&str) -> Result<u64>,
+}
+
+/// Resolves prefixed ids of several types back to the type and the numeric id.
+///
+/// This is what a Relay-style `node(id:)` endpoint needs: every entity type registers
+/// its prefix once, and any id handed out by [`HasHashId::hash_id`] can be resolved
+/// without knowing its type up front.
+///
+/// # Example
+/// ```
+/// use serde_hash::entity::GlobalIdRegistry;
+/// use serde_hash::hashids::SerdeHashOptions;
+/// use serde_hash::{HasHashId, HashIds};
+///
+/// #[derive(HashIds)]
+/// #[hash(prefix = "usr_")]
+/// pub struct User {
+///     #[hash]
+///     pub id: u64,
+/// }
+///
+/// #[derive(HashIds)]
+/// #[hash(prefix = "inv_")]
+/// pub struct Invoice {
+///     #[hash]
+///     pub id: u64,
+/// }
+///
+/// SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+///
+/// let mut registry = GlobalIdRegistry::new();
+/// registry.register::<User>().unwrap();
+/// registry.register::<Invoice>().unwrap();
+///
+/// let id = Invoice { id: 42 }.hash_id();
+/// assert!(id.starts_with("inv_"));
+/// assert_eq!(registry.resolve(&id).unwrap(), ("Invoice", 42));
+/// ```
+#[derive(Default)]
+pub struct GlobalIdRegistry {
+    types: BTreeMap<&'static str, Registration>,
+}
+
+impl GlobalIdRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `T` under its [`HasHashId::PREFIX`].
+    ///
+    /// # Returns
+    ///
+    /// An error if `T` has no prefix, or if another type already registered it.
+    pub fn register<T: HasHashId>(&mut self) -> Result<(), SerdeHashError> {
+        if T::PREFIX.is_empty() {
+            return Err(SerdeHashError::MissingPrefix(T::type_name()));
+        }
+        if let Some(existing) = self.types.get(T::PREFIX) {
+            return Err(SerdeHashError::DuplicatePrefix {
+                prefix: T::PREFIX,
+                existing: existing.type_name,
+            });
+        }
+        self.types.insert(
+            T::PREFIX,
+            Registration {
+                type_name: T::type_name(),
+                decode: T::from_hash_id,
+            },
+        );
+        Ok(())
+    }
+
+    /// Resolves a prefixed id to the name of its type and its numeric id.
+    ///
+    /// When prefixes overlap (`us_` and `us_org_`), the longest matching prefix wins.
+    ///
+    /// # Returns
+    ///
+    /// An error if no registered prefix matches, or if the rest of the id doesn't decode.
+    pub fn resolve(&self, hash: &str) -> Result<(&'static str, u64)> {
+        let registration = self
+            .types
+            .iter()
+            .filter(|(prefix, _)| hash.starts_with(*prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, registration)| registration)
+            .ok_or_else(|| {
+                SerdeHashError::InvalidHash(format!(
+                    "no registered type matches the prefix of: {}",
+                    hash
+                ))
+            })?;
+        Ok((registration.type_name, (registration.decode)(hash)?))
+    }
+}
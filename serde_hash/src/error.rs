@@ -0,0 +1,269 @@
+use std::fmt;
+
+/// The result of fallible `serde_hash` operations, such as building options or decoding
+/// hashes.
+pub type Result<T, E = SerdeHashError> = std::result::Result<T, E>;
+
+/// Errors reported by `serde_hash` itself, as opposed to the serde format in use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SerdeHashError {
+    /// The alphabet has fewer than the 16 unique characters hashids needs.
+    AlphabetTooShort { unique: usize },
+    /// The alphabet lists the same character more than once.
+    AlphabetDuplicate(char),
+    /// The alphabet contains a space, which hashids reserves.
+    AlphabetContainsSpace,
+    /// The alphabet contains two characters that form a single grapheme when written next
+    /// to each other, e.g. a letter followed by a combining accent, so hashes wouldn't show
+    /// one symbol per character.
+    AlphabetCombiningCharacters { first: char, second: char },
+    /// The alphabet contains this non-ASCII character, but the `unicode` feature that
+    /// checks such alphabets for combining characters is disabled.
+    AlphabetNotAscii(char),
+    /// Case-insensitive decoding was requested, but the alphabet contains this character
+    /// in both cases.
+    AlphabetCaseSensitive(char),
+    /// Crockford decoding was requested, but the alphabet contains this character, which
+    /// the decoding maps to another one: a lowercase letter, `I`, `L`, `O` or a hyphen.
+    AlphabetNotCrockford(char),
+    /// URL safety was required, but the alphabet contains this character, which has to be
+    /// percent-encoded in URLs.
+    AlphabetNotUrlSafe(char),
+    /// The global options were already set, either by an earlier `build()` or by a hash
+    /// operation that initialized them with the defaults.
+    AlreadyConfigured,
+    /// A hash could not be decoded; holds the decoder's reason.
+    InvalidHash(String),
+    /// A value can't be hashed, e.g. a fraction too large for its scale; holds the reason.
+    InvalidValue(String),
+    /// A `SaltRegistry` has no salt for the requested tenant.
+    UnknownTenant,
+    /// A type without an id prefix was registered with a `GlobalIdRegistry`.
+    MissingPrefix(&'static str),
+    /// Two types registered the same id prefix with a `GlobalIdRegistry`.
+    DuplicatePrefix {
+        prefix: &'static str,
+        existing: &'static str,
+    },
+    /// The global options were built with a salt that is too easy to guess.
+    WeakSalt(WeakSaltError),
+    /// A pattern passed to `with_field_patterns` doesn't compile.
+    InvalidFieldPattern { pattern: String, reason: String },
+    /// The length passed to `with_exact_length` can't fit every single-value hash.
+    ExactLengthTooShort { length: usize, required: usize },
+    /// A hash of several values came out longer than the exact length.
+    ExactLengthExceeded { length: usize, exact: usize },
+    /// A hash came out longer than the limit set with `with_max_length`.
+    MaxLengthExceeded { length: usize, max: usize },
+    /// The value passed to `try_with_min_length` doesn't fit in a `usize`.
+    MinLengthOutOfRange(String),
+    /// The length passed to `with_max_length` is below the minimum (or exact) length.
+    MaxLengthBelowMinimum { max: usize, min: usize },
+    /// A hash holds more values than `with_max_decoded_values` allows.
+    TooManyValues { count: usize, max: usize },
+    /// The extension type passed to `with_msgpack_ext` is negative, which MessagePack
+    /// reserves for predefined types.
+    ReservedExtType(i8),
+    /// An expiring hash was decoded after its expiry time, in seconds since the Unix epoch.
+    Expired { expires_at: u64 },
+    /// A consistency token doesn't match the active options' token; holds both tokens.
+    ConsistencyMismatch { expected: String, actual: String },
+    /// Reading or writing a bulk job failed; holds the I/O error.
+    Io(String),
+    /// A record of a bulk job or a JSON payload isn't valid JSON or CSV; holds the parser's
+    /// error.
+    Malformed(String),
+    /// `error` happened while processing part of a larger input, e.g. `"in record 2"`.
+    Context {
+        context: String,
+        error: Box<SerdeHashError>,
+    },
+}
+
+impl SerdeHashError {
+    /// Wraps the error with what was being processed when it happened.
+    #[cfg(any(feature = "json", feature = "proto"))]
+    pub(crate) fn context(self, context: impl Into<String>) -> Self {
+        Self::Context {
+            context: context.into(),
+            error: Box::new(self),
+        }
+    }
+}
+
+impl From<std::io::Error> for SerdeHashError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error.to_string())
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for SerdeHashError {
+    fn from(error: serde_json::Error) -> Self {
+        if error.is_io() {
+            Self::Io(error.to_string())
+        } else {
+            Self::Malformed(error.to_string())
+        }
+    }
+}
+
+#[cfg(feature = "csv")]
+impl From<::csv::Error> for SerdeHashError {
+    fn from(error: ::csv::Error) -> Self {
+        if error.is_io_error() {
+            Self::Io(error.to_string())
+        } else {
+            Self::Malformed(error.to_string())
+        }
+    }
+}
+
+/// Why a salt was rejected by `serde_hash::salt::validate_salt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WeakSaltError {
+    /// The salt has fewer characters than required.
+    TooShort { length: usize, min: usize },
+    /// The salt repeats too few distinct characters, e.g. `"aaaaaaaaaaaaaaaa"`.
+    TooFewDistinct { distinct: usize, min: usize },
+    /// The salt is the published `serde_hash::salt::TESTING_SALT`.
+    TestingSalt,
+}
+
+impl From<WeakSaltError> for SerdeHashError {
+    fn from(error: WeakSaltError) -> Self {
+        Self::WeakSalt(error)
+    }
+}
+
+impl fmt::Display for SerdeHashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AlphabetTooShort { unique } => write!(
+                f,
+                "alphabet must contain at least 16 unique characters, but has {}",
+                unique
+            ),
+            Self::AlphabetDuplicate(c) => {
+                write!(f, "alphabet contains the character '{}' more than once", c)
+            }
+            Self::AlphabetContainsSpace => f.write_str("alphabet may not contain spaces"),
+            Self::AlphabetCombiningCharacters { first, second } => write!(
+                f,
+                "alphabet characters '{}' (U+{:04X}) and '{}' (U+{:04X}) combine into a \
+                 single grapheme",
+                first, *first as u32, second, *second as u32
+            ),
+            Self::AlphabetNotAscii(c) => write!(
+                f,
+                "alphabet character '{}' (U+{:04X}) isn't ASCII; enable the `unicode` feature \
+                 for Unicode alphabets",
+                c, *c as u32
+            ),
+            Self::AlphabetCaseSensitive(c) => write!(
+                f,
+                "case-insensitive decoding requires an alphabet without case pairs, \
+                 but '{}' appears in both cases",
+                c
+            ),
+            Self::AlphabetNotCrockford(c) => write!(
+                f,
+                "Crockford decoding maps '{}' to another character, so the alphabet can't \
+                 contain it",
+                c
+            ),
+            Self::AlphabetNotUrlSafe(c) => write!(
+                f,
+                "alphabet contains '{}', which isn't an unreserved URL character",
+                c
+            ),
+            Self::AlreadyConfigured => f.write_str("hash options have already been configured"),
+            Self::InvalidHash(reason) => write!(f, "invalid hash: {}", reason),
+            Self::InvalidValue(reason) => write!(f, "invalid value: {}", reason),
+            Self::UnknownTenant => f.write_str("no salt is registered for this tenant"),
+            Self::MissingPrefix(type_name) => {
+                write!(f, "type {} has no id prefix to register", type_name)
+            }
+            Self::DuplicatePrefix { prefix, existing } => write!(
+                f,
+                "id prefix '{}' is already registered by {}",
+                prefix, existing
+            ),
+            Self::WeakSalt(error) => write!(f, "weak salt: {}", error),
+            Self::InvalidFieldPattern { pattern, reason } => {
+                write!(f, "invalid field pattern '{}': {}", pattern, reason)
+            }
+            Self::ExactLengthTooShort { length, required } => write!(
+                f,
+                "exact length {} is too short, hashes of a single value need up to {} characters",
+                length, required
+            ),
+            Self::ExactLengthExceeded { length, exact } => write!(
+                f,
+                "hash of {} characters doesn't fit the exact length of {}",
+                length, exact
+            ),
+            Self::MaxLengthExceeded { length, max } => write!(
+                f,
+                "hash of {} characters exceeds the maximum length of {}",
+                length, max
+            ),
+            Self::MinLengthOutOfRange(value) => {
+                write!(f, "minimum length {} doesn't fit in a usize", value)
+            }
+            Self::MaxLengthBelowMinimum { max, min } => write!(
+                f,
+                "maximum length {} is below the minimum length of {}",
+                max, min
+            ),
+            Self::TooManyValues { count, max } => write!(
+                f,
+                "hash holds {} values, but at most {} are allowed",
+                count, max
+            ),
+            Self::ReservedExtType(tag) => write!(
+                f,
+                "MessagePack extension type {} is reserved, use one from 0 to 127",
+                tag
+            ),
+            Self::Expired { expires_at } => {
+                write!(f, "hash expired at {} seconds since the Unix epoch", expires_at)
+            }
+            Self::ConsistencyMismatch { expected, actual } => write!(
+                f,
+                "consistency token {} doesn't match this configuration's token {}",
+                expected, actual
+            ),
+            Self::Io(error) => write!(f, "I/O error: {}", error),
+            Self::Malformed(error) => write!(f, "malformed input: {}", error),
+            Self::Context { context, error } => write!(f, "{}: {}", context, error),
+        }
+    }
+}
+
+impl std::error::Error for SerdeHashError {}
+
+impl fmt::Display for WeakSaltError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort { length, min } => write!(
+                f,
+                "salt must be at least {} characters long, but has {}",
+                min, length
+            ),
+            Self::TooFewDistinct { distinct, min } => write!(
+                f,
+                "salt must contain at least {} distinct characters, but has {}",
+                min, distinct
+            ),
+            Self::TestingSalt => write!(
+                f,
+                "salt is the published testing salt, which only tests should use"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for WeakSaltError {}
@@ -0,0 +1,83 @@
+//! Hashes that stop decoding after a deadline.
+//!
+//! Share links and email verification tokens need an id that only works for a while.
+//! [`encode_with_expiry`] folds the expiry time into the hash next to the value, and
+//! [`decode_with_expiry`] rejects the hash once that time has passed.
+//!
+//! The expiry is only as tamper-proof as the hash itself: anyone who can produce hashes for
+//! the salt can produce tokens with a later expiry. Use a salt that never leaves the server.
+//!
+//! # Example
+//! ```
+//! use serde_hash::expiry::{decode_with_expiry, encode_with_expiry};
+//! use serde_hash::hashids::SerdeHashOptions;
+//! use std::time::Duration;
+//!
+//! SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+//!
+//! let token = encode_with_expiry(42, Duration::from_secs(15 * 60));
+//! assert_eq!(decode_with_expiry(&token).unwrap(), 42);
+//! ```
+
+use crate::error::{Result, SerdeHashError};
+use crate::hashids::{decode, encode};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Seconds since the Unix epoch, saturating at the epoch for earlier times.
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Encodes `value` into a hash that [`decode_with_expiry`] accepts for `ttl` from now.
+///
+/// # Arguments
+///
+/// * `value` - The value to be encoded.
+/// * `ttl` - How long the hash stays valid.
+///
+/// # Returns
+///
+/// A string that represents the encoded hash of the value and its expiry time.
+pub fn encode_with_expiry(value: u64, ttl: Duration) -> String {
+    let expires_at = SystemTime::now()
+        .checked_add(ttl)
+        .map_or(u64::MAX, unix_seconds);
+    encode_with_expiry_at(value, expires_at)
+}
+
+/// Encodes `value` into a hash that expires at `expires_at`, in seconds since the Unix
+/// epoch.
+pub fn encode_with_expiry_at(value: u64, expires_at: u64) -> String {
+    encode(&[value, expires_at])
+}
+
+/// Decodes a hash produced by [`encode_with_expiry`], if it hasn't expired yet.
+///
+/// # Returns
+///
+/// * On success, the value.
+/// * On failure, an error if the hash is invalid or doesn't hold a value and an expiry
+///   time. Expired hashes fail with [`SerdeHashError::Expired`], which the error can be
+///   downcast to, e.g. to tell users their link is too old rather than broken.
+pub fn decode_with_expiry(hash: impl AsRef<str>) -> Result<u64> {
+    decode_with_expiry_at(hash, unix_seconds(SystemTime::now()))
+}
+
+/// Like [`decode_with_expiry`], but checks the expiry against `now`, in seconds since the
+/// Unix epoch, instead of the current time.
+pub fn decode_with_expiry_at(hash: impl AsRef<str>, now: u64) -> Result<u64> {
+    let values = decode(hash)?;
+    let [value, expires_at] = values[..] else {
+        return Err(SerdeHashError::InvalidHash(format!(
+            "expected 2 values in expiring hash, found {}",
+            values.len()
+        )));
+    };
+    // A hash is valid up to and including the second it expires at.
+    if now > expires_at {
+        return Err(SerdeHashError::Expired { expires_at });
+    }
+    Ok(value)
+}
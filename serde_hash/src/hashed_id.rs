@@ -0,0 +1,94 @@
+//! Typed ids that are written as their hash.
+//!
+//! A [`HashedId`] holds any numeric id type and uses its hash everywhere the id leaves the
+//! process: `Display`, `FromStr`, serde and [`PublicId`]. Domain types can then keep a
+//! `HashedId<u32>` field without a `hash` attribute, and generic code gets its public id
+//! like any other [`PublicId`].
+//!
+//! # Example
+//! ```
+//! use serde_hash::hashids::SerdeHashOptions;
+//! use serde_hash::{HashedId, PublicId};
+//!
+//! SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+//!
+//! let id = HashedId(42u32);
+//! let public_id = id.as_public_id();
+//! assert_eq!(public_id, id.to_string());
+//! assert_eq!(HashedId::<u32>::parse_public_id(&public_id).unwrap(), id);
+//! ```
+
+use crate::entity::PublicId;
+use crate::error::{Result, SerdeHashError};
+use crate::serde_impl::{HashNumeric, decode_id, encode_id, numeric};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// A numeric id that displays, parses and serializes as its hash, using the same options
+/// as `hash` fields.
+///
+/// With the `rkyv` feature it can be archived with rkyv, which stores the id unhashed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct HashedId<T>(pub T);
+
+impl<T: HashNumeric> HashedId<T> {
+    /// Encodes the id with the active options.
+    pub fn encode(&self) -> String {
+        encode_id(self.0)
+    }
+
+    /// Decodes a hash produced by [`HashedId::encode`].
+    ///
+    /// # Returns
+    ///
+    /// An error if the hash is invalid or decodes to a value that doesn't fit in `T`.
+    pub fn decode(hash: impl AsRef<str>) -> Result<Self> {
+        decode_id(hash.as_ref()).map(Self)
+    }
+}
+
+impl<T: HashNumeric> From<T> for HashedId<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: HashNumeric> fmt::Display for HashedId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl<T: HashNumeric> FromStr for HashedId<T> {
+    type Err = SerdeHashError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::decode(s)
+    }
+}
+
+impl<T: HashNumeric> PublicId for HashedId<T> {
+    type Id = Self;
+
+    fn as_public_id(&self) -> String {
+        self.encode()
+    }
+
+    fn parse_public_id(public_id: &str) -> Result<Self> {
+        Self::decode(public_id)
+    }
+}
+
+impl<T: HashNumeric> Serialize for HashedId<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        numeric::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T: HashNumeric> Deserialize<'de> for HashedId<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        numeric::deserialize(deserializer).map(Self)
+    }
+}
@@ -1,7 +1,24 @@
+use crate::alphabet::{Alphabet, crockford_symbol, is_url_safe, validate_alphabet};
+use crate::codec::Codec;
+use crate::context::HashContext;
+use crate::encoder::{DecodeReport, HashIdsEncoder};
+use crate::error::{Result, SerdeHashError};
+use crate::patterns::FieldPatterns;
+use crate::provider::{active_context, installed_provider};
+#[cfg(feature = "rand")]
 use crate::salt::generate_salt;
-use anyhow::Result;
-use hash_ids::HashIds;
+#[cfg(feature = "hkdf")]
+use crate::salt::derive_salt;
+use crate::salt::validate_salt;
+#[cfg(feature = "hkdf")]
+use crate::secret::replace_string;
+use crate::secret::SecretString;
+#[cfg(feature = "hmac")]
+use hmac::{Hmac, KeyInit, Mac};
+#[cfg(feature = "log")]
 use log::debug;
+#[cfg(feature = "hmac")]
+use sha2::Sha256;
 
 /// Decodes a given hash string into a vector of `u64` integers.
 ///
@@ -13,11 +30,7 @@ use log::debug;
 ///
 /// A vector of `u64` integers that were encoded in the given hash string.
 pub fn decode(hash: impl AsRef<str>) -> Result<Vec<u64>> {
-    let hash = hash.as_ref();
-    let hash_ids = hashids();
-    let decode = hash_ids.decode(hash)?;
-    debug!("Decoding: {} -> {:?}", hash, decode);
-    Ok(decode)
+    current_encoder().decode(hash)
 }
 
 /// Encodes a slice of `u64` integers into a hash string.
@@ -30,10 +43,23 @@ pub fn decode(hash: impl AsRef<str>) -> Result<Vec<u64>> {
 ///
 /// A string that represents the encoded hash of the input data.
 pub fn encode(data: &[u64]) -> String {
-    let hash_ids = hashids();
-    let encode = hash_ids.encode(data);
-    debug!("Encoding: {:?} -> {}", data, encode);
-    encode
+    current_encoder().encode(data)
+}
+
+/// Encodes a slice of `u64` integers into a hash string, failing instead of returning a
+/// hash longer than the [`SerdeHashOptions::with_exact_length`] or
+/// [`SerdeHashOptions::with_max_length`] setting.
+///
+/// # Arguments
+///
+/// * `data` - A slice of `u64` integers to be encoded.
+///
+/// # Returns
+///
+/// * The hash string, or [`SerdeHashError::ExactLengthExceeded`] or
+///   [`SerdeHashError::MaxLengthExceeded`] if it doesn't fit.
+pub fn try_encode(data: &[u64]) -> Result<String, SerdeHashError> {
+    current_encoder().try_encode(data)
 }
 
 /// Decodes a hash string into a single `u64` value.
@@ -48,16 +74,25 @@ pub fn encode(data: &[u64]) -> String {
 /// * On failure, returns an error if the hash does not decode to exactly one `u64` value,
 ///   or if an error occurs during decoding.
 pub fn decode_single(hash: impl AsRef<str>) -> Result<u64> {
-    let hash = hash.as_ref(); // Extracts the underlying string reference from the wrapper.
-    let decode = decode(hash)?; // Attempts to decode the hash into a vector of `u64` integers.
-
-    // Check if the decoded result contains exactly one value.
-    if decode.len() != 1 {
-        return Err(anyhow::Error::msg(format!("Invalid hash: {}", hash))); // Returns an error if not.
-    }
+    current_encoder().decode_single(hash)
+}
 
-    // Successfully return the single decoded value.
-    Ok(decode[0])
+/// Like [`decode_single`], also reporting which configuration decoded the hash, see
+/// [`HashIdsEncoder::decode_single_with_report`].
+///
+/// # Example
+/// ```
+/// use serde_hash::encoder::DecodeBackend;
+/// use serde_hash::hashids::{decode_single_with_report, encode_single};
+///
+/// let hash = encode_single(42);
+/// let (value, report) = decode_single_with_report(&hash).unwrap();
+/// assert_eq!(value, 42);
+/// assert_eq!(report.backend, DecodeBackend::Hashids);
+/// assert_eq!(report.length, hash.len());
+/// ```
+pub fn decode_single_with_report(hash: impl AsRef<str>) -> Result<(u64, DecodeReport)> {
+    current_encoder().decode_single_with_report(hash)
 }
 
 /// Encodes a single `u64` value into a hash string.
@@ -73,46 +108,405 @@ pub fn encode_single(data: u64) -> String {
     encode(&[data]) // Calls the `encode` function with the input value wrapped in a slice.
 }
 
-fn hashids() -> HashIds {
-    let options = get_hash_options();
-    HashIds::builder()
-        .with_salt(options.salt.as_str())
-        .with_min_length(options.min_length)
-        .with_alphabet(options.alphabet.as_str())
-        .finish()
-        .unwrap()
+/// Encodes a byte slice, such as a session nonce or a short digest, into a hash string.
+///
+/// The bytes are split into big-endian `u64` limbs after a length prefix, so leading and
+/// trailing zero bytes survive the round trip. Hashes grow with the input; this is meant
+/// for short tokens, not for arbitrary payloads.
+///
+/// # Arguments
+///
+/// * `data` - The bytes to be encoded.
+///
+/// # Returns
+///
+/// A string that represents the encoded hash of the input bytes.
+pub fn encode_bytes(data: &[u8]) -> String {
+    current_encoder().encode_bytes(data)
+}
+
+/// Decodes a hash string produced by [`encode_bytes`].
+///
+/// # Arguments
+///
+/// * `hash` - A string reference that contains the hash to be decoded.
+///
+/// # Returns
+///
+/// * On success, returns the original bytes.
+/// * On failure, returns an error if the hash is invalid or wasn't produced from bytes.
+pub fn decode_bytes(hash: impl AsRef<str>) -> Result<Vec<u8>> {
+    current_encoder().decode_bytes(hash)
+}
+
+/// Produces a salted one-way digest of a single `u64` value.
+///
+/// The digest is the first 16 bytes of an HMAC-SHA256 of the value keyed with the
+/// configured salt, rendered as lowercase hex. The same value and salt always produce
+/// the same digest, but the value can't be decoded from it. Keep in mind that small ID
+/// spaces can still be enumerated by anyone who knows the salt.
+///
+/// # Arguments
+///
+/// * `data` - A single `u64` value to be redacted.
+///
+/// # Returns
+///
+/// * A 32-character hex string.
+#[cfg(feature = "hmac")]
+pub fn redact_single(data: u64) -> String {
+    redact(&[data])
+}
+
+/// Produces a salted one-way digest of a slice of `u64` integers, see [`redact_single`].
+///
+/// The digest covers every value in order, so a single value redacts the same as with
+/// [`redact_single`].
+///
+/// # Arguments
+///
+/// * `data` - A slice of `u64` integers to be redacted.
+///
+/// # Returns
+///
+/// * A 32-character hex string.
+#[cfg(feature = "hmac")]
+pub fn redact(data: &[u64]) -> String {
+    let context = active_context();
+    let options = match &context {
+        Some(context) => context.options(),
+        None => get_hash_options(),
+    };
+    let mut mac = Hmac::<Sha256>::new_from_slice(options.salt().as_bytes())
+        .expect("HMAC accepts keys of any length");
+    for value in data {
+        mac.update(&value.to_be_bytes());
+    }
+    let digest = mac.finalize().into_bytes();
+    let redacted = digest[..16].iter().map(|b| format!("{:02x}", b)).collect();
+    #[cfg(feature = "log")]
+    debug!("Redacting: {:?} -> {}", data, redacted);
+    redacted
+}
+
+/// Encodes a slice of `u64` integers into a hash string of at least `min_length`
+/// characters, ignoring the globally configured minimum length.
+///
+/// # Arguments
+///
+/// * `data` - A slice of `u64` integers to be encoded.
+/// * `min_length` - The minimum length of the resulting hash.
+///
+/// # Returns
+///
+/// A string that represents the encoded hash of the input data.
+pub fn encode_with_min_length(data: &[u64], min_length: usize) -> String {
+    hashids_with_min_length(min_length).encode(data)
+}
+
+/// Encodes a single `u64` value into a hash string of at least `min_length` characters.
+///
+/// # Arguments
+///
+/// * `data` - A single `u64` value to be encoded into a hash.
+/// * `min_length` - The minimum length of the resulting hash.
+///
+/// # Returns
+///
+/// * A string that represents the encoded hash of the input value.
+pub fn encode_single_with_min_length(data: u64, min_length: usize) -> String {
+    encode_with_min_length(&[data], min_length)
+}
+
+/// Decodes a hash string produced with a minimum length of `min_length` into a single
+/// `u64` value.
+///
+/// Only the hardened decoder (see [`SerdeHashOptions::with_constant_time_decode`]) depends
+/// on the minimum length; otherwise this is the same as [`decode_single`].
+///
+/// # Arguments
+///
+/// * `hash` - A string reference that contains the hash to be decoded.
+/// * `min_length` - The minimum length the hash was encoded with.
+pub fn decode_single_with_min_length(hash: impl AsRef<str>, min_length: usize) -> Result<u64> {
+    hashids_with_min_length(min_length).decode_single(hash)
+}
+
+/// Decodes a hash string produced with a minimum length of `min_length` into a vector of
+/// `u64` integers, see [`decode_single_with_min_length`].
+///
+/// # Arguments
+///
+/// * `hash` - A string reference that contains the hash to be decoded.
+/// * `min_length` - The minimum length the hash was encoded with.
+pub fn decode_with_min_length(hash: impl AsRef<str>, min_length: usize) -> Result<Vec<u64>> {
+    hashids_with_min_length(min_length).decode(hash)
+}
+
+/// Like [`decode_with_min_length`], but only accepts hashes holding one numeric value,
+/// see [`HashIdsEncoder::decode_numeric`].
+pub(crate) fn decode_numeric_with_min_length(hash: &str, min_length: usize) -> Result<Vec<u64>> {
+    hashids_with_min_length(min_length).decode_numeric(hash)
+}
+
+/// Encodes a slice of `u64` integers with `options`, without consulting or setting the
+/// global options.
+///
+/// Libraries that embed serde_hash can use this to hash with their own configuration
+/// without interfering with the host application's. Each call builds the encoder from
+/// `options`; build a [`HashIdsEncoder`] once instead when encoding in a loop.
+///
+/// # Arguments
+///
+/// * `options` - The configuration to encode with.
+/// * `data` - A slice of `u64` integers to be encoded.
+///
+/// # Returns
+///
+/// * The hash string, or the validation error if `options` are unusable.
+pub fn encode_with(options: &SerdeHashOptions, data: &[u64]) -> Result<String, SerdeHashError> {
+    Ok(HashIdsEncoder::new(options)?.encode(data))
+}
+
+/// Decodes a hash string with `options`, without consulting or setting the global options.
+///
+/// See [`encode_with`].
+///
+/// # Arguments
+///
+/// * `options` - The configuration the hash was encoded with.
+/// * `hash` - A string slice that holds the hash to be decoded.
+///
+/// # Returns
+///
+/// * The decoded values, or an error if `options` are unusable or the hash is invalid.
+pub fn decode_with(options: &SerdeHashOptions, hash: impl AsRef<str>) -> Result<Vec<u64>> {
+    HashIdsEncoder::new(options)?.decode(hash)
+}
+
+/// Returns the encoder built from the active options: the task's context, the installed
+/// [`OptionsProvider`](crate::provider::OptionsProvider), or else the global options.
+///
+/// The encoder is built once and cached, so manual `Serialize`/`Deserialize` impls can
+/// fetch it once and call [`HashIdsEncoder::encode`] and [`HashIdsEncoder::decode`]
+/// directly instead of going through the free functions for every value.
+///
+/// # Example
+/// ```
+/// use serde_hash::hashids::current_encoder;
+///
+/// let encoder = current_encoder();
+/// let hash = encoder.encode_single(42);
+/// assert_eq!(encoder.decode_single(&hash).unwrap(), 42);
+/// ```
+pub fn current_encoder() -> Arc<HashIdsEncoder> {
+    match active_context() {
+        Some(context) => context.encoder().clone(),
+        None => global_context().encoder().clone(),
+    }
 }
 
-use std::sync::OnceLock;
+/// Returns the encoder for the active options like [`current_encoder`], but returns an
+/// error instead of panicking if the global options can't produce one.
+///
+/// Manual `Serialize` impls can map the error into the serializer's error, so a broken
+/// configuration fails the one request instead of unwinding through the handler.
+///
+/// # Example
+/// ```
+/// use serde::{Serialize, Serializer};
+/// use serde_hash::hashids::try_current_encoder;
+///
+/// struct Id(u64);
+///
+/// impl Serialize for Id {
+///     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+///         let encoder = try_current_encoder().map_err(serde::ser::Error::custom)?;
+///         serializer.serialize_str(&encoder.encode_single(self.0))
+///     }
+/// }
+///
+/// assert!(serde_json::to_string(&Id(42)).is_ok());
+/// ```
+pub fn try_current_encoder() -> crate::Result<Arc<HashIdsEncoder>> {
+    match active_context() {
+        Some(context) => Ok(context.encoder().clone()),
+        None => Ok(try_global_context()?.encoder().clone()),
+    }
+}
+
+/// Reads a setting of the active options, i.e. the task's context, the installed provider
+/// or the global options, falling back to the default without initializing the global
+/// options.
+///
+/// Serialization checks settings for every value, also when hashing with the encoder of
+/// a [`hash_with_salt!`](crate::hash_with_salt) module; the global options have to stay
+/// unset for the application to configure them.
+pub(crate) fn active_setting<T: Default>(get: impl FnOnce(&SerdeHashOptions) -> T) -> T {
+    match active_context() {
+        Some(context) => get(context.options()),
+        None => HASH_OPTIONS.get().map(get).unwrap_or_default(),
+    }
+}
+
+/// The global options as a context, built on first use.
+///
+/// The context caches the encoders for the global options, so once it is built, fetching
+/// the encoder behind nearly every encode and decode is a single atomic load with no
+/// locking.
+static GLOBAL_CONTEXT: OnceLock<HashContext> = OnceLock::new();
+
+/// Returns the context for the global options, initializing them with the defaults if
+/// they weren't set.
+pub(crate) fn global_context() -> &'static HashContext {
+    try_global_context().expect("global hash options are validated by build()")
+}
+
+/// Like [`global_context`], but returns the error if the global options don't build.
+fn try_global_context() -> crate::Result<&'static HashContext> {
+    if let Some(context) = GLOBAL_CONTEXT.get() {
+        return Ok(context);
+    }
+    let context = get_hash_options().clone().into_context()?;
+    Ok(GLOBAL_CONTEXT.get_or_init(|| context))
+}
+
+/// Returns the global options if they were set or used, without initializing them.
+pub(crate) fn configured_options() -> Option<&'static SerdeHashOptions> {
+    HASH_OPTIONS.get()
+}
+
+fn hashids_with_min_length(min_length: usize) -> Arc<HashIdsEncoder> {
+    match active_context() {
+        Some(context) => context.encoder_with_min_length(min_length),
+        None => global_context().encoder_with_min_length(min_length),
+    }
+}
+
+use std::fmt;
+use std::sync::{Arc, OnceLock};
 
 /// Configuration options for the hash ID generation.
 ///
 /// This struct stores the configuration parameters used by the hash ID generator,
 /// including the salt for randomization, minimum length of generated hashes,
 /// and the alphabet used for encoding.
+///
+/// Options can be cloned, e.g. once per tenant, and compared. Their `Debug` output leaves
+/// out the salt.
+#[derive(Clone, PartialEq, Eq)]
 pub struct SerdeHashOptions {
-    /// Salt string used to randomize hash generation
-    pub salt: String,
+    /// Salt string used to randomize hash generation, see [`SerdeHashOptions::salt`]
+    salt: SecretString,
     /// Minimum length of generated hash strings
     pub min_length: usize,
-    /// Character set used for encoding values into hash strings
-    pub alphabet: String,
+    /// Character set used for encoding values into hash strings, see
+    /// [`SerdeHashOptions::alphabet`]
+    alphabet: SecretString,
+    /// Whether incoming hashes are matched against the alphabet regardless of case
+    pub case_insensitive_decode: bool,
+    /// Whether incoming hashes are read like Crockford's Base32, see
+    /// [`SerdeHashOptions::with_crockford_decode`]
+    pub crockford_decode: bool,
+    /// Whether decoding does the same work for valid and invalid hashes
+    pub constant_time_decode: bool,
+    /// Whether hashes end in a check character that is verified on decode
+    pub checksum: bool,
+    /// Whether hashes match hashids.js exactly, see [`SerdeHashOptions::with_js_compat`]
+    pub js_compat: bool,
+    /// Whether `build()` accepts a salt that fails [`validate_salt`]
+    pub allow_weak_salt: bool,
+    /// Keys of dynamic payloads that hold ids, see [`SerdeHashOptions::with_field_patterns`]
+    pub field_patterns: Vec<String>,
+    /// Length every hash is padded to, see [`SerdeHashOptions::with_exact_length`]
+    pub exact_length: Option<usize>,
+    /// Whether serializing a hash field of 0 fails, see [`SerdeHashOptions::with_reject_zero`]
+    pub reject_zero: bool,
+    /// Whether the alphabet must be URL-safe, see [`SerdeHashOptions::with_url_safe`]
+    pub url_safe: bool,
+    /// Extension type hash fields are written as in binary formats, see
+    /// [`SerdeHashOptions::with_msgpack_ext`]
+    pub msgpack_ext: Option<i8>,
+    /// Longest hash encoding may produce, see [`SerdeHashOptions::with_max_length`]
+    pub max_length: Option<usize>,
+    /// Most values a decoded hash may hold, see [`SerdeHashOptions::with_max_decoded_values`]
+    pub max_decoded_values: usize,
+    /// Whether hashes are readable placeholders, see [`SerdeHashOptions::with_placeholders`]
+    #[cfg(feature = "testing")]
+    pub(crate) placeholders: bool,
+    /// Codec used in place of hashids, see [`SerdeHashOptions::with_mock_codec`]
+    #[cfg(feature = "testing")]
+    pub(crate) mock_codec: Option<crate::testing::MockCodec>,
 }
 
 impl Default for SerdeHashOptions {
     /// Creates default configuration with:
-    /// - A randomly generated salt
+    /// - A randomly generated salt. Without the `rand` feature the salt is empty, which
+    ///   building the options rejects, so it has to be set with
+    ///   [`SerdeHashOptions::with_salt`]
     /// - Minimum hash length of 8 characters
     /// - Standard alphanumeric alphabet (a-z, A-Z, 0-9)
     fn default() -> Self {
         Self {
-            salt: generate_salt(), // Generate a random salt string
+            #[cfg(feature = "rand")]
+            salt: generate_salt().into(), // Generate a random salt string
+            #[cfg(not(feature = "rand"))]
+            salt: SecretString::default(),
             min_length: 8,         // Set default minimum hash length
-            alphabet: "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890".to_string(),
+            alphabet: Alphabet::Base62.as_str().to_string().into(),
+            case_insensitive_decode: false,
+            crockford_decode: false,
+            constant_time_decode: false,
+            checksum: false,
+            js_compat: false,
+            allow_weak_salt: false,
+            field_patterns: Vec::new(),
+            exact_length: None,
+            reject_zero: false,
+            url_safe: false,
+            msgpack_ext: None,
+            max_length: None,
+            max_decoded_values: DEFAULT_MAX_DECODED_VALUES,
+            #[cfg(feature = "testing")]
+            placeholders: false,
+            #[cfg(feature = "testing")]
+            mock_codec: None,
         }
     }
 }
 
+impl fmt::Debug for SerdeHashOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("SerdeHashOptions");
+        debug
+            .field("salt", &"<redacted>")
+            .field("min_length", &self.min_length)
+            .field("alphabet", &self.alphabet())
+            .field("case_insensitive_decode", &self.case_insensitive_decode)
+            .field("crockford_decode", &self.crockford_decode)
+            .field("constant_time_decode", &self.constant_time_decode)
+            .field("checksum", &self.checksum)
+            .field("js_compat", &self.js_compat)
+            .field("allow_weak_salt", &self.allow_weak_salt)
+            .field("field_patterns", &self.field_patterns)
+            .field("exact_length", &self.exact_length)
+            .field("reject_zero", &self.reject_zero)
+            .field("url_safe", &self.url_safe)
+            .field("msgpack_ext", &self.msgpack_ext)
+            .field("max_length", &self.max_length)
+            .field("max_decoded_values", &self.max_decoded_values);
+        #[cfg(feature = "testing")]
+        debug.field("placeholders", &self.placeholders);
+        #[cfg(feature = "testing")]
+        debug.field("mock_codec", &self.mock_codec);
+        debug.finish()
+    }
+}
+
+/// How many values a decoded hash may hold by default, see
+/// [`SerdeHashOptions::with_max_decoded_values`].
+pub const DEFAULT_MAX_DECODED_VALUES: usize = 16;
+
 /// Global singleton instance of hash options initialized lazily
 static HASH_OPTIONS: OnceLock<SerdeHashOptions> = OnceLock::new();
 
@@ -122,6 +516,10 @@ static HASH_OPTIONS: OnceLock<SerdeHashOptions> = OnceLock::new();
 /// If the configuration hasn't been initialized yet, it will initialize
 /// it with default values.
 ///
+/// These are the options [`GlobalOptions`](crate::provider::GlobalOptions) provides. A
+/// provider installed with [`set_options_provider`](crate::provider::set_options_provider)
+/// takes their place, and isn't reflected here.
+///
 /// # Returns
 ///
 /// A static reference to the global `SerdeHashOptions` instance
@@ -140,6 +538,35 @@ impl SerdeHashOptions {
         Self::default()
     }
 
+    /// Creates options for tests, with the fixed [`TESTING_SALT`](crate::salt::TESTING_SALT)
+    /// in place of a random salt so hashes in snapshot tests are the same in every
+    /// environment.
+    ///
+    /// The testing salt is public, so these options allow weak salts; building options
+    /// with it otherwise fails.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `SerdeHashOptions` with the testing salt and default settings.
+    pub fn for_tests() -> Self {
+        Self::new()
+            .with_salt(crate::salt::TESTING_SALT)
+            .with_weak_salt_allowed(true)
+    }
+
+    /// Returns the salt.
+    ///
+    /// It's kept in a private field so it can be wiped when the options are dropped, see
+    /// the `zeroize` feature.
+    pub fn salt(&self) -> &str {
+        &self.salt
+    }
+
+    /// Returns the alphabet.
+    pub fn alphabet(&self) -> &str {
+        &self.alphabet
+    }
+
     /// Sets a custom salt for the hash options.
     ///
     /// # Arguments
@@ -151,10 +578,37 @@ impl SerdeHashOptions {
     ///
     /// Self with the updated salt value for method chaining.
     pub fn with_salt(mut self, salt: impl AsRef<str>) -> Self {
-        self.salt = salt.as_ref().to_string(); // Convert input to a String and store it
+        self.salt.replace(salt.as_ref().to_string());
         self
     }
 
+    /// Sets the salt to one derived from a master key and a context label.
+    ///
+    /// See [`derive_salt`] for how the salt is derived. This keeps raw salts out of
+    /// configuration files: only the master key is secret, and each environment or
+    /// service uses its own context label.
+    ///
+    /// # Arguments
+    ///
+    /// * `master_key` - The secret to derive the salt from.
+    /// * `context` - A label such as `"orders/production"`.
+    ///
+    /// # Returns
+    ///
+    /// Self with the derived salt for method chaining.
+    #[cfg(feature = "hkdf")]
+    pub fn with_derived_salt(
+        self,
+        master_key: impl AsRef<[u8]>,
+        context: impl AsRef<str>,
+    ) -> Self {
+        let mut salt = derive_salt(master_key, context);
+        let options = self.with_salt(&salt);
+        // Wipes the derived salt with the `zeroize` feature.
+        replace_string(&mut salt, String::new());
+        options
+    }
+
     /// Sets a custom minimum length for generated hash IDs.
     ///
     /// # Arguments
@@ -167,7 +621,8 @@ impl SerdeHashOptions {
     ///
     /// # Panics
     ///
-    /// Panics if the provided value cannot be converted to an usize.
+    /// Panics if the provided value cannot be converted to an usize. Use
+    /// [`SerdeHashOptions::try_with_min_length`] for lengths that come from configuration.
     pub fn with_min_length<T>(mut self, min_length: T) -> Self
     where
         T: TryInto<usize>,
@@ -177,6 +632,28 @@ impl SerdeHashOptions {
         self
     }
 
+    /// Sets a custom minimum length like [`SerdeHashOptions::with_min_length`], but
+    /// returns an error instead of panicking if it doesn't fit in a `usize`.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_length` - The minimum length for generated hash IDs, e.g. an `i64` read from
+    ///   a config file.
+    ///
+    /// # Returns
+    ///
+    /// * Self with the updated minimum length for method chaining.
+    /// * [`SerdeHashError::MinLengthOutOfRange`] if the value is negative or too large.
+    pub fn try_with_min_length<T>(mut self, min_length: T) -> crate::Result<Self>
+    where
+        T: TryInto<usize> + fmt::Display + Copy,
+    {
+        self.min_length = min_length
+            .try_into()
+            .map_err(|_| SerdeHashError::MinLengthOutOfRange(min_length.to_string()))?;
+        Ok(self)
+    }
+
     /// Sets a custom alphabet for generating hash IDs.
     ///
     /// # Arguments
@@ -188,16 +665,470 @@ impl SerdeHashOptions {
     ///
     /// Self with the updated alphabet for method chaining.
     pub fn with_alphabet(mut self, alphabet: impl AsRef<str>) -> Self {
-        self.alphabet = alphabet.as_ref().to_string(); // Convert input to a String and store it
+        self.alphabet.replace(alphabet.as_ref().to_string());
         self
     }
 
+    /// Enables or disables case-insensitive decoding.
+    ///
+    /// When enabled, characters of an incoming hash that aren't in the alphabet are
+    /// replaced by their other-case counterpart before decoding, so IDs typed by hand
+    /// decode regardless of case. Encoding is unaffected. This requires an alphabet in
+    /// which no two characters differ only by case, such as `Alphabet::LowercaseHex`;
+    /// [`SerdeHashOptions::validate`] rejects any other combination.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to normalize the case of incoming hashes.
+    ///
+    /// # Returns
+    ///
+    /// Self with the updated setting for method chaining.
+    pub fn with_case_insensitive_decode(mut self, enabled: bool) -> Self {
+        self.case_insensitive_decode = enabled;
+        self
+    }
+
+    /// Reads incoming hashes like Crockford's Base32: lowercase letters are uppercased, `O`
+    /// is read as `0`, `I` and `L` as `1`, and hyphens are ignored. IDs printed on labels
+    /// or read out to support staff then decode despite the usual mix-ups. Encoding is
+    /// unaffected.
+    ///
+    /// This is enabled by `with_alphabet_preset(Alphabet::Crockford32)`. Other alphabets
+    /// must not contain lowercase letters, `I`, `L`, `O` or `-`, which
+    /// [`SerdeHashOptions::validate`] checks.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to map ambiguous characters before decoding.
+    ///
+    /// # Returns
+    ///
+    /// Self with the updated setting for method chaining.
+    pub fn with_crockford_decode(mut self, enabled: bool) -> Self {
+        self.crockford_decode = enabled;
+        self
+    }
+
+    /// Enables or disables hardened, constant-work decoding.
+    ///
+    /// When enabled, every incoming hash goes through the same checks whether or not an
+    /// earlier one already failed: each character is checked against the alphabet, the
+    /// length is checked against the minimum length, and the decoded values are encoded
+    /// again and compared with the input in constant time, which also rejects hashes with
+    /// tampered padding. All failures report the same error. This narrows the timing
+    /// differences an attacker probing hashes can observe, at the cost of an extra encode
+    /// per decode. It doesn't make the underlying hashids decoding itself constant-time.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to use the hardened decoder.
+    ///
+    /// # Returns
+    ///
+    /// Self with the updated setting for method chaining.
+    pub fn with_constant_time_decode(mut self, enabled: bool) -> Self {
+        self.constant_time_decode = enabled;
+        self
+    }
+
+    /// Enables or disables a check character at the end of every hash.
+    ///
+    /// When enabled, encoded hashes get one extra character, a Luhn mod N check digit over
+    /// the alphabet, and decoding rejects hashes whose check character doesn't match. This
+    /// catches typos in IDs that are read aloud or typed by hand (e.g. during support
+    /// calls) before they reach a database lookup. Hashes produced with and without a
+    /// checksum are not interchangeable.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to append and verify the check character.
+    ///
+    /// # Returns
+    ///
+    /// Self with the updated setting for method chaining.
+    pub fn with_checksum(mut self, enabled: bool) -> Self {
+        self.checksum = enabled;
+        self
+    }
+
+    /// Enables or disables compatibility with hashids.js (and the original PHP Hashids).
+    ///
+    /// The `hash_ids` algorithm this crate follows by default differs from hashids.js in
+    /// two ways. With a salt and an alphabet that has too few of the characters
+    /// `cfhistuCFHISTU`, such as a hex alphabet, hashids.js shuffles the separators before
+    /// topping them up from the alphabet, so hashes of several values come out
+    /// differently. And hashids.js rejects hashes that don't encode back to the same
+    /// string, e.g. with altered padding. Enable this when hashes are shared with a
+    /// JavaScript service; see [`crate::interop::hashids_js`].
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to encode and decode exactly like hashids.js.
+    ///
+    /// # Returns
+    ///
+    /// Self with the updated setting for method chaining.
+    pub fn with_js_compat(mut self, enabled: bool) -> Self {
+        self.js_compat = enabled;
+        self
+    }
+
+    /// Lets `build()` install a salt that fails [`validate_salt`], e.g. a short fixed salt
+    /// in tests. Weak salts are rejected by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `allowed` - Whether to skip the salt strength check.
+    ///
+    /// # Returns
+    ///
+    /// Self with the updated setting for method chaining.
+    pub fn with_weak_salt_allowed(mut self, allowed: bool) -> Self {
+        self.allow_weak_salt = allowed;
+        self
+    }
+
+    /// Sets the keys that hold ids in payloads without a Rust type, such as the
+    /// `serde_json::Value`s rewritten by [`crate::value`].
+    ///
+    /// Patterns are globs like `"*_id"`, or regular expressions between slashes with the
+    /// `regex` feature; see [`crate::patterns`]. They're only compiled when the options are
+    /// validated, so a bad pattern surfaces from `build()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `patterns` - The key patterns, e.g. `["id", "*_id"]`.
+    ///
+    /// # Returns
+    ///
+    /// Self with the updated patterns for method chaining.
+    pub fn with_field_patterns<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.field_patterns = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Pads every hash to exactly `length` characters, for systems that store public ids
+    /// in fixed-width columns. This replaces the minimum length.
+    ///
+    /// Validation fails if a hash of a single value, such as `u64::MAX`, could be longer
+    /// than `length` (counting the check character with
+    /// [`SerdeHashOptions::with_checksum`]). Hashes of several values, such as cursors and
+    /// packed fields, can still outgrow it; [`HashIdsEncoder::try_encode`] reports those,
+    /// while [`HashIdsEncoder::encode`] returns the longer hash and logs a warning.
+    ///
+    /// # Arguments
+    ///
+    /// * `length` - The length of every hash.
+    ///
+    /// # Returns
+    ///
+    /// Self with the updated setting for method chaining.
+    pub fn with_exact_length(mut self, length: usize) -> Self {
+        self.exact_length = Some(length);
+        self
+    }
+
+    /// Makes serializing a hash field that holds 0 fail, since 0 is usually an "unset"
+    /// placeholder rather than a real id and its hash shouldn't be published. This covers
+    /// every hash field, including the values of `Vec` and `Option` fields; use
+    /// `#[hash(nonzero)]` to reject 0 for single fields only.
+    ///
+    /// Encoding functions such as [`encode_single`] still accept 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to fail serialization on hash fields of 0.
+    ///
+    /// # Returns
+    ///
+    /// Self with the updated setting for method chaining.
+    pub fn with_reject_zero(mut self, enabled: bool) -> Self {
+        self.reject_zero = enabled;
+        self
+    }
+
+    /// Requires the alphabet to consist of unreserved URL characters only (`A-Z`, `a-z`,
+    /// `0-9`, `-`, `.`, `_` and `~`), so hashes can go into URL paths and query strings
+    /// without percent-encoding. Validation fails with
+    /// [`SerdeHashError::AlphabetNotUrlSafe`] otherwise.
+    ///
+    /// For alphabets that aren't URL-safe, [`crate::url::encode_url_component`]
+    /// percent-encodes hashes instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to require a URL-safe alphabet.
+    ///
+    /// # Returns
+    ///
+    /// Self with the updated setting for method chaining.
+    pub fn with_url_safe(mut self, enabled: bool) -> Self {
+        self.url_safe = enabled;
+        self
+    }
+
+    /// Writes hash fields as raw ids in MessagePack extension values of type `tag`
+    /// instead of hash strings when serializing with rmp-serde, which keeps internal
+    /// MessagePack traffic compact while JSON stays hashed. Deserializing accepts both
+    /// the extension values and hash strings.
+    ///
+    /// This applies to every format that isn't human-readable, so only enable it when
+    /// MessagePack is the one binary format in use: the values can't be read back by
+    /// formats that aren't self-describing, such as bincode. It doesn't change the
+    /// `Internal` and `Debug` profiles.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The application-specific extension type, from 0 to 127. Negative types
+    ///   are reserved by MessagePack and fail validation.
+    ///
+    /// # Returns
+    ///
+    /// Self with the updated setting for method chaining.
+    pub fn with_msgpack_ext(mut self, tag: i8) -> Self {
+        self.msgpack_ext = Some(tag);
+        self
+    }
+
+    /// Caps the length of hashes, for consumers that only accept so many characters, e.g.
+    /// a `VARCHAR(12)` column or a URL slug limit. Large values with a long minimum length
+    /// or several values in one hash can otherwise grow past it.
+    ///
+    /// The limit is enforced at encode time: serializing a hash field that would be
+    /// longer fails, as does [`HashIdsEncoder::try_encode`], while
+    /// [`HashIdsEncoder::encode`] returns the longer hash and logs a warning. Validation
+    /// fails if the limit is below the minimum length.
+    ///
+    /// # Arguments
+    ///
+    /// * `length` - The most characters a hash may have, including the check character
+    ///   added by [`SerdeHashOptions::with_checksum`].
+    ///
+    /// # Returns
+    ///
+    /// Self with the updated setting for method chaining.
+    pub fn with_max_length(mut self, length: usize) -> Self {
+        self.max_length = Some(length);
+        self
+    }
+
+    /// Limits how many values a hash may hold when it is decoded. Hashes with more are
+    /// rejected before their values are decoded, so a crafted hash can't make decoding
+    /// allocate and work on thousands of values, or hand a long list to code expecting a
+    /// small tuple.
+    ///
+    /// The default is [`DEFAULT_MAX_DECODED_VALUES`], plenty for ids, cursors and packed
+    /// fields. Raise it for hashes of long lists; `usize::MAX` turns the limit off. Byte
+    /// hashes from [`HashIdsEncoder::encode_bytes`] aren't limited.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - The most values a decoded hash may hold.
+    ///
+    /// # Returns
+    ///
+    /// Self with the updated setting for method chaining.
+    pub fn with_max_decoded_values(mut self, max: usize) -> Self {
+        self.max_decoded_values = max;
+        self
+    }
+
+    /// Replaces hashes with readable placeholders such as `hash(42)`, for snapshot tests
+    /// of serialized structs. Placeholders decode back to their values, so round trips
+    /// keep working.
+    ///
+    /// Only available with the `testing` feature, which should only be enabled for tests.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to write placeholders instead of hashes.
+    ///
+    /// # Returns
+    ///
+    /// Self with the updated setting for method chaining.
+    #[cfg(feature = "testing")]
+    pub fn with_placeholders(mut self, enabled: bool) -> Self {
+        self.placeholders = enabled;
+        self
+    }
+
+    /// Encodes and decodes with `codec` instead of hashids, so hashes are predictable
+    /// strings like `"h42"`. See [`crate::testing`].
+    ///
+    /// Only available with the `testing` feature, which should only be enabled for tests.
+    ///
+    /// # Arguments
+    ///
+    /// * `codec` - The mock codec to write and read hashes with.
+    ///
+    /// # Returns
+    ///
+    /// Self with the updated setting for method chaining.
+    #[cfg(feature = "testing")]
+    pub fn with_mock_codec(mut self, codec: crate::testing::MockCodec) -> Self {
+        self.mock_codec = Some(codec);
+        self
+    }
+
+    /// Sets the alphabet to one of the built-in presets.
+    ///
+    /// `Alphabet::Crockford32` also enables [`SerdeHashOptions::with_crockford_decode`].
+    ///
+    /// # Arguments
+    ///
+    /// * `preset` - The preset to use, e.g. `Alphabet::Base58NoLookalikes`.
+    ///
+    /// # Returns
+    ///
+    /// Self with the updated alphabet for method chaining.
+    pub fn with_alphabet_preset(mut self, preset: Alphabet) -> Self {
+        if preset == Alphabet::Crockford32 {
+            self.crockford_decode = true;
+        }
+        self.with_alphabet(preset.as_str())
+    }
+
+    /// Checks that these options can be used for hash generation.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the options are usable.
+    /// * The first problem found otherwise, e.g. an alphabet with duplicate characters.
+    pub fn validate(&self) -> Result<(), SerdeHashError> {
+        validate_alphabet(&self.alphabet)?;
+        if self.case_insensitive_decode
+            && let Some(c) = self.alphabet.chars().find(|c| {
+                c.to_lowercase()
+                    .chain(c.to_uppercase())
+                    .any(|other| other != *c && self.alphabet.contains(other))
+            })
+        {
+            return Err(SerdeHashError::AlphabetCaseSensitive(c));
+        }
+        if self.crockford_decode
+            && let Some(c) = self
+                .alphabet
+                .chars()
+                .find(|&c| crockford_symbol(c) != Some(c))
+        {
+            return Err(SerdeHashError::AlphabetNotCrockford(c));
+        }
+        if self.url_safe
+            && let Some(c) = self.alphabet.chars().find(|&c| !is_url_safe(c))
+        {
+            return Err(SerdeHashError::AlphabetNotUrlSafe(c));
+        }
+        if let Some(tag) = self.msgpack_ext
+            && tag < 0
+        {
+            return Err(SerdeHashError::ReservedExtType(tag));
+        }
+        if let Some(max) = self.max_length {
+            let min = self.exact_length.unwrap_or(self.min_length);
+            if max < min {
+                return Err(SerdeHashError::MaxLengthBelowMinimum { max, min });
+            }
+        }
+        FieldPatterns::new(&self.field_patterns)?;
+        if let Some(length) = self.exact_length {
+            let codec = Codec::new(&self.alphabet, &self.salt, self.js_compat);
+            // No single value has more digits than `u64::MAX`.
+            let required = codec.encode(&[u64::MAX], 0).chars().count() + usize::from(self.checksum);
+            if length < required {
+                return Err(SerdeHashError::ExactLengthTooShort { length, required });
+            }
+        }
+        Ok(())
+    }
+
+    /// Encodes each of `values` with these options, without installing them, to review
+    /// what public ids will look like (length, characters) before settling on a salt and
+    /// alphabet.
+    ///
+    /// The salt strength isn't checked, so candidate salts can be previewed as well.
+    ///
+    /// # Example
+    /// ```
+    /// use serde_hash::alphabet::Alphabet;
+    /// use serde_hash::hashids::SerdeHashOptions;
+    ///
+    /// let candidate = SerdeHashOptions::new()
+    ///     .with_alphabet_preset(Alphabet::LowercaseHex)
+    ///     .with_min_length(12);
+    /// for hash in candidate.preview(&[1, 42, u32::MAX as u64]).unwrap() {
+    ///     assert_eq!(hash.len(), 12);
+    /// }
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - Sample ids, each encoded into its own hash.
+    ///
+    /// # Returns
+    ///
+    /// * The hashes, in the order of `values`.
+    /// * The validation error if the options are unusable.
+    pub fn preview(&self, values: &[u64]) -> Result<Vec<String>, SerdeHashError> {
+        let encoder = HashIdsEncoder::new(self)?;
+        Ok(values.iter().map(|&value| encoder.encode_single(value)).collect())
+    }
+
+    /// Validates the configuration and turns it into a [`HashContext`] that can be stored
+    /// in application state and shared between threads.
+    ///
+    /// # Returns
+    ///
+    /// * The context, or the validation error if the options are unusable.
+    pub fn into_context(self) -> Result<HashContext, SerdeHashError> {
+        HashContext::new(self)
+    }
+
+    /// Validates the configuration and stores it in the global `HASH_OPTIONS`.
+    ///
+    /// Besides [`SerdeHashOptions::validate`], this checks the salt with [`validate_salt`]
+    /// unless weak salts were allowed with [`SerdeHashOptions::with_weak_salt_allowed`].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` once the options are installed.
+    /// * An error if the options are invalid, the salt is weak, the global options were
+    ///   already set, or an [`OptionsProvider`](crate::provider::OptionsProvider) was
+    ///   installed.
+    pub fn try_build(self) -> Result<(), SerdeHashError> {
+        if installed_provider().is_some() {
+            return Err(SerdeHashError::AlreadyConfigured);
+        }
+        self.validate()?;
+        if !self.allow_weak_salt {
+            validate_salt(&self.salt)?;
+        }
+        HASH_OPTIONS
+            .set(self)
+            .map_err(|_| SerdeHashError::AlreadyConfigured)
+    }
+
     /// Finalizes the configuration and stores it in the global `HASH_OPTIONS`.
     ///
     /// This method sets the configured options as the global hash options that
     /// will be used for all subsequent hash operations in the application.
-    /// Once set, the options cannot be changed as they're stored in a `OnceLock`.
+    /// Once set, the options cannot be changed as they're stored in a `OnceLock`;
+    /// later calls are ignored. It's safe to call from several threads at startup:
+    /// exactly one call installs its options, and every thread then sees those options.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the options are invalid (see [`SerdeHashOptions::validate`]) or the salt is
+    /// weak (see [`validate_salt`]), so a bad alphabet or an example salt surfaces at
+    /// startup rather than in production.
     pub fn build(self) {
-        let _ = HASH_OPTIONS.set(self); // Store the configured options in the global OnceLock
+        match self.try_build() {
+            Ok(()) | Err(SerdeHashError::AlreadyConfigured) => {}
+            Err(e) => panic!("Invalid hash options: {}", e),
+        }
     }
 }
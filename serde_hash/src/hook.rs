@@ -0,0 +1,48 @@
+//! A process-wide hook for observing hashes that fail to decode.
+//!
+//! Failed decodes during deserialization are a common sign of someone guessing ids. The
+//! hook lets a service count or log them in one place instead of at every endpoint.
+
+use crate::error::SerdeHashError;
+use std::fmt::Display;
+use std::sync::{PoisonError, RwLock};
+
+/// A function called with the rejected hash and the reason it was rejected.
+pub type DecodeErrorHook = fn(&str, &SerdeHashError);
+
+static DECODE_ERROR_HOOK: RwLock<Option<DecodeErrorHook>> = RwLock::new(None);
+
+/// Registers `hook` to be called whenever a hash fails to decode during deserialization.
+///
+/// Replaces any previously registered hook. The hook runs on the deserializing thread
+/// before the error is returned to serde, so it should be cheap.
+///
+/// # Example
+/// ```
+/// use serde_hash::error::SerdeHashError;
+///
+/// fn on_decode_error(hash: &str, error: &SerdeHashError) {
+///     eprintln!("rejected hash {:?}: {}", hash, error);
+/// }
+///
+/// serde_hash::set_decode_error_hook(on_decode_error);
+/// ```
+pub fn set_decode_error_hook(hook: DecodeErrorHook) {
+    *DECODE_ERROR_HOOK.write().unwrap_or_else(PoisonError::into_inner) = Some(hook);
+}
+
+/// Removes the registered decode error hook, if any.
+pub fn clear_decode_error_hook() {
+    *DECODE_ERROR_HOOK.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Passes `result` through, calling the decode error hook first if it is an error.
+#[doc(hidden)]
+pub fn report_decode_error<T, E: Display>(hash: &str, result: Result<T, E>) -> Result<T, E> {
+    if let Err(e) = &result
+        && let Some(hook) = *DECODE_ERROR_HOOK.read().unwrap_or_else(PoisonError::into_inner)
+    {
+        hook(hash, &SerdeHashError::InvalidHash(e.to_string()));
+    }
+    result
+}
@@ -0,0 +1,41 @@
+//! Exchanging hashes with other Hashids implementations.
+
+/// Compatibility with [hashids.js](https://github.com/niieani/hashids.js), as used by
+/// Node services.
+///
+/// Hashes only match when both sides use the same salt, minimum length and alphabet, and
+/// this side has [`SerdeHashOptions::with_js_compat`] enabled. [`options`] builds such a
+/// configuration with the same defaults as `new Hashids(salt, minLength)`.
+///
+/// # Example
+/// ```
+/// use serde_hash::encoder::HashIdsEncoder;
+/// use serde_hash::interop::hashids_js;
+///
+/// // new Hashids('this is my salt', 0, '0123456789abcdef').encode(1, 2, 3)
+/// let options = hashids_js::options("this is my salt", 0).with_alphabet("0123456789abcdef");
+/// let encoder = HashIdsEncoder::new(&options).unwrap();
+/// assert_eq!(encoder.encode(&[1, 2, 3]), "68c508");
+/// assert_eq!(encoder.decode("68c508").unwrap(), [1, 2, 3]);
+/// ```
+///
+/// [`SerdeHashOptions::with_js_compat`]: crate::hashids::SerdeHashOptions::with_js_compat
+pub mod hashids_js {
+    use crate::hashids::SerdeHashOptions;
+
+    /// The alphabet hashids.js uses when none is given.
+    pub const DEFAULT_ALPHABET: &str =
+        "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890";
+
+    /// Creates options that encode and decode like `new Hashids(salt, min_length)`.
+    ///
+    /// Use [`SerdeHashOptions::with_alphabet`] on the result when the JavaScript side
+    /// passes a custom alphabet.
+    pub fn options(salt: impl AsRef<str>, min_length: usize) -> SerdeHashOptions {
+        SerdeHashOptions::new()
+            .with_salt(salt)
+            .with_min_length(min_length)
+            .with_alphabet(DEFAULT_ALPHABET)
+            .with_js_compat(true)
+    }
+}
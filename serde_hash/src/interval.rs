@@ -0,0 +1,180 @@
+//! Intervals encoded as a single hash string.
+//!
+//! APIs that expose opaque "window" tokens for a range of ids or timestamps can encode
+//! both endpoints into one hash. The module doubles as a serde `with` module for
+//! `Range<T>`, `RangeInclusive<T>` and `(T, T)` fields, and [`option`] handles the
+//! same types wrapped in `Option`.
+//!
+//! # Example
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use serde_hash::hashids::SerdeHashOptions;
+//! use std::ops::Range;
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! pub struct Window {
+//!     #[serde(with = "serde_hash::interval")]
+//!     pub ids: Range<u64>,
+//! }
+//!
+//! SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+//!
+//! let window = Window { ids: 100..200 };
+//! let json = serde_json::to_string(&window).unwrap();
+//! assert_eq!(serde_json::from_str::<Window>(&json).unwrap(), window);
+//! ```
+
+use crate::error::{Result, SerdeHashError};
+use crate::hashids::{decode, encode};
+use crate::hook::report_decode_error;
+use crate::serde_impl::{
+    HashNumeric, join_limbs, numeric_from_u128, numeric_to_u128, serialize_hash,
+};
+use serde::{Deserialize, Deserializer, Serializer};
+use std::ops::{Range, RangeInclusive};
+
+/// Types with a start and an end that can be encoded into one hash.
+pub trait HashInterval: Sized {
+    /// Returns the start and end, as stored in the hash.
+    fn endpoints(&self) -> (u128, u128);
+
+    /// Rebuilds the interval from decoded endpoints, or returns `None` if they don't fit
+    /// the endpoint type.
+    fn from_endpoints(start: u128, end: u128) -> Option<Self>;
+}
+
+impl<T: HashNumeric> HashInterval for Range<T> {
+    fn endpoints(&self) -> (u128, u128) {
+        (numeric_to_u128(self.start), numeric_to_u128(self.end))
+    }
+
+    fn from_endpoints(start: u128, end: u128) -> Option<Self> {
+        Some(numeric_from_u128(start)?..numeric_from_u128(end)?)
+    }
+}
+
+impl<T: HashNumeric> HashInterval for RangeInclusive<T> {
+    fn endpoints(&self) -> (u128, u128) {
+        (numeric_to_u128(*self.start()), numeric_to_u128(*self.end()))
+    }
+
+    fn from_endpoints(start: u128, end: u128) -> Option<Self> {
+        Some(numeric_from_u128(start)?..=numeric_from_u128(end)?)
+    }
+}
+
+impl<T: HashNumeric> HashInterval for (T, T) {
+    fn endpoints(&self) -> (u128, u128) {
+        (numeric_to_u128(self.0), numeric_to_u128(self.1))
+    }
+
+    fn from_endpoints(start: u128, end: u128) -> Option<Self> {
+        Some((numeric_from_u128(start)?, numeric_from_u128(end)?))
+    }
+}
+
+/// Encodes both endpoints of `interval` into one hash string.
+///
+/// # Arguments
+///
+/// * `interval` - The interval to encode, e.g. `100..200`.
+///
+/// # Returns
+///
+/// A string that represents the encoded hash of the interval. If either endpoint is above
+/// `u64::MAX`, the hash holds both limbs of each, most significant first.
+pub fn encode_interval<I: HashInterval>(interval: &I) -> String {
+    let (start, end) = interval.endpoints();
+    match (u64::try_from(start), u64::try_from(end)) {
+        (Ok(start), Ok(end)) => encode(&[start, end]),
+        _ => encode(&[(start >> 64) as u64, start as u64, (end >> 64) as u64, end as u64]),
+    }
+}
+
+/// Decodes a hash produced by [`encode_interval`].
+///
+/// # Returns
+///
+/// * The interval, on success.
+/// * An error if the hash is invalid, doesn't hold exactly two values, ends before it
+///   starts, or has endpoints that don't fit the endpoint type.
+pub fn decode_interval<I: HashInterval>(hash: impl AsRef<str>) -> Result<I> {
+    let values = decode(hash)?;
+    let (start, end) = match values[..] {
+        [start, end] => (start as u128, end as u128),
+        [start_high, start_low, end_high, end_low] if start_high != 0 || end_high != 0 => {
+            (join_limbs(start_high, start_low), join_limbs(end_high, end_low))
+        }
+        _ => {
+            return Err(SerdeHashError::InvalidHash(format!(
+                "expected 2 values in interval, found {}",
+                values.len()
+            )));
+        }
+    };
+    if start > end {
+        return Err(SerdeHashError::InvalidHash(format!(
+            "interval ends at {} before it starts at {}",
+            end, start
+        )));
+    }
+    match I::from_endpoints(start, end) {
+        Some(interval) => Ok(interval),
+        None => Err(SerdeHashError::InvalidHash(format!(
+            "interval {}..{} does not fit in {}",
+            start,
+            end,
+            std::any::type_name::<I>()
+        ))),
+    }
+}
+
+fn decode_field<I: HashInterval, E: serde::de::Error>(hash: &str) -> Result<I, E> {
+    report_decode_error(hash, decode_interval(hash)).map_err(E::custom)
+}
+
+/// Serializes an interval field as one hash string.
+///
+/// Usage: `#[serde(with = "serde_hash::interval")]`
+pub fn serialize<I: HashInterval, S: Serializer>(
+    interval: &I,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serialize_hash(&encode_interval(interval), serializer)
+}
+
+/// Deserializes an interval field from one hash string.
+///
+/// Usage: `#[serde(with = "serde_hash::interval")]`
+pub fn deserialize<'de, I: HashInterval, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<I, D::Error> {
+    let hash = String::deserialize(deserializer)?;
+    decode_field(&hash)
+}
+
+/// Serde `with` module for `Option` interval fields.
+///
+/// Usage: `#[serde(with = "serde_hash::interval::option")]`
+pub mod option {
+    use super::*;
+
+    pub fn serialize<I: HashInterval, S: Serializer>(
+        interval: &Option<I>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match interval {
+            Some(interval) => serializer.serialize_some(&encode_interval(interval)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, I: HashInterval, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<I>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(hash) => decode_field(&hash).map(Some),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! Decoding hashed ids inside [RFC 6902](https://www.rfc-editor.org/rfc/rfc6902) JSON Patch
+//! documents.
+//!
+//! PATCH endpoints receive the same hashes the API hands out, but a patch is applied to the
+//! domain model's numeric representation. [`PatchIds`] rewrites a patch in place so that
+//! every configured id is a plain number before the patch is applied, for example with the
+//! [`json-patch`](https://docs.rs/json-patch) crate.
+//!
+//! Two kinds of places are rewritten:
+//!
+//! * **Id fields** (see [`PatchIds::with_field`]): an operation's `value` is decoded when its
+//!   `path` ends in an id field (`/manager`) or in an element of one (`/friends/0`,
+//!   `/friends/-`). Objects inside a `value` are searched for id fields as well.
+//! * **Keyed collections** (see [`PatchIds::with_keyed_collection`]): maps keyed by id. The
+//!   path segment after the collection name (`/members/qKknODM7Ej/role`) is decoded in both
+//!   `path` and `from`, and so are the keys of the collection inside a `value`.
+//!
+//! # Example
+//! ```
+//! use serde_hash::hashids::SerdeHashOptions;
+//! use serde_hash::json_patch::PatchIds;
+//! use serde_json::json;
+//!
+//! SerdeHashOptions::new()
+//!     .with_salt("hello world")
+//!     .with_weak_salt_allowed(true)
+//!     .with_min_length(10)
+//!     .build();
+//!
+//! let mut patch = json!([
+//!     { "op": "replace", "path": "/manager", "value": "qKknODM7Ej" },
+//!     { "op": "remove", "path": "/members/qKknODM7Ej" },
+//! ]);
+//! PatchIds::new()
+//!     .with_field("manager")
+//!     .with_keyed_collection("members")
+//!     .decode(&mut patch)
+//!     .unwrap();
+//!
+//! assert_eq!(
+//!     patch,
+//!     json!([
+//!         { "op": "replace", "path": "/manager", "value": 158674 },
+//!         { "op": "remove", "path": "/members/158674" },
+//!     ])
+//! );
+//! ```
+
+use crate::error::{Result, SerdeHashError};
+use crate::serde_impl::decode_field;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+/// Describes which parts of a JSON Patch hold hashed ids.
+#[derive(Debug, Clone, Default)]
+pub struct PatchIds {
+    fields: HashSet<String>,
+    keyed: HashSet<String>,
+}
+
+impl PatchIds {
+    /// Creates an empty description; no ids are decoded until fields are added.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks `name` as an id field. Its values may be a hash, an array of hashes (nested to
+    /// any depth) or `null`.
+    pub fn with_field(mut self, name: impl Into<String>) -> Self {
+        self.fields.insert(name.into());
+        self
+    }
+
+    /// Marks `name` as a map keyed by hashed ids.
+    pub fn with_keyed_collection(mut self, name: impl Into<String>) -> Self {
+        self.keyed.insert(name.into());
+        self
+    }
+
+    /// Decodes every configured id in `patch` in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `patch` - A JSON Patch document, i.e. an array of operation objects.
+    ///
+    /// # Returns
+    ///
+    /// An error if `patch` isn't an array of operations or if any id fails to decode. The
+    /// patch may be partially rewritten when an error is returned.
+    pub fn decode(&self, patch: &mut Value) -> Result<()> {
+        let operations = patch
+            .as_array_mut()
+            .ok_or_else(|| {
+                SerdeHashError::Malformed("a JSON Patch must be an array of operations".into())
+            })?;
+        for (index, operation) in operations.iter_mut().enumerate() {
+            let operation = operation
+                .as_object_mut()
+                .ok_or_else(|| {
+                    SerdeHashError::Malformed(format!("operation {} is not an object", index))
+                })?;
+            self.decode_operation(operation)
+                .map_err(|e| e.context(format!("in operation {}", index)))?;
+        }
+        Ok(())
+    }
+
+    fn decode_operation(&self, operation: &mut Map<String, Value>) -> Result<()> {
+        for key in ["path", "from"] {
+            if let Some(Value::String(pointer)) = operation.get_mut(key) {
+                *pointer = self.decode_pointer(pointer)?;
+            }
+        }
+
+        let segments: Vec<String> = match operation.get("path") {
+            Some(Value::String(pointer)) => pointer.split('/').skip(1).map(unescape).collect(),
+            _ => return Ok(()),
+        };
+        let Some(value) = operation.get_mut("value") else {
+            return Ok(());
+        };
+        match segments.as_slice() {
+            [.., last] if self.fields.contains(last) => decode_ids(value),
+            [.., parent, last] if self.fields.contains(parent) && is_array_index(last) => {
+                decode_ids(value)
+            }
+            [.., last] if self.keyed.contains(last) => self.decode_keyed(value),
+            _ => self.decode_value(value),
+        }
+    }
+
+    /// Decodes the segment after each keyed collection in a JSON Pointer.
+    fn decode_pointer(&self, pointer: &str) -> Result<String> {
+        let mut segments: Vec<String> = pointer.split('/').map(str::to_string).collect();
+        for i in 2..segments.len() {
+            if self.keyed.contains(&unescape(&segments[i - 1])) {
+                segments[i] = decode_field(&unescape(&segments[i]))?.to_string();
+            }
+        }
+        Ok(segments.join("/"))
+    }
+
+    /// Searches an arbitrary value for id fields and keyed collections.
+    fn decode_value(&self, value: &mut Value) -> Result<()> {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map.iter_mut() {
+                    if self.fields.contains(key) {
+                        decode_ids(child)?;
+                    } else if self.keyed.contains(key) {
+                        self.decode_keyed(child)?;
+                    } else {
+                        self.decode_value(child)?;
+                    }
+                }
+                Ok(())
+            }
+            Value::Array(values) => values.iter_mut().try_for_each(|v| self.decode_value(v)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Decodes the keys of a map keyed by hashed ids.
+    fn decode_keyed(&self, value: &mut Value) -> Result<()> {
+        let Value::Object(map) = value else {
+            return self.decode_value(value);
+        };
+        let mut decoded = Map::new();
+        for (key, mut child) in std::mem::take(map) {
+            self.decode_value(&mut child)?;
+            decoded.insert(decode_field(&key)?.to_string(), child);
+        }
+        *map = decoded;
+        Ok(())
+    }
+}
+
+/// Replaces a hash, or every hash in a (nested) array, with its numeric id.
+fn decode_ids(value: &mut Value) -> Result<()> {
+    match value {
+        Value::String(hash) => {
+            *value = Value::from(decode_field(hash)?);
+            Ok(())
+        }
+        Value::Array(values) => values.iter_mut().try_for_each(decode_ids),
+        Value::Null => Ok(()),
+        other => Err(SerdeHashError::InvalidHash(format!("expected a hash, found {}", other))),
+    }
+}
+
+fn is_array_index(segment: &str) -> bool {
+    segment == "-" || (!segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Undoes the `~1` and `~0` escapes of a JSON Pointer segment.
+fn unescape(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
@@ -1,5 +1,79 @@
 #![doc = include_str!("../README.MD")]
+#[cfg(not(feature = "std"))]
+compile_error!("serde_hash doesn't support `no_std` yet, enable the `std` feature");
+#[cfg(feature = "hmac")]
+pub use consistency::{consistency_token, verify_consistency_token};
+pub use diagnostics::{Explanation, explain};
+pub use display::HashDisplay;
+pub use entity::{HasHashId, PublicId};
+pub use hashed_id::HashedId;
+pub use error::{Result, SerdeHashError};
+pub use hook::set_decode_error_hook;
+pub use reflect::HashedFields;
+#[cfg(feature = "derive")]
 pub use serde_hash_derive::*;
+/// The serde version this crate is built against, which generated code names.
+pub use serde;
+pub mod alphabet;
+#[cfg(feature = "axum")]
+pub mod axum;
+#[cfg(feature = "json")]
+pub mod bulk;
+#[cfg(feature = "csv")]
+pub mod csv;
+mod codec;
+#[cfg(feature = "hmac")]
+pub mod consistency;
+pub mod context;
+#[cfg(feature = "cursor")]
+pub mod cursor;
+pub mod diagnostics;
+pub mod display;
+pub mod encoder;
+pub mod entity;
+pub mod error;
+#[cfg(feature = "expiry")]
+pub mod expiry;
+pub mod hashed_id;
 pub mod hashids;
+pub mod hook;
+pub mod interop;
+#[cfg(feature = "interval")]
+pub mod interval;
+#[cfg(feature = "json-patch")]
+pub mod json_patch;
+mod msgpack;
+#[cfg(feature = "hmac")]
+pub mod orderable;
+pub mod ordered;
+pub mod patterns;
+pub mod prelude;
+pub mod profile;
+#[cfg(feature = "proto")]
+pub mod proto;
+pub mod provider;
+pub mod reflect;
+#[cfg(feature = "registry")]
+pub mod registry;
 pub mod salt;
+pub mod scaled;
+mod secret;
+#[cfg(feature = "serde_with")]
+pub mod serde_as;
 pub mod serde_impl;
+pub mod telemetry;
+#[cfg(any(feature = "tera", feature = "askama"))]
+pub mod templates;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod url;
+#[cfg(feature = "json")]
+pub mod value;
+
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "json")]
+    pub use serde_json;
+    pub use serde::{Deserializer, Serializer};
+    pub use crate::telemetry::DeriveSpan;
+}
@@ -0,0 +1,119 @@
+//! Hash fields as MessagePack extension values, see
+//! [`SerdeHashOptions::with_msgpack_ext`](crate::hashids::SerdeHashOptions::with_msgpack_ext).
+//!
+//! rmp-serde writes a newtype struct with the name below, holding an `(i8, bytes)` tuple,
+//! as an extension value of that type, and hands extension values back the same way.
+
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::{Serialize, Serializer};
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+
+/// The newtype name rmp-serde maps to extension values.
+const EXT_STRUCT_NAME: &str = "_ExtStruct";
+
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// Writes the limbs of a value as an extension value of type `tag`, holding the value
+/// big-endian in 8 bytes, or 16 if it doesn't fit in a `u64`.
+pub(crate) fn serialize_ext<S: Serializer>(
+    tag: i8,
+    [high, low]: [u64; 2],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let bytes = ((high as u128) << 64 | low as u128).to_be_bytes();
+    let data = if high == 0 { &bytes[8..] } else { &bytes[..] };
+    serializer.serialize_newtype_struct(EXT_STRUCT_NAME, &(tag, Bytes(data)))
+}
+
+/// Reads the limbs written by [`serialize_ext`], or the values of a hash string passed
+/// to `decode`, so payloads written before the extension type was enabled still read.
+pub(crate) fn deserialize_ext<'de, D, F, E>(
+    deserializer: D,
+    tag: i8,
+    decode: F,
+) -> Result<Vec<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+    F: Fn(&str) -> Result<Vec<u64>, E>,
+    E: Display,
+{
+    deserializer.deserialize_any(ExtVisitor(tag, decode, PhantomData))
+}
+
+struct ExtVisitor<F, E>(i8, F, PhantomData<fn() -> E>);
+
+impl<'de, F, E> Visitor<'de> for ExtVisitor<F, E>
+where
+    F: Fn(&str) -> Result<Vec<u64>, E>,
+    E: Display,
+{
+    type Value = Vec<u64>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a hash or a MessagePack extension value of type {}", self.0)
+    }
+
+    fn visit_str<Err: de::Error>(self, hash: &str) -> Result<Self::Value, Err> {
+        (self.1)(hash).map_err(Err::custom)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_tuple(2, self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let tag: i8 = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        if tag != self.0 {
+            return Err(de::Error::custom(format!(
+                "expected MessagePack extension type {}, found {}",
+                self.0, tag
+            )));
+        }
+        let ExtData(values) = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(values)
+    }
+}
+
+/// The data of an extension value, as hash values: one for 8 bytes, both limbs for 16.
+struct ExtData(Vec<u64>);
+
+impl<'de> serde::Deserialize<'de> for ExtData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DataVisitor;
+
+        impl Visitor<'_> for DataVisitor {
+            type Value = ExtData;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("8 or 16 bytes")
+            }
+
+            fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<ExtData, E> {
+                match bytes.len() {
+                    8 => Ok(ExtData(vec![u64::from_be_bytes(bytes.try_into().unwrap())])),
+                    16 => {
+                        let value = u128::from_be_bytes(bytes.try_into().unwrap());
+                        Ok(ExtData(vec![(value >> 64) as u64, value as u64]))
+                    }
+                    len => Err(E::invalid_length(len, &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_bytes(DataVisitor)
+    }
+}
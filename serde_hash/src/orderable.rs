@@ -0,0 +1,346 @@
+//! Public ids whose strings sort like the numbers they encode.
+//!
+//! Hashids scramble the order of the ids they encode, which breaks consumers that sort by
+//! the public id, such as a database index on the encoded column or a client paging by
+//! "greater than the last id". Orderable ids trade some opacity for order: every id is
+//! written as a fixed-width number in the configured alphabet sorted by character, so
+//! comparing two strings byte by byte compares the ids.
+//!
+//! The salt still decides the strings. Before an id is written it is multiplied by a
+//! salt-derived stride and shifted by a salt-derived offset, so consecutive ids aren't
+//! consecutive strings and a string only decodes if it lands exactly on a stride.
+//! Unlike hashids, the strings still reveal which of two ids is larger and roughly how
+//! far apart they are; use [`crate::ordered::OrderedHash`] when only Rust code needs the
+//! order.
+//!
+//! Only the salt and alphabet of the active options are used. The minimum length,
+//! checksum and other hashids options don't apply, and `u128` ids take twice the width
+//! of the other types so every id of one type has the same length.
+//!
+//! The module doubles as a serde `with` module for numeric fields.
+//!
+//! # Example
+//! ```
+//! use serde_hash::hashids::SerdeHashOptions;
+//! use serde_hash::orderable::{decode_orderable, encode_orderable};
+//!
+//! SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+//!
+//! let (small, large) = (encode_orderable(9), encode_orderable(10));
+//! assert!(small < large);
+//! assert_eq!(small.len(), large.len());
+//! assert_eq!(decode_orderable(&large).unwrap(), 10);
+//! ```
+
+use crate::error::{Result, SerdeHashError};
+use crate::hashids::get_hash_options;
+use crate::hook::report_decode_error;
+use crate::provider::active_context;
+use crate::serde_impl::HashNumeric;
+use hmac::{Hmac, KeyInit, Mac};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::Sha256;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// The sorted alphabet, stride and offset orderable ids are written with.
+struct OrderableKey {
+    alphabet: Vec<char>,
+    stride: u128,
+    offset: u128,
+    /// How many characters one `u64` limb takes.
+    width: usize,
+}
+
+impl OrderableKey {
+    /// Derives the key from the salt and alphabet of the active options.
+    fn current() -> Self {
+        let context = active_context();
+        let options = match &context {
+            Some(context) => context.options(),
+            None => get_hash_options(),
+        };
+        let mut alphabet: Vec<char> = options.alphabet().chars().collect();
+        alphabet.sort_unstable();
+        alphabet.dedup();
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(options.salt().as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(b"serde_hash orderable");
+        let digest = mac.finalize().into_bytes();
+        let offset = u64::from_be_bytes(digest[..8].try_into().unwrap());
+        // Keep the stride above 2^15, so neighbouring ids are never close together.
+        let stride = u16::from_be_bytes([digest[8], digest[9]]) | 0x8000;
+
+        let max = u128::from(u64::MAX) * u128::from(stride) + u128::from(offset);
+        let base = alphabet.len() as u128;
+        let mut width = 1;
+        let mut capacity = base;
+        while capacity <= max {
+            capacity *= base;
+            width += 1;
+        }
+        Self {
+            alphabet,
+            stride: u128::from(stride),
+            offset: u128::from(offset),
+            width,
+        }
+    }
+
+    fn encode(&self, limbs: &[u64]) -> String {
+        let base = self.alphabet.len() as u128;
+        let mut encoded = String::with_capacity(self.width * limbs.len());
+        for &limb in limbs {
+            let mut number = u128::from(limb) * self.stride + self.offset;
+            let mut digits = vec![self.alphabet[0]; self.width];
+            for digit in digits.iter_mut().rev() {
+                *digit = self.alphabet[(number % base) as usize];
+                number /= base;
+            }
+            encoded.extend(digits);
+        }
+        encoded
+    }
+
+    fn decode(&self, hash: &str, count: usize) -> Result<Vec<u64>> {
+        let chars: Vec<char> = hash.chars().collect();
+        if chars.len() != self.width * count {
+            return Err(SerdeHashError::InvalidHash(format!(
+                "{} is not an orderable id (expected {} characters, found {})",
+                hash,
+                self.width * count,
+                chars.len()
+            )));
+        }
+        let base = self.alphabet.len() as u128;
+        chars
+            .chunks(self.width)
+            .map(|digits| {
+                let mut number = 0u128;
+                for c in digits {
+                    let Ok(position) = self.alphabet.binary_search(c) else {
+                        return Err(SerdeHashError::InvalidHash(format!(
+                            "{} is not an orderable id (unexpected character {:?})",
+                            hash, c
+                        )));
+                    };
+                    number = number * base + position as u128;
+                }
+                match number.checked_sub(self.offset) {
+                    Some(shifted) if shifted % self.stride == 0 => {
+                        u64::try_from(shifted / self.stride).ok()
+                    }
+                    _ => None,
+                }
+                .ok_or_else(|| {
+                    SerdeHashError::InvalidHash(format!("{} is not an orderable id", hash))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns how many `u64` limbs an orderable id of type `T` takes.
+fn limb_count<T>() -> usize {
+    std::mem::size_of::<T>().div_ceil(8).max(1)
+}
+
+/// Encodes a `u64` id into a fixed-width string that sorts like the id.
+///
+/// # Arguments
+///
+/// * `value` - The id to be encoded.
+///
+/// # Returns
+///
+/// A string whose length only depends on the active alphabet and salt.
+pub fn encode_orderable(value: u64) -> String {
+    OrderableKey::current().encode(&[value])
+}
+
+/// Decodes a string produced by [`encode_orderable`].
+///
+/// # Returns
+///
+/// * On success, the id.
+/// * On failure, an error if the string has the wrong length, contains characters outside
+///   the alphabet, or wasn't produced with the active salt.
+pub fn decode_orderable(hash: impl AsRef<str>) -> Result<u64> {
+    let values = OrderableKey::current().decode(hash.as_ref(), 1)?;
+    Ok(values[0])
+}
+
+fn encode_numeric<T: HashNumeric>(value: T) -> String {
+    let key = OrderableKey::current();
+    match limb_count::<T>() {
+        1 => key.encode(&value.to_limbs()[1..]),
+        _ => key.encode(&value.to_limbs()),
+    }
+}
+
+fn decode_numeric<T: HashNumeric>(hash: &str) -> Result<T> {
+    let values = OrderableKey::current().decode(hash, limb_count::<T>())?;
+    let limbs = match values[..] {
+        [low] => [0, low],
+        [high, low] => [high, low],
+        _ => unreachable!("orderable ids have one or two limbs"),
+    };
+    match T::try_from_limbs(limbs) {
+        Some(value) => Ok(value),
+        None => Err(SerdeHashError::InvalidHash(format!(
+            "orderable id {} does not fit in {}",
+            hash,
+            std::any::type_name::<T>()
+        ))),
+    }
+}
+
+/// A numeric id that displays and serializes as an orderable string, see the
+/// [module documentation](self).
+///
+/// The `rkyv` feature adds rkyv's `Archive`, `Serialize` and `Deserialize`, which store the
+/// raw number.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct Orderable<T>(T);
+
+impl<T: HashNumeric> Orderable<T> {
+    /// Wraps a numeric id.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns the numeric id.
+    pub fn value(&self) -> T {
+        self.0
+    }
+
+    /// Unwraps the numeric id.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// Encodes the id with the active options.
+    pub fn encode(&self) -> String {
+        encode_numeric(self.0)
+    }
+
+    /// Decodes a string produced by [`Orderable::encode`].
+    ///
+    /// # Returns
+    ///
+    /// An error if the string isn't a valid orderable id or decodes to a value that
+    /// doesn't fit in `T`.
+    pub fn decode(hash: impl AsRef<str>) -> Result<Self> {
+        decode_numeric(hash.as_ref()).map(Self)
+    }
+}
+
+impl<T: HashNumeric> From<T> for Orderable<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: HashNumeric> PartialEq for Orderable<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_limbs() == other.0.to_limbs()
+    }
+}
+
+impl<T: HashNumeric> Eq for Orderable<T> {}
+
+impl<T: HashNumeric> PartialOrd for Orderable<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: HashNumeric> Ord for Orderable<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.to_limbs().cmp(&other.0.to_limbs())
+    }
+}
+
+impl<T: HashNumeric> Hash for Orderable<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_limbs().hash(state);
+    }
+}
+
+impl<T: HashNumeric> fmt::Display for Orderable<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl<T: HashNumeric> FromStr for Orderable<T> {
+    type Err = SerdeHashError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::decode(s)
+    }
+}
+
+impl<T: HashNumeric> Serialize for Orderable<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T: HashNumeric> Deserialize<'de> for Orderable<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer).map(Self)
+    }
+}
+
+/// Serializes a numeric field as an orderable string.
+///
+/// Usage: `#[serde(with = "serde_hash::orderable")]`
+pub fn serialize<T: HashNumeric, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&encode_numeric(*value))
+}
+
+/// Deserializes a numeric field from an orderable string.
+///
+/// Usage: `#[serde(with = "serde_hash::orderable")]`
+pub fn deserialize<'de, T: HashNumeric, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    let hash = String::deserialize(deserializer)?;
+    report_decode_error(&hash, decode_numeric(&hash)).map_err(serde::de::Error::custom)
+}
+
+/// Serde `with` module for `Option` numeric fields.
+///
+/// Usage: `#[serde(with = "serde_hash::orderable::option")]`
+pub mod option {
+    use super::*;
+
+    pub fn serialize<T: HashNumeric, S: Serializer>(
+        value: &Option<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_some(&encode_numeric(*value)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, T: HashNumeric, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<T>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(hash) => report_decode_error(&hash, decode_numeric(&hash))
+                .map(Some)
+                .map_err(serde::de::Error::custom),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Hashed ids that still sort by their numeric value.
+//!
+//! Hash strings don't sort like the numbers they encode, so sorting a page of encoded ids
+//! (or keying a `BTreeMap` by them) scrambles the order. An [`OrderedHash`] keeps the
+//! original value: comparisons, equality and hashing use the number, while `Display` and
+//! serialization use the hash string.
+//!
+//! # Example
+//! ```
+//! use serde_hash::hashids::SerdeHashOptions;
+//! use serde_hash::ordered::OrderedHash;
+//!
+//! SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+//!
+//! let mut ids: Vec<OrderedHash<u64>> = vec![30.into(), 2.into(), 100.into()];
+//! ids.sort();
+//! assert_eq!(ids.iter().map(|id| id.value()).collect::<Vec<_>>(), [2, 30, 100]);
+//!
+//! let json = serde_json::to_string(&ids).unwrap();
+//! assert_eq!(serde_json::from_str::<Vec<OrderedHash<u64>>>(&json).unwrap(), ids);
+//! ```
+
+use crate::entity::PublicId;
+use crate::error::{Result, SerdeHashError};
+use crate::serde_impl::{HashNumeric, decode_id, encode_id, numeric};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// A numeric id that displays and serializes as its hash string but compares, sorts and
+/// hashes as the number.
+///
+/// With the `rkyv` feature, rkyv archives hold the raw number rather than the hash.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct OrderedHash<T>(T);
+
+impl<T: HashNumeric> OrderedHash<T> {
+    /// Wraps a numeric id.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns the numeric id.
+    pub fn value(&self) -> T {
+        self.0
+    }
+
+    /// Unwraps the numeric id.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// Encodes the id with the global options.
+    pub fn encode(&self) -> String {
+        encode_id(self.0)
+    }
+
+    /// Decodes a hash produced by [`OrderedHash::encode`].
+    ///
+    /// # Returns
+    ///
+    /// An error if the hash is invalid or decodes to a value that doesn't fit in `T`.
+    pub fn decode(hash: impl AsRef<str>) -> Result<Self> {
+        decode_id(hash.as_ref()).map(Self)
+    }
+}
+
+impl<T: HashNumeric> From<T> for OrderedHash<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: HashNumeric> PartialEq for OrderedHash<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_limbs() == other.0.to_limbs()
+    }
+}
+
+impl<T: HashNumeric> Eq for OrderedHash<T> {}
+
+impl<T: HashNumeric> PartialOrd for OrderedHash<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: HashNumeric> Ord for OrderedHash<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.to_limbs().cmp(&other.0.to_limbs())
+    }
+}
+
+impl<T: HashNumeric> Hash for OrderedHash<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_limbs().hash(state);
+    }
+}
+
+impl<T: HashNumeric> fmt::Display for OrderedHash<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl<T: HashNumeric> FromStr for OrderedHash<T> {
+    type Err = SerdeHashError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::decode(s)
+    }
+}
+
+impl<T: HashNumeric> PublicId for OrderedHash<T> {
+    type Id = Self;
+
+    fn as_public_id(&self) -> String {
+        self.encode()
+    }
+
+    fn parse_public_id(public_id: &str) -> Result<Self> {
+        Self::decode(public_id)
+    }
+}
+
+impl<T: HashNumeric> Serialize for OrderedHash<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        numeric::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T: HashNumeric> Deserialize<'de> for OrderedHash<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        numeric::deserialize(deserializer).map(Self)
+    }
+}
@@ -0,0 +1,112 @@
+//! Key patterns that select which fields of a dynamic payload hold ids.
+//!
+//! Payloads without a Rust type, such as the `serde_json::Value`s handled by
+//! [`crate::value`], can't be annotated field by field. Instead, the options list the
+//! keys to hash with [`SerdeHashOptions::with_field_patterns`]:
+//!
+//! * A glob, matched against the whole key. `*` matches any run of characters and `?`
+//!   matches one, so `"*_id"` matches `user_id` but not `identity`.
+//! * A regular expression between slashes, e.g. `"/^(user|org)_id$/"`. Regex patterns need
+//!   the `regex` feature, and match anywhere in the key unless anchored.
+//!
+//! [`SerdeHashOptions::with_field_patterns`]: crate::hashids::SerdeHashOptions::with_field_patterns
+
+use crate::error::SerdeHashError;
+
+/// A single compiled field pattern.
+#[derive(Debug, Clone)]
+enum Pattern {
+    Glob(Vec<char>),
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+impl Pattern {
+    fn parse(pattern: &str) -> Result<Self, SerdeHashError> {
+        let invalid = |reason: String| SerdeHashError::InvalidFieldPattern {
+            pattern: pattern.to_string(),
+            reason,
+        };
+        match pattern.strip_prefix('/').and_then(|rest| rest.strip_suffix('/')) {
+            #[cfg(feature = "regex")]
+            Some(expression) => regex::Regex::new(expression)
+                .map(Self::Regex)
+                .map_err(|e| invalid(e.to_string())),
+            #[cfg(not(feature = "regex"))]
+            Some(_) => Err(invalid(
+                "regex patterns need the `regex` feature".to_string(),
+            )),
+            None if pattern.is_empty() => Err(invalid("the pattern is empty".to_string())),
+            None => Ok(Self::Glob(pattern.chars().collect())),
+        }
+    }
+
+    fn matches(&self, key: &str) -> bool {
+        match self {
+            Self::Glob(glob) => glob_matches(glob, &key.chars().collect::<Vec<_>>()),
+            #[cfg(feature = "regex")]
+            Self::Regex(regex) => regex.is_match(key),
+        }
+    }
+}
+
+/// Matches `key` against a glob, backtracking to the most recent `*` on a mismatch.
+fn glob_matches(glob: &[char], key: &[char]) -> bool {
+    let (mut g, mut k) = (0, 0);
+    let mut star = None;
+    while k < key.len() {
+        match glob.get(g) {
+            Some('*') => {
+                star = Some((g, k));
+                g += 1;
+            }
+            Some(&c) if c == '?' || c == key[k] => {
+                g += 1;
+                k += 1;
+            }
+            _ => match star {
+                Some((star_g, star_k)) => {
+                    g = star_g + 1;
+                    k = star_k + 1;
+                    star = Some((star_g, star_k + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    glob[g..].iter().all(|&c| c == '*')
+}
+
+/// The compiled field patterns of a set of options.
+#[derive(Debug, Clone, Default)]
+pub struct FieldPatterns(Vec<Pattern>);
+
+impl FieldPatterns {
+    /// Compiles `patterns`, see the [module documentation](self) for the syntax.
+    ///
+    /// # Returns
+    ///
+    /// * The compiled patterns.
+    /// * [`SerdeHashError::InvalidFieldPattern`] for the first pattern that doesn't compile.
+    pub fn new<I, S>(patterns: I) -> Result<Self, SerdeHashError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        patterns
+            .into_iter()
+            .map(|pattern| Pattern::parse(pattern.as_ref()))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    /// Returns `true` if any pattern matches `key`.
+    pub fn matches(&self, key: &str) -> bool {
+        self.0.iter().any(|pattern| pattern.matches(key))
+    }
+
+    /// Returns `true` if there are no patterns, so no key matches.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
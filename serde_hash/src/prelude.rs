@@ -0,0 +1,30 @@
+//! Everything a typical crate needs, in one import.
+//!
+//! ```
+//! use serde_hash::prelude::*;
+//!
+//! #[serde_hash]
+//! #[derive(Serialize, Deserialize)]
+//! pub struct User {
+//!     #[serde(hash)]
+//!     pub id: u64,
+//!     #[serde(with = "option_numeric")]
+//!     pub team_id: Option<u64>,
+//! }
+//!
+//! SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+//! ```
+//!
+//! `Serialize` and `Deserialize` are serde's traits and derives, re-exported with
+//! [`serde`](crate::serde). Code generated by this crate's macros names serde through the
+//! same re-export, so `serde` doesn't have to be a dependency of its own.
+
+pub use crate::alphabet::Alphabet;
+pub use crate::display::HashDisplay;
+pub use crate::entity::{HasHashId, PublicId};
+pub use crate::hashed_id::HashedId;
+pub use crate::hashids::SerdeHashOptions;
+pub use crate::serde_impl::{numeric, option_numeric, option_vec_numeric, vec_numeric};
+#[cfg(feature = "derive")]
+pub use crate::{HashIds, hashable, serde_hash};
+pub use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -0,0 +1,105 @@
+//! Choosing per call whether hash fields are written as hashes.
+//!
+//! The same struct often travels to more than one audience: public API responses need
+//! hashes, an internal queue or cache is simpler with the raw ids, and a log line is most
+//! useful with both. A [`SerializationProfile`] picks the representation for one call,
+//! either around a closure with [`with_profile`] or as a wrapper with [`Profiled`]:
+//!
+//! | Profile    | `158674` serializes as   |
+//! |------------|--------------------------|
+//! | `Public`   | `"qKknODM7Ej"`           |
+//! | `Internal` | `158674`                 |
+//! | `Debug`    | `"158674 (qKknODM7Ej)"`  |
+//!
+//! The profile applies to every reversible hash field, whichever macro or `with` module
+//! produced it. Under `Internal`, deserialization also reads raw numbers, so internal
+//! payloads round-trip, except through the lenient modules, which always expect a hash.
+//! `Debug` output is meant for logs and isn't read back. Redacted, masked and packed
+//! fields are written the same under every profile.
+//!
+//! The profile is scoped to the current thread and lasts for the duration of the
+//! closure, so it covers a whole synchronous `serde_json::to_string` call but not an
+//! `.await` inside the closure.
+//!
+//! # Example
+//! ```
+//! use serde::Serialize;
+//! use serde_hash::hashids::SerdeHashOptions;
+//! use serde_hash::profile::{Profiled, SerializationProfile};
+//! use serde_hash::serde_hash;
+//!
+//! #[serde_hash]
+//! #[derive(Serialize)]
+//! pub struct User {
+//!     #[serde(hash)]
+//!     pub id: u64,
+//! }
+//!
+//! SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+//!
+//! let user = User { id: 42 };
+//! let internal = serde_json::to_string(&Profiled::new(SerializationProfile::Internal, &user));
+//! assert_eq!(internal.unwrap(), r#"{"id":42}"#);
+//! ```
+
+use serde::{Serialize, Serializer};
+use std::cell::Cell;
+
+/// How hash fields are written, see the [module documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SerializationProfile {
+    /// Hash fields are written as hashes.
+    #[default]
+    Public,
+    /// Hash fields are written, and read, as the raw numbers.
+    Internal,
+    /// Hash fields are written as the number followed by its hash in parentheses.
+    Debug,
+}
+
+thread_local! {
+    static PROFILE: Cell<SerializationProfile> = const { Cell::new(SerializationProfile::Public) };
+}
+
+/// Restores the previous profile when dropped, including on panic.
+struct Restore(SerializationProfile);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        PROFILE.with(|current| current.set(self.0));
+    }
+}
+
+/// Runs `f` with `profile` applied to every hash field serialized or deserialized on this
+/// thread. Profiles nest; the innermost one applies.
+pub fn with_profile<R>(profile: SerializationProfile, f: impl FnOnce() -> R) -> R {
+    let _restore = Restore(PROFILE.with(|current| current.replace(profile)));
+    f()
+}
+
+/// Returns the profile in effect on this thread, [`SerializationProfile::Public`] unless
+/// called inside [`with_profile`].
+pub fn current_profile() -> SerializationProfile {
+    PROFILE.with(Cell::get)
+}
+
+/// Serializes the borrowed value under a profile, e.g.
+/// `serde_json::to_string(&Profiled::new(SerializationProfile::Debug, &user))`.
+#[derive(Debug, Clone, Copy)]
+pub struct Profiled<'a, T: ?Sized> {
+    profile: SerializationProfile,
+    value: &'a T,
+}
+
+impl<'a, T: ?Sized> Profiled<'a, T> {
+    /// Wraps `value` so it serializes under `profile`.
+    pub fn new(profile: SerializationProfile, value: &'a T) -> Self {
+        Self { profile, value }
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for Profiled<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        with_profile(self.profile, || self.value.serialize(serializer))
+    }
+}
@@ -0,0 +1,147 @@
+//! Mapping protobuf messages, e.g. generated by prost, to types with numeric ids.
+//!
+//! Services that expose both gRPC and JSON need the same public ids in both. Protobuf
+//! messages carry them as `string` fields, so converting between a message and the
+//! internal type means hashing and decoding those fields with the configured encoder.
+//! [`ProtoId`] does this for one field: numeric ids become `String`s, `Vec`s become
+//! `repeated string`s and `Option`s become `optional string`s. [`proto_bridge!`] writes
+//! the conversions for a whole message:
+//!
+//! * `From<&Domain> for Message`, hashing the id fields and cloning the others. Fields of
+//!   the message that aren't listed keep their defaults.
+//! * `TryFrom<Message> for Domain`, decoding the id fields and moving the others. The
+//!   error names the message field that held an invalid id.
+//!
+//! # Example
+//! ```
+//! use serde_hash::hashids::SerdeHashOptions;
+//!
+//! // As generated by prost for
+//! // `message User { string public_id = 1; string name = 2; repeated string team_ids = 3; }`
+//! mod pb {
+//!     #[derive(Clone, PartialEq, Default, Debug)]
+//!     pub struct User {
+//!         pub public_id: String,
+//!         pub name: String,
+//!         pub team_ids: Vec<String>,
+//!     }
+//! }
+//!
+//! #[derive(Debug, PartialEq)]
+//! pub struct User {
+//!     pub id: u64,
+//!     pub name: String,
+//!     pub team_ids: Vec<u32>,
+//! }
+//!
+//! serde_hash::proto_bridge! {
+//!     User => pb::User {
+//!         ids { id => public_id, team_ids => team_ids }
+//!         fields { name => name }
+//!     }
+//! }
+//!
+//! SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+//!
+//! let user = User { id: 42, name: "Alice".to_string(), team_ids: vec![1, 2] };
+//! let message = pb::User::from(&user);
+//! assert_eq!(message.public_id, serde_hash::hashids::encode_single(42));
+//! assert_eq!(User::try_from(message).unwrap(), user);
+//! ```
+
+use crate::error::{Result, SerdeHashError};
+use crate::hashids::encode;
+use crate::serde_impl::{HashNumeric, decode_field_values, encode_numeric, numeric_from_values};
+
+/// An id field of a domain type, and how it's represented in a protobuf message.
+pub trait ProtoId: Sized {
+    /// The message field's type: `String`, or a `Vec` or `Option` of it.
+    type Proto;
+
+    /// Hashes the value with the active options.
+    fn to_proto(&self) -> Self::Proto;
+
+    /// Decodes the message field, naming `field` in errors.
+    fn from_proto(proto: Self::Proto, field: &str) -> Result<Self>;
+}
+
+impl<T: HashNumeric> ProtoId for T {
+    type Proto = String;
+
+    fn to_proto(&self) -> String {
+        encode_numeric(*self, encode)
+    }
+
+    fn from_proto(proto: String, field: &str) -> Result<Self> {
+        let values = decode_field_values(&proto)
+            .map_err(|e| e.context(format!("invalid id in field '{}'", field)))?;
+        numeric_from_values::<T, serde::de::value::Error>(&values, Some(field))
+            .map_err(|e| SerdeHashError::InvalidHash(e.to_string()))
+    }
+}
+
+impl<T: ProtoId> ProtoId for Vec<T> {
+    type Proto = Vec<T::Proto>;
+
+    fn to_proto(&self) -> Self::Proto {
+        self.iter().map(ProtoId::to_proto).collect()
+    }
+
+    fn from_proto(proto: Self::Proto, field: &str) -> Result<Self> {
+        proto.into_iter().map(|value| T::from_proto(value, field)).collect()
+    }
+}
+
+impl<T: ProtoId> ProtoId for Option<T> {
+    type Proto = Option<T::Proto>;
+
+    fn to_proto(&self) -> Self::Proto {
+        self.as_ref().map(ProtoId::to_proto)
+    }
+
+    fn from_proto(proto: Self::Proto, field: &str) -> Result<Self> {
+        proto.map(|value| T::from_proto(value, field)).transpose()
+    }
+}
+
+/// Writes the conversions between a domain type and a protobuf message, see the
+/// [module documentation](crate::proto).
+///
+/// `ids` maps the domain type's id fields to the message fields holding their hashes, and
+/// `fields` maps the fields that are copied as they are. Either list may be empty. Every
+/// field of the domain type has to be listed, while the message may have more, as long
+/// as it implements `Default`.
+#[macro_export]
+macro_rules! proto_bridge {
+    (
+        $domain:path => $proto:path {
+            ids { $($id:ident => $proto_id:ident),* $(,)? }
+            fields { $($field:ident => $proto_field:ident),* $(,)? }
+        }
+    ) => {
+        impl ::std::convert::From<&$domain> for $proto {
+            #[allow(clippy::needless_update)]
+            fn from(value: &$domain) -> Self {
+                Self {
+                    $($proto_id: $crate::proto::ProtoId::to_proto(&value.$id),)*
+                    $($proto_field: ::std::clone::Clone::clone(&value.$field),)*
+                    ..::std::default::Default::default()
+                }
+            }
+        }
+
+        impl ::std::convert::TryFrom<$proto> for $domain {
+            type Error = $crate::SerdeHashError;
+
+            fn try_from(message: $proto) -> ::std::result::Result<Self, Self::Error> {
+                Ok(Self {
+                    $($id: $crate::proto::ProtoId::from_proto(
+                        message.$proto_id,
+                        stringify!($proto_id),
+                    )?,)*
+                    $($field: message.$proto_field,)*
+                })
+            }
+        }
+    };
+}
@@ -0,0 +1,103 @@
+//! Supplying the options from somewhere other than the global `OnceLock`.
+//!
+//! Hashing looks up its configuration in this order: the task's context (see
+//! `with_context` with the `tokio` feature), then the process-wide [`OptionsProvider`].
+//! The default provider is [`GlobalOptions`], which serves the options installed with
+//! [`SerdeHashOptions::build`](crate::hashids::SerdeHashOptions::build). Applications that
+//! keep their configuration in a dependency-injection container, or pick it from
+//! per-request state of their own, install a provider with [`set_options_provider`]
+//! instead.
+//!
+//! # Example
+//! ```
+//! use serde_hash::hashids::{SerdeHashOptions, encode_single};
+//! use serde_hash::provider::set_options_provider;
+//!
+//! let options = SerdeHashOptions::new().with_salt("replace-with-a-random-salt");
+//! let context = options.into_context().unwrap();
+//! set_options_provider(context.clone()).unwrap();
+//!
+//! assert_eq!(encode_single(42), context.encode_single(42));
+//! ```
+
+use crate::context::{HashContext, current_context};
+use crate::error::SerdeHashError;
+use crate::hashids::{configured_options, global_context};
+use std::sync::OnceLock;
+
+/// Supplies the configuration hashing uses outside of a task context.
+///
+/// [`OptionsProvider::context`] is called for every encode and decode, so it should be
+/// cheap, e.g. clone a context built at startup. Contexts share their encoders, so
+/// cloning one doesn't rebuild anything.
+///
+/// Besides [`GlobalOptions`], this is implemented for [`HashContext`], which provides
+/// itself, and for closures returning a context.
+pub trait OptionsProvider: Send + Sync {
+    /// Returns the configuration to hash with.
+    fn context(&self) -> HashContext;
+}
+
+/// The options installed with [`SerdeHashOptions::build`], or the defaults if none were.
+///
+/// This is the provider used until [`set_options_provider`] installs another one.
+///
+/// [`SerdeHashOptions::build`]: crate::hashids::SerdeHashOptions::build
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GlobalOptions;
+
+impl OptionsProvider for GlobalOptions {
+    fn context(&self) -> HashContext {
+        global_context().clone()
+    }
+}
+
+impl OptionsProvider for HashContext {
+    fn context(&self) -> HashContext {
+        self.clone()
+    }
+}
+
+impl<F: Fn() -> HashContext + Send + Sync> OptionsProvider for F {
+    fn context(&self) -> HashContext {
+        self()
+    }
+}
+
+static PROVIDER: OnceLock<Box<dyn OptionsProvider>> = OnceLock::new();
+
+/// Installs the provider hashing uses in place of the global options.
+///
+/// Like the global options, the provider is set once for the lifetime of the process.
+///
+/// # Arguments
+///
+/// * `provider` - Supplies the configuration for every encode and decode outside of a
+///   task context.
+///
+/// # Returns
+///
+/// * `Ok(())` once the provider is installed.
+/// * [`SerdeHashError::AlreadyConfigured`] if a provider was already installed, or the
+///   global options were already set or used.
+pub fn set_options_provider(
+    provider: impl OptionsProvider + 'static,
+) -> Result<(), SerdeHashError> {
+    if configured_options().is_some() {
+        return Err(SerdeHashError::AlreadyConfigured);
+    }
+    PROVIDER
+        .set(Box::new(provider))
+        .map_err(|_| SerdeHashError::AlreadyConfigured)
+}
+
+/// Returns the provider installed with [`set_options_provider`], if any.
+pub(crate) fn installed_provider() -> Option<&'static dyn OptionsProvider> {
+    PROVIDER.get().map(Box::as_ref)
+}
+
+/// Returns the task's context, or else the installed provider's. `None` means the global
+/// options apply.
+pub(crate) fn active_context() -> Option<HashContext> {
+    current_context().or_else(|| installed_provider().map(OptionsProvider::context))
+}
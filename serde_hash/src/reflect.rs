@@ -0,0 +1,40 @@
+//! Which fields of a type are hashed, for code that inspects types at runtime.
+//!
+//! Middleware, schema generators and audit tooling often need to know which keys of a
+//! payload hold hashed ids without serializing a value first. `#[derive(HashIds)]` and
+//! `#[serde_hash]` implement [`HashedFields`] for every struct they expand.
+//!
+//! # Example
+//! ```
+//! use serde_hash::{HashIds, HashedFields};
+//!
+//! #[derive(HashIds)]
+//! #[hash(rename_all = "camelCase")]
+//! pub struct Order {
+//!     #[hash]
+//!     pub order_id: u64,
+//!     #[hash]
+//!     pub customer_id: Option<u64>,
+//!     pub total: u32,
+//! }
+//!
+//! assert_eq!(Order::HASHED_FIELDS, ["orderId", "customerId"]);
+//! assert!(Order::is_hashed("orderId"));
+//! assert!(!Order::is_hashed("total"));
+//! ```
+
+/// Types that know which of their serialized keys hold hashed values.
+pub trait HashedFields {
+    /// The serialized keys of the hashed fields, in declaration order.
+    ///
+    /// Keys are listed after renaming, so they match the serialized output. A field
+    /// written under a suffixed key next to its raw value lists only the suffixed key, and
+    /// two fields packed into one hash list the key of that hash. Fields written as plain
+    /// digits by `#[hash(stringify_only)]` hold no hash and aren't listed.
+    const HASHED_FIELDS: &'static [&'static str];
+
+    /// Returns `true` if `key` is one of [`HashedFields::HASHED_FIELDS`].
+    fn is_hashed(key: &str) -> bool {
+        Self::HASHED_FIELDS.contains(&key)
+    }
+}
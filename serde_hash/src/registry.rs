@@ -0,0 +1,151 @@
+//! Per-tenant salts registered at runtime.
+//!
+//! A [`SaltRegistry`] holds one [`HashContext`] per tenant, all sharing the same base
+//! options and differing only in the salt. Tenants can be added, re-salted and evicted
+//! while the registry is in use from several threads, so a SaaS platform serves any
+//! number of tenants from one process.
+//!
+//! # Example
+//! ```
+//! use serde_hash::hashids::SerdeHashOptions;
+//! use serde_hash::registry::SaltRegistry;
+//!
+//! let registry = SaltRegistry::new(SerdeHashOptions::new().with_min_length(10)).unwrap();
+//! registry.register("acme", "acme-salt-from-the-vault").unwrap();
+//! registry.register("globex", "globex-salt-from-the-vault").unwrap();
+//!
+//! let hash = registry.encode("acme", 42).unwrap();
+//! assert_eq!(registry.decode("acme", &hash).unwrap(), 42);
+//! assert_ne!(registry.encode("globex", 42).unwrap(), hash);
+//!
+//! registry.evict("globex");
+//! assert!(registry.encode("globex", 42).is_err());
+//! ```
+//!
+//! With the `tokio` feature, pass a tenant's [`SaltRegistry::context`] to `with_context`
+//! so serde's `hash` fields use the tenant's salt, too.
+
+use crate::context::HashContext;
+use crate::error::{Result, SerdeHashError};
+use crate::hashids::SerdeHashOptions;
+use crate::salt::validate_salt;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Tenant salts keyed by `K`, e.g. a tenant id or name.
+pub struct SaltRegistry<K> {
+    base: SerdeHashOptions,
+    tenants: RwLock<HashMap<K, HashContext>>,
+}
+
+impl<K: Eq + Hash> SaltRegistry<K> {
+    /// Creates an empty registry whose tenants use `base` with their own salt.
+    ///
+    /// # Returns
+    ///
+    /// An error if `base` is invalid, see [`SerdeHashOptions::validate`].
+    pub fn new(base: SerdeHashOptions) -> Result<Self, SerdeHashError> {
+        base.validate()?;
+        Ok(Self {
+            base,
+            tenants: RwLock::default(),
+        })
+    }
+
+    /// Registers `tenant` with `salt`, replacing its previous salt if it had one.
+    ///
+    /// The salt is checked with [`validate_salt`] unless the base options allow weak
+    /// salts.
+    ///
+    /// # Returns
+    ///
+    /// An error if the salt is weak; the tenant's previous salt then stays in place.
+    pub fn register(&self, tenant: K, salt: impl AsRef<str>) -> Result<(), SerdeHashError> {
+        if !self.base.allow_weak_salt {
+            validate_salt(salt.as_ref())?;
+        }
+        let context = self.base.clone().with_salt(salt).into_context()?;
+        self.write().insert(tenant, context);
+        Ok(())
+    }
+
+    /// Removes `tenant`, returning whether it was registered.
+    pub fn evict<Q>(&self, tenant: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.write().remove(tenant).is_some()
+    }
+
+    /// Keeps only the tenants for which `keep` returns true, e.g. to evict every tenant
+    /// that closed their account.
+    pub fn retain(&self, mut keep: impl FnMut(&K) -> bool) {
+        self.write().retain(|tenant, _| keep(tenant));
+    }
+
+    /// Returns the context `tenant` hashes with, if it is registered.
+    pub fn context<Q>(&self, tenant: &Q) -> Option<HashContext>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.read().get(tenant).cloned()
+    }
+
+    /// Encodes `value` with the salt of `tenant`.
+    ///
+    /// # Returns
+    ///
+    /// An error if `tenant` isn't registered.
+    pub fn encode<Q>(&self, tenant: &Q, value: u64) -> Result<String>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        Ok(self.tenant(tenant)?.encode_single(value))
+    }
+
+    /// Decodes a hash made with the salt of `tenant`.
+    ///
+    /// # Returns
+    ///
+    /// An error if `tenant` isn't registered or the hash is invalid.
+    pub fn decode<Q>(&self, tenant: &Q, hash: impl AsRef<str>) -> Result<u64>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.tenant(tenant)?.decode_single(hash)
+    }
+
+    /// Returns the number of registered tenants.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Returns whether no tenants are registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // A panic in `K`'s `Hash` or `Eq` impl poisons the lock, but leaves the map intact, so
+    // the registry keeps working instead of panicking on every later call.
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<K, HashContext>> {
+        self.tenants.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<K, HashContext>> {
+        self.tenants.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn tenant<Q>(&self, tenant: &Q) -> Result<HashContext>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.context(tenant).ok_or(SerdeHashError::UnknownTenant)
+    }
+}
@@ -1,6 +1,105 @@
+use crate::error::WeakSaltError;
+#[cfg(feature = "hkdf")]
+use crate::secret::wipe_bytes;
+#[cfg(feature = "hkdf")]
+use hkdf::Hkdf;
+#[cfg(feature = "log")]
 use log::debug;
+#[cfg(feature = "rand")]
 use rand::distr::Alphanumeric;
+#[cfg(feature = "rand")]
 use rand::Rng;
+#[cfg(feature = "hkdf")]
+use sha2::Sha256;
+
+/// The length of the salts made by [`generate_salt`] and [`generate_salt_with`].
+pub const SALT_LENGTH: usize = 32;
+
+/// The shortest salt [`validate_salt`] accepts.
+pub const MIN_SALT_LENGTH: usize = 16;
+
+/// The fewest distinct characters [`validate_salt`] accepts in a salt.
+pub const MIN_SALT_DISTINCT: usize = 8;
+
+/// The salt of [`SerdeHashOptions::for_tests`](crate::hashids::SerdeHashOptions::for_tests),
+/// so hashes in snapshot tests are the same in every environment.
+///
+/// The salt is public, so [`validate_salt`] rejects it and building options with it fails
+/// unless weak salts are allowed.
+pub const TESTING_SALT: &str = "serde_hash testing salt";
+
+/// Checks that `salt` is long and varied enough to be hard to guess.
+///
+/// The global options run this check when they are built, so a salt copied from an
+/// example (like `"hello world"`) doesn't end up in production. Generated and derived
+/// salts always pass.
+///
+/// # Returns
+///
+/// * `Ok(())` if the salt has at least [`MIN_SALT_LENGTH`] characters, of which at least
+///   [`MIN_SALT_DISTINCT`] are distinct, and isn't [`TESTING_SALT`].
+/// * The first problem found otherwise.
+///
+/// # Example
+///
+/// ```
+/// use serde_hash::salt::{generate_salt, validate_salt};
+///
+/// assert!(validate_salt("hello world").is_err());
+/// assert!(validate_salt(&generate_salt()).is_ok());
+/// ```
+pub fn validate_salt(salt: &str) -> Result<(), WeakSaltError> {
+    if salt == TESTING_SALT {
+        return Err(WeakSaltError::TestingSalt);
+    }
+    let length = salt.chars().count();
+    if length < MIN_SALT_LENGTH {
+        return Err(WeakSaltError::TooShort {
+            length,
+            min: MIN_SALT_LENGTH,
+        });
+    }
+    let mut chars: Vec<char> = salt.chars().collect();
+    chars.sort_unstable();
+    chars.dedup();
+    if chars.len() < MIN_SALT_DISTINCT {
+        return Err(WeakSaltError::TooFewDistinct {
+            distinct: chars.len(),
+            min: MIN_SALT_DISTINCT,
+        });
+    }
+    Ok(())
+}
+
+/// Estimates the entropy of `salt` in bits, from how often each character appears.
+///
+/// This is the Shannon entropy of the salt's characters times its length, an upper bound
+/// on how hard the salt is to guess: a generated salt scores well over 100 bits, while
+/// words and phrases score higher than they deserve.
+///
+/// # Example
+///
+/// ```
+/// use serde_hash::salt::estimate_entropy;
+///
+/// assert_eq!(estimate_entropy("aaaa"), 0.0);
+/// assert_eq!(estimate_entropy("abcd"), 8.0);
+/// ```
+pub fn estimate_entropy(salt: &str) -> f64 {
+    let mut counts = std::collections::HashMap::new();
+    for c in salt.chars() {
+        *counts.entry(c).or_insert(0usize) += 1;
+    }
+    let length = salt.chars().count() as f64;
+    let per_char: f64 = counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / length;
+            -p * p.log2()
+        })
+        .sum();
+    per_char * length
+}
 
 /// Generates a cryptographically secure random salt string of 32 characters.
 ///
@@ -12,17 +111,109 @@ use rand::Rng;
 ///
 /// A randomly generated String of 32 alphanumeric characters.
 ///
+/// Only available with the `rand` feature, which is enabled by default.
+///
 /// # Example
 ///
 /// ```
 /// let salt = serde_hash::salt::generate_salt();
 /// // Returns a random string like "a1B2c3D4e5F6g7H8i9J0k1L2m3N4o5P6"
 /// ```
+#[cfg(feature = "rand")]
 pub fn generate_salt() -> String {
+    generate_salt_len(SALT_LENGTH)
+}
+
+/// Generates a cryptographically secure random salt string of `length` characters.
+///
+/// # Arguments
+///
+/// * `length` - The number of alphanumeric characters in the salt.
+///
+/// # Example
+///
+/// ```
+/// let salt = serde_hash::salt::generate_salt_len(64);
+/// assert_eq!(salt.len(), 64);
+/// ```
+#[cfg(feature = "rand")]
+pub fn generate_salt_len(length: usize) -> String {
+    sample_salt(&mut rand::rng(), length)
+}
+
+/// Generates a 32-character salt string from `rng` instead of the thread-local
+/// generator.
+///
+/// A seeded generator produces the same salt every time, which is useful in tests and
+/// reproducible builds. Use a cryptographically secure generator for production salts.
+///
+/// # Arguments
+///
+/// * `rng` - The random source to draw characters from.
+///
+/// # Example
+///
+/// ```
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+///
+/// let salt = serde_hash::salt::generate_salt_with(&mut StdRng::seed_from_u64(7));
+/// assert_eq!(salt, serde_hash::salt::generate_salt_with(&mut StdRng::seed_from_u64(7)));
+/// ```
+#[cfg(feature = "rand")]
+pub fn generate_salt_with<R: Rng + ?Sized>(rng: &mut R) -> String {
+    sample_salt(rng, SALT_LENGTH)
+}
+
+#[cfg(feature = "rand")]
+fn sample_salt<R: Rng + ?Sized>(rng: &mut R, length: usize) -> String {
+    #[cfg(feature = "log")]
     debug!("Generating salt"); // Log when salt generation begins
-    rand::rng()
-        .sample_iter(&Alphanumeric) // Generate a stream of random alphanumeric chars
-        .take(32)                   // Limit to 32 characters
+    rng.sample_iter(&Alphanumeric) // Generate a stream of random alphanumeric chars
+        .take(length)               // Limit to the requested length
         .map(char::from)            // Convert raw values to characters
         .collect()                  // Collect characters into a String
-}
\ No newline at end of file
+}
+
+/// Derives a salt from a master secret and a context label using HKDF-SHA256.
+///
+/// The same key and context always produce the same salt, so each environment or
+/// service can get its own salt (e.g. context `"orders/production"`) while only the
+/// master key needs to be stored as a secret. Changing the context rotates the salt.
+///
+/// Only available with the `hkdf` feature.
+///
+/// # Arguments
+///
+/// * `master_key` - The secret input keying material.
+/// * `context` - A label distinguishing this salt from others derived from the same key.
+///
+/// # Returns
+///
+/// A 64-character lowercase hex string.
+///
+/// # Example
+///
+/// ```
+/// let salt = serde_hash::salt::derive_salt(b"master secret", "orders/production");
+/// assert_eq!(salt, serde_hash::salt::derive_salt(b"master secret", "orders/production"));
+/// assert_ne!(salt, serde_hash::salt::derive_salt(b"master secret", "orders/staging"));
+/// ```
+#[cfg(feature = "hkdf")]
+pub fn derive_salt(master_key: impl AsRef<[u8]>, context: impl AsRef<str>) -> String {
+    #[cfg(feature = "log")]
+    debug!("Deriving salt for context {:?}", context.as_ref());
+    let mut salt = [0u8; 32];
+    Hkdf::<Sha256>::new(None, master_key.as_ref())
+        .expand(context.as_ref().as_bytes(), &mut salt)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    // Written into one preallocated string so no partial copies of the salt are left behind.
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let mut hex = String::with_capacity(salt.len() * 2);
+    for b in salt {
+        hex.push(char::from(HEX[usize::from(b >> 4)]));
+        hex.push(char::from(HEX[usize::from(b & 0xf)]));
+    }
+    wipe_bytes(&mut salt);
+    hex
+}
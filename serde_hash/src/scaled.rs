@@ -0,0 +1,156 @@
+//! Hashing `f32` and `f64` business values as fixed-point numbers.
+//!
+//! Hashids only encode integers, so amounts of money, coordinates and other fractional
+//! values are hashed at a fixed scale: the value is multiplied by the scale and rounded,
+//! e.g. `12.34` at a scale of `100` becomes `1234`. Decoding divides by the scale again,
+//! so every value round-trips exactly to the precision the scale keeps. Negative values
+//! are supported.
+//!
+//! The macros use this for `#[hash(scale = N)]` fields of type `f32` or `f64`, or any
+//! nesting of `Vec` and `Option` around one. Serializing fails for `NaN`, infinities and
+//! values too large to scale into an `i64`.
+//!
+//! # Example
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use serde_hash::hashids::SerdeHashOptions;
+//! use serde_hash::serde_hash;
+//!
+//! #[serde_hash]
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! pub struct Quote {
+//!     #[hash(scale = 100)]
+//!     pub price: f64,
+//!     #[hash(scale = 1_000_000)]
+//!     pub location: Option<Vec<f64>>,
+//! }
+//!
+//! SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+//!
+//! let quote = Quote { price: 12.34, location: Some(vec![48.858844, -2.294351]) };
+//! let json = serde_json::to_string(&quote).unwrap();
+//! assert_eq!(serde_json::from_str::<Quote>(&json).unwrap(), quote);
+//! ```
+
+use crate::error::{Result, SerdeHashError};
+use crate::hashids;
+use crate::serde_impl::{FieldWith, HashShape, decode_field_values};
+use serde::{Deserializer, Serializer};
+
+/// Scales `value` into the fixed-point integer stored in the hash.
+///
+/// The integer is zigzag-encoded, so small negative values stay small: `0, -1, 1, -2`
+/// become `0, 1, 2, 3`.
+fn to_fixed(value: f64, scale: u64) -> Result<u64> {
+    let scaled = (value * scale as f64).round();
+    // `i64::MAX as f64` rounds up to 2^63, which is already out of range.
+    if !scaled.is_finite() || scaled < i64::MIN as f64 || scaled >= i64::MAX as f64 {
+        return Err(SerdeHashError::InvalidValue(format!(
+            "{} can't be hashed at a scale of {}",
+            value, scale
+        )));
+    }
+    let fixed = scaled as i64;
+    Ok(((fixed << 1) ^ (fixed >> 63)) as u64)
+}
+
+/// Turns a fixed-point integer written by [`to_fixed`] back into the value.
+fn from_fixed(fixed: u64, scale: u64) -> f64 {
+    let fixed = ((fixed >> 1) as i64) ^ -((fixed & 1) as i64);
+    fixed as f64 / scale as f64
+}
+
+/// Encodes `value` at `scale` into a hash string.
+///
+/// # Arguments
+///
+/// * `value` - The value to be encoded, e.g. an amount of money.
+/// * `scale` - What the value is multiplied by before rounding, e.g. `100` for cents.
+///
+/// # Returns
+///
+/// The hash, or an error if `value` isn't finite or doesn't fit an `i64` once scaled.
+pub fn encode_scaled(value: f64, scale: u64) -> Result<String> {
+    Ok(hashids::encode_single(to_fixed(value, scale)?))
+}
+
+/// Decodes a hash produced by [`encode_scaled`] with the same `scale`.
+pub fn decode_scaled(hash: impl AsRef<str>, scale: u64) -> Result<f64> {
+    Ok(from_fixed(hashids::decode_single(hash)?, scale))
+}
+
+/// Field shapes accepted by `#[hash(scale = N)]`: `f32` or `f64`, or any nesting of `Vec`
+/// and `Option` around one.
+///
+/// Each shape maps to the [`HashShape`] of its fixed-point integers, which does the
+/// actual hashing.
+pub trait ScaledShape: Sized {
+    /// The same shape holding the fixed-point integers.
+    type Fixed: HashShape;
+
+    fn to_fixed(&self, scale: u64) -> Result<Self::Fixed>;
+    fn from_fixed(fixed: Self::Fixed, scale: u64) -> Self;
+}
+
+impl ScaledShape for f64 {
+    type Fixed = u64;
+
+    fn to_fixed(&self, scale: u64) -> Result<u64> {
+        to_fixed(*self, scale)
+    }
+
+    fn from_fixed(fixed: u64, scale: u64) -> Self {
+        from_fixed(fixed, scale)
+    }
+}
+
+impl ScaledShape for f32 {
+    type Fixed = u64;
+
+    fn to_fixed(&self, scale: u64) -> Result<u64> {
+        to_fixed(f64::from(*self), scale)
+    }
+
+    fn from_fixed(fixed: u64, scale: u64) -> Self {
+        from_fixed(fixed, scale) as f32
+    }
+}
+
+impl<T: ScaledShape> ScaledShape for Vec<T> {
+    type Fixed = Vec<T::Fixed>;
+
+    fn to_fixed(&self, scale: u64) -> Result<Self::Fixed> {
+        self.iter().map(|value| value.to_fixed(scale)).collect()
+    }
+
+    fn from_fixed(fixed: Self::Fixed, scale: u64) -> Self {
+        fixed.into_iter().map(|value| T::from_fixed(value, scale)).collect()
+    }
+}
+
+impl<T: ScaledShape> ScaledShape for Option<T> {
+    type Fixed = Option<T::Fixed>;
+
+    fn to_fixed(&self, scale: u64) -> Result<Self::Fixed> {
+        self.as_ref().map(|value| value.to_fixed(scale)).transpose()
+    }
+
+    fn from_fixed(fixed: Self::Fixed, scale: u64) -> Self {
+        fixed.map(|value| T::from_fixed(value, scale))
+    }
+}
+
+/// The [`FieldWith`] implementation the macros use for `#[hash(scale = SCALE)]` fields.
+pub struct Scaled<const SCALE: u64>;
+
+impl<T: ScaledShape, const SCALE: u64> FieldWith<T> for Scaled<SCALE> {
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        let fixed = value.to_fixed(SCALE).map_err(serde::ser::Error::custom)?;
+        fixed.serialize_with(hashids::encode, serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        let fixed = T::Fixed::deserialize_with(deserializer, decode_field_values)?;
+        Ok(T::from_fixed(fixed, SCALE))
+    }
+}
@@ -0,0 +1,73 @@
+//! Wiping secrets from memory.
+//!
+//! With the `zeroize` feature enabled, the salt (and the alphabet derived from it) is
+//! overwritten with zeros before its memory is released, so it doesn't linger in freed
+//! allocations or core dumps. Without the feature these functions do nothing. The types
+//! holding secrets implement `Drop` either way, so enabling the feature anywhere in a
+//! dependency graph doesn't change what compiles.
+
+use std::ops::Deref;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// A string that is wiped when dropped or replaced.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub(crate) struct SecretString(String);
+
+impl SecretString {
+    /// Replaces the contents, wiping the previous ones first.
+    pub(crate) fn replace(&mut self, value: String) {
+        replace_string(&mut self.0, value);
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl Deref for SecretString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        wipe_string(&mut self.0);
+    }
+}
+
+/// Overwrites the contents of `value` and clears it.
+pub(crate) fn wipe_string(value: &mut String) {
+    #[cfg(feature = "zeroize")]
+    value.zeroize();
+    #[cfg(not(feature = "zeroize"))]
+    let _ = value;
+}
+
+/// Overwrites the contents of `value` and clears it.
+pub(crate) fn wipe_chars(value: &mut Vec<char>) {
+    #[cfg(feature = "zeroize")]
+    value.zeroize();
+    #[cfg(not(feature = "zeroize"))]
+    let _ = value;
+}
+
+/// Overwrites `value` with zeros.
+#[cfg(feature = "hkdf")]
+pub(crate) fn wipe_bytes(value: &mut [u8]) {
+    #[cfg(feature = "zeroize")]
+    value.zeroize();
+    #[cfg(not(feature = "zeroize"))]
+    let _ = value;
+}
+
+/// Replaces `slot` with `value`, wiping the previous contents first.
+pub(crate) fn replace_string(slot: &mut String, value: String) {
+    wipe_string(slot);
+    *slot = value;
+}
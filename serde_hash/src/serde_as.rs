@@ -0,0 +1,61 @@
+//! Adapters for [`serde_with`](https://docs.rs/serde_with)'s `#[serde_as]`.
+//!
+//! The `with` modules in [`crate::serde_impl`] only cover the container shapes they were
+//! written for, such as `Vec<T>` or `Option<Vec<T>>`. [`HashId`] instead hashes a single
+//! numeric value and leaves the container to `serde_with`, so it nests inside any of its
+//! combinators: `Vec<HashId>`, `Option<HashId>`, `HashMap<HashId, _>`, `[HashId; N]` and
+//! so on.
+//!
+//! # Example
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use serde_hash::hashids::SerdeHashOptions;
+//! use serde_hash::serde_as::HashId;
+//! use serde_with::serde_as;
+//! use std::collections::HashMap;
+//!
+//! #[serde_as]
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! pub struct Team {
+//!     #[serde_as(as = "HashId")]
+//!     pub id: u64,
+//!     #[serde_as(as = "Option<Vec<HashId>>")]
+//!     pub members: Option<Vec<u32>>,
+//!     #[serde_as(as = "HashMap<HashId, _>")]
+//!     pub roles: HashMap<u64, String>,
+//! }
+//!
+//! SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+//!
+//! let team = Team {
+//!     id: 1,
+//!     members: Some(vec![2, 3]),
+//!     roles: HashMap::from([(2, "owner".to_string())]),
+//! };
+//! let json = serde_json::to_string(&team).unwrap();
+//! assert_eq!(serde_json::from_str::<Team>(&json).unwrap(), team);
+//! ```
+
+use crate::hashids::encode;
+use crate::serde_impl::{HashNumeric, HashShape, decode_field_values};
+use serde::{Deserializer, Serializer};
+use serde_with::{DeserializeAs, SerializeAs};
+
+/// Hashes a numeric value (`u8`, `u16`, `u32`, `u64`, `u128`, `usize`) with the active
+/// options when used in `#[serde_as(as = "...")]`.
+///
+/// Serializes exactly like `#[serde(hash)]` on a plain numeric field, so the two can be
+/// mixed freely.
+pub struct HashId;
+
+impl<T: HashNumeric> SerializeAs<T> for HashId {
+    fn serialize_as<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        value.serialize_with(encode, serializer)
+    }
+}
+
+impl<'de, T: HashNumeric> DeserializeAs<'de, T> for HashId {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        T::deserialize_with(deserializer, decode_field_values)
+    }
+}
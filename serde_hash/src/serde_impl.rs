@@ -1,10 +1,64 @@
-use crate::hashids::{decode_single, encode_single};
+use crate::encoder::HashIdsEncoder;
+use crate::error::SerdeHashError;
+#[cfg(feature = "hmac")]
+use crate::hashids::redact;
+use crate::hashids::{
+    active_setting, current_encoder, decode, decode_numeric_with_min_length, encode,
+    encode_with_min_length,
+};
+use crate::hook::report_decode_error;
+use crate::msgpack::{deserialize_ext, serialize_ext};
+use crate::profile::{SerializationProfile, current_profile, with_profile};
+use serde::de::{DeserializeOwned, DeserializeSeed, SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+use std::ops::RangeBounds;
+
+/// Decodes a single hash read during deserialization, reporting failures to the
+/// decode error hook.
+#[cfg(feature = "json")]
+pub(crate) fn decode_field(hash: &str) -> crate::Result<u64> {
+    report_decode_error(hash, crate::hashids::decode_single(hash))
+}
+
+/// Decodes a hash holding one numeric value read during deserialization, reporting
+/// failures to the decode error hook. See [`numeric_from_values`].
+pub(crate) fn decode_field_values(hash: &str) -> crate::Result<Vec<u64>> {
+    report_decode_error(hash, current_encoder().decode_numeric(hash))
+}
 
 /// Trait for numeric types that can be hash-encoded as u64.
 pub trait HashNumeric: Copy {
+    /// Converts the value to a `u64`, keeping only the low limb of a `u128`. Hashing goes
+    /// through [`HashNumeric::to_limbs`] instead, so wide values aren't truncated.
     fn to_u64(self) -> u64;
+    /// Converts a `u64` to the type, truncating values that don't fit. Decoding goes
+    /// through [`HashNumeric::try_from_u64`] instead.
     fn from_u64(v: u64) -> Self;
+
+    /// Converts a decoded value, or returns `None` if it doesn't fit in `Self`.
+    ///
+    /// Deserialization uses this so out-of-range hashes are rejected rather than
+    /// truncated. The default accepts every value through [`HashNumeric::from_u64`].
+    fn try_from_u64(v: u64) -> Option<Self> {
+        Some(Self::from_u64(v))
+    }
+
+    /// Splits the value into two `u64` limbs, most significant first.
+    ///
+    /// Only `u128` values above `u64::MAX` have a nonzero high limb.
+    fn to_limbs(self) -> [u64; 2] {
+        [0, self.to_u64()]
+    }
+
+    /// Rebuilds a value from its limbs, or returns `None` if it doesn't fit in `Self`.
+    fn try_from_limbs(limbs: [u64; 2]) -> Option<Self> {
+        match limbs {
+            [0, low] => Self::try_from_u64(low),
+            _ => None,
+        }
+    }
 }
 
 macro_rules! impl_hash_numeric {
@@ -13,12 +67,805 @@ macro_rules! impl_hash_numeric {
             impl HashNumeric for $t {
                 fn to_u64(self) -> u64 { self as u64 }
                 fn from_u64(v: u64) -> Self { v as Self }
+                fn try_from_u64(v: u64) -> Option<Self> { Self::try_from(v).ok() }
             }
         )*
     }
 }
 
-impl_hash_numeric!(u8, u16, u32, u64, u128, usize);
+impl_hash_numeric!(u8, u16, u32, u64, usize);
+
+impl HashNumeric for u128 {
+    fn to_u64(self) -> u64 { self as u64 }
+    fn from_u64(v: u64) -> Self { v as Self }
+
+    fn to_limbs(self) -> [u64; 2] {
+        [(self >> 64) as u64, self as u64]
+    }
+
+    fn try_from_limbs([high, low]: [u64; 2]) -> Option<Self> {
+        Some(join_limbs(high, low))
+    }
+}
+
+/// Joins two limbs, most significant first, into a `u128`.
+pub(crate) fn join_limbs(high: u64, low: u64) -> u128 {
+    (high as u128) << 64 | low as u128
+}
+
+/// Widens a numeric value to a `u128` without truncating it.
+#[cfg(feature = "interval")]
+pub(crate) fn numeric_to_u128<T: HashNumeric>(value: T) -> u128 {
+    let [high, low] = value.to_limbs();
+    join_limbs(high, low)
+}
+
+/// Narrows a `u128` to the numeric type, or returns `None` if it doesn't fit.
+#[cfg(any(feature = "cursor", feature = "interval"))]
+pub(crate) fn numeric_from_u128<T: HashNumeric>(value: u128) -> Option<T> {
+    T::try_from_limbs([(value >> 64) as u64, value as u64])
+}
+
+/// Encodes a numeric value with `encode`.
+///
+/// Values that fit in a `u64` are encoded as one value, so a `u128` field holding a small
+/// id hashes the same as a `u64` field. Larger `u128` values are encoded as both limbs,
+/// most significant first, in a single hash.
+pub(crate) fn encode_numeric<T: HashNumeric>(value: T, encode: impl Fn(&[u64]) -> String) -> String {
+    match value.to_limbs() {
+        [0, low] => encode(&[low]),
+        limbs => encode(&limbs),
+    }
+}
+
+/// Encodes a single id with the active options, as a `hash` field holding it is encoded.
+///
+/// The id wrappers ([`HashedId`](crate::HashedId),
+/// [`OrderedHash`](crate::ordered::OrderedHash)) encode and decode through this and
+/// [`decode_id`], and serialize through [`numeric`], so they all treat the options alike.
+pub(crate) fn encode_id<T: HashNumeric>(value: T) -> String {
+    encode_numeric(value, encode)
+}
+
+/// Decodes a hash written by [`encode_id`].
+///
+/// # Returns
+///
+/// An error if the hash is invalid or decodes to a value that doesn't fit in `T`.
+pub(crate) fn decode_id<T: HashNumeric>(hash: &str) -> crate::Result<T> {
+    let values = decode_field_values(hash)?;
+    numeric_from_values::<T, serde::de::value::Error>(&values, None)
+        .map_err(|e| SerdeHashError::InvalidHash(e.to_string()))
+}
+
+/// Rebuilds a numeric value from the values of a hash written by [`encode_numeric`],
+/// failing instead of truncating values that don't fit the type.
+pub(crate) fn numeric_from_values<T: HashNumeric, E: serde::de::Error>(
+    values: &[u64],
+    field: Option<&str>,
+) -> Result<T, E> {
+    match *values {
+        [value] => narrow(value, field),
+        [high, low] if high != 0 => numeric_from_limbs(high, low, field),
+        _ => Err(E::custom(format!("expected 1 value in hash, found {}", values.len()))),
+    }
+}
+
+/// Rebuilds a numeric value from both of its limbs, failing if it doesn't fit the type.
+fn numeric_from_limbs<T: HashNumeric, E: serde::de::Error>(
+    high: u64,
+    low: u64,
+    field: Option<&str>,
+) -> Result<T, E> {
+    T::try_from_limbs([high, low]).ok_or_else(|| does_not_fit::<T, E>(join_limbs(high, low), field))
+}
+
+/// Converts a decoded value to the field's type, failing instead of truncating values
+/// that don't fit, e.g. "hash decodes to 70000 which does not fit in u16 field 'age'".
+pub(crate) fn narrow<T: HashNumeric, E: serde::de::Error>(
+    value: u64,
+    field: Option<&str>,
+) -> Result<T, E> {
+    T::try_from_u64(value).ok_or_else(|| does_not_fit::<T, E>(value, field))
+}
+
+fn does_not_fit<T, E: serde::de::Error>(value: impl Display, field: Option<&str>) -> E {
+    let ty = std::any::type_name::<T>();
+    match field {
+        Some(field) => E::custom(format!(
+            "hash decodes to {} which does not fit in {} field '{}'",
+            value, ty, field
+        )),
+        None => E::custom(format!(
+            "hash decodes to {} which does not fit in {}",
+            value, ty
+        )),
+    }
+}
+
+/// Field shapes accepted by the `hash` attribute: a numeric value, or any nesting of
+/// `Vec` and `Option` around one (e.g. `Vec<Vec<u64>>` or `Option<Vec<u32>>`).
+///
+/// The shape decides how the encoded strings are laid out; the caller decides how the
+/// values of each hash are turned into a string and back. A hash holds one value, or both
+/// limbs of a `u128` that doesn't fit in a `u64`.
+pub trait HashShape: Sized {
+    fn serialize_with<S: Serializer>(
+        &self,
+        encode: impl Fn(&[u64]) -> String,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>;
+
+    fn deserialize_with<'de, D: Deserializer<'de>, E: Display>(
+        deserializer: D,
+        decode: impl Fn(&str) -> Result<Vec<u64>, E>,
+    ) -> Result<Self, D::Error>;
+
+    /// Like [`HashShape::deserialize_with`], naming `field` in errors for values that
+    /// don't fit the numeric type.
+    fn deserialize_field<'de, D: Deserializer<'de>, E: Display>(
+        deserializer: D,
+        field: Option<&str>,
+        decode: impl Fn(&str) -> Result<Vec<u64>, E>,
+    ) -> Result<Self, D::Error> {
+        let _ = field;
+        Self::deserialize_with(deserializer, decode)
+    }
+
+    /// Whether any value held by the shape is 0, for `#[hash(nonzero)]`.
+    fn contains_zero(&self) -> bool;
+}
+
+/// The error for a hash field of 0 that is rejected by `#[hash(nonzero)]` or
+/// [`SerdeHashOptions::with_reject_zero`](crate::hashids::SerdeHashOptions::with_reject_zero).
+fn zero_rejected<E: serde::ser::Error>() -> E {
+    E::custom("refusing to hash 0, which is rejected as an unset id")
+}
+
+/// Fails for a hash longer than the
+/// [`SerdeHashOptions::with_max_length`](crate::hashids::SerdeHashOptions::with_max_length)
+/// setting of the active options.
+pub(crate) fn check_max_length<E: serde::ser::Error>(hash: &str) -> Result<(), E> {
+    match active_setting(|options| options.max_length) {
+        Some(max) if hash.chars().count() > max => Err(E::custom(
+            SerdeHashError::MaxLengthExceeded {
+                length: hash.chars().count(),
+                max,
+            },
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Writes `hash` as a string, failing if it is longer than the maximum length.
+///
+/// Every serializer that writes a hash string goes through here, so none of them can skip
+/// the limit.
+pub(crate) fn serialize_hash<S: Serializer>(hash: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    check_max_length(hash)?;
+    serializer.serialize_str(hash)
+}
+
+/// Whether `T` is wider than a `u64`, so its raw values are written as `u128`.
+fn is_wide<T>() -> bool {
+    std::mem::size_of::<T>() > 8
+}
+
+impl<T: HashNumeric> HashShape for T {
+    fn serialize_with<S: Serializer>(
+        &self,
+        encode: impl Fn(&[u64]) -> String,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let [high, low] = self.to_limbs();
+        if high == 0 && low == 0 && active_setting(|options| options.reject_zero) {
+            return Err(zero_rejected());
+        }
+        match current_profile() {
+            SerializationProfile::Public => match active_setting(|options| options.msgpack_ext) {
+                Some(tag) if !serializer.is_human_readable() => {
+                    serialize_ext(tag, [high, low], serializer)
+                }
+                _ => serialize_hash(&encode_numeric(*self, encode), serializer),
+            },
+            SerializationProfile::Internal if is_wide::<T>() => {
+                serializer.serialize_u128(join_limbs(high, low))
+            }
+            SerializationProfile::Internal => serializer.serialize_u64(low),
+            SerializationProfile::Debug => serializer.serialize_str(&format!(
+                "{} ({})",
+                join_limbs(high, low),
+                encode_numeric(*self, encode)
+            )),
+        }
+    }
+
+    fn deserialize_with<'de, D: Deserializer<'de>, E: Display>(
+        deserializer: D,
+        decode: impl Fn(&str) -> Result<Vec<u64>, E>,
+    ) -> Result<Self, D::Error> {
+        Self::deserialize_field(deserializer, None, decode)
+    }
+
+    fn deserialize_field<'de, D: Deserializer<'de>, E: Display>(
+        deserializer: D,
+        field: Option<&str>,
+        decode: impl Fn(&str) -> Result<Vec<u64>, E>,
+    ) -> Result<Self, D::Error> {
+        if current_profile() == SerializationProfile::Internal {
+            return if is_wide::<T>() {
+                let value = u128::deserialize(deserializer)?;
+                T::try_from_limbs([(value >> 64) as u64, value as u64])
+                    .ok_or_else(|| does_not_fit::<T, D::Error>(value, field))
+            } else {
+                narrow(u64::deserialize(deserializer)?, field)
+            };
+        }
+        if let Some(tag) = active_setting(|options| options.msgpack_ext)
+            && current_profile() == SerializationProfile::Public
+            && !deserializer.is_human_readable()
+        {
+            let decoded = deserialize_ext(deserializer, tag, decode)?;
+            return numeric_from_values(&decoded, field);
+        }
+        let s = String::deserialize(deserializer)?;
+        let decoded = decode(&s).map_err(serde::de::Error::custom)?;
+        numeric_from_values(&decoded, field)
+    }
+
+    fn contains_zero(&self) -> bool {
+        self.to_limbs() == [0, 0]
+    }
+}
+
+impl<T: HashShape> HashShape for Vec<T> {
+    fn serialize_with<S: Serializer>(
+        &self,
+        encode: impl Fn(&[u64]) -> String,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(|v| ShapeWith(v, &encode)))
+    }
+
+    fn deserialize_with<'de, D: Deserializer<'de>, E: Display>(
+        deserializer: D,
+        decode: impl Fn(&str) -> Result<Vec<u64>, E>,
+    ) -> Result<Self, D::Error> {
+        Self::deserialize_field(deserializer, None, decode)
+    }
+
+    fn deserialize_field<'de, D: Deserializer<'de>, E: Display>(
+        deserializer: D,
+        field: Option<&str>,
+        decode: impl Fn(&str) -> Result<Vec<u64>, E>,
+    ) -> Result<Self, D::Error> {
+        struct SeqVisitor<'a, T, F, E>(&'a F, Option<&'a str>, PhantomData<fn() -> (T, E)>);
+
+        impl<'de, T, F, E> Visitor<'de> for SeqVisitor<'_, T, F, E>
+        where
+            T: HashShape,
+            F: Fn(&str) -> Result<Vec<u64>, E>,
+            E: Display,
+        {
+            type Value = Vec<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a sequence")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+                let seed = || ShapeSeed(self.0, self.1, PhantomData);
+                while let Some(value) = seq.next_element_seed(seed())? {
+                    values.push(value);
+                }
+                Ok(values)
+            }
+        }
+
+        deserializer.deserialize_seq(SeqVisitor(&decode, field, PhantomData))
+    }
+
+    fn contains_zero(&self) -> bool {
+        self.iter().any(HashShape::contains_zero)
+    }
+}
+
+impl<T: HashShape> HashShape for Option<T> {
+    fn serialize_with<S: Serializer>(
+        &self,
+        encode: impl Fn(&[u64]) -> String,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match self {
+            Some(v) => serializer.serialize_some(&ShapeWith(v, &encode)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    fn deserialize_with<'de, D: Deserializer<'de>, E: Display>(
+        deserializer: D,
+        decode: impl Fn(&str) -> Result<Vec<u64>, E>,
+    ) -> Result<Self, D::Error> {
+        Self::deserialize_field(deserializer, None, decode)
+    }
+
+    fn deserialize_field<'de, D: Deserializer<'de>, E: Display>(
+        deserializer: D,
+        field: Option<&str>,
+        decode: impl Fn(&str) -> Result<Vec<u64>, E>,
+    ) -> Result<Self, D::Error> {
+        struct OptionVisitor<'a, T, F, E>(&'a F, Option<&'a str>, PhantomData<fn() -> (T, E)>);
+
+        impl<'de, T, F, E> Visitor<'de> for OptionVisitor<'_, T, F, E>
+        where
+            T: HashShape,
+            F: Fn(&str) -> Result<Vec<u64>, E>,
+            E: Display,
+        {
+            type Value = Option<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an option")
+            }
+
+            fn visit_none<Er: serde::de::Error>(self) -> Result<Self::Value, Er> {
+                Ok(None)
+            }
+
+            fn visit_unit<Er: serde::de::Error>(self) -> Result<Self::Value, Er> {
+                Ok(None)
+            }
+
+            fn visit_some<D: Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<Self::Value, D::Error> {
+                T::deserialize_field(deserializer, self.1, self.0).map(Some)
+            }
+        }
+
+        deserializer.deserialize_option(OptionVisitor(&decode, field, PhantomData))
+    }
+
+    fn contains_zero(&self) -> bool {
+        self.as_ref().is_some_and(HashShape::contains_zero)
+    }
+}
+
+/// Serializes a nested value of a [`HashShape`] with the outer value's encoder.
+struct ShapeWith<'a, T, F>(&'a T, &'a F);
+
+impl<T: HashShape, F: Fn(&[u64]) -> String> Serialize for ShapeWith<'_, T, F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_with(self.1, serializer)
+    }
+}
+
+/// Deserializes a nested value of a [`HashShape`] with the outer value's decoder.
+struct ShapeSeed<'a, T, F, E>(&'a F, Option<&'a str>, PhantomData<fn() -> (T, E)>);
+
+impl<'de, T, F, E> DeserializeSeed<'de> for ShapeSeed<'_, T, F, E>
+where
+    T: HashShape,
+    F: Fn(&str) -> Result<Vec<u64>, E>,
+    E: Display,
+{
+    type Value = T;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+        T::deserialize_field(deserializer, self.1, self.0)
+    }
+}
+
+/// Serializes the borrowed field as hash strings.
+///
+/// Used by the code generated for `#[derive(HashIds)]`.
+pub struct Encoded<'a, T>(pub &'a T);
+
+impl<T: HashShape> Serialize for Encoded<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_with(encode, serializer)
+    }
+}
+
+/// Deserializes a field from hash strings.
+///
+/// Used by the code generated for `#[derive(HashIds)]`.
+pub struct Decoded<T>(pub T);
+
+impl<'de, T: HashShape> Deserialize<'de> for Decoded<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        DecodedField::new(None).deserialize(deserializer)
+    }
+}
+
+/// Deserializes a field from hash strings, naming the field in errors.
+///
+/// Used by the code generated for `#[derive(HashIds)]`.
+pub struct DecodedField<'a, T>(Option<&'a str>, PhantomData<fn() -> T>);
+
+impl<'a, T> DecodedField<'a, T> {
+    pub fn new(field: Option<&'a str>) -> Self {
+        Self(field, PhantomData)
+    }
+}
+
+impl<'de, T: HashShape> DeserializeSeed<'de> for DecodedField<'_, T> {
+    type Value = Decoded<T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Decoded<T>, D::Error> {
+        T::deserialize_field(deserializer, self.0, |s| {
+            decode_field_values(s).map_err(|e| format!("Failed to decode hash: {}", e))
+        })
+        .map(Decoded)
+    }
+}
+
+/// Serializes `value` with the hashes produced by `encoder` instead of the global options.
+///
+/// Used by the modules generated with [`hash_with_salt!`](crate::hash_with_salt).
+pub fn serialize_with_encoder<T: HashShape, S: Serializer>(
+    value: &T,
+    encoder: &HashIdsEncoder,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value.serialize_with(|v| encoder.encode(v), serializer)
+}
+
+/// Deserializes a value from hashes produced by `encoder` instead of the global options.
+///
+/// Used by the modules generated with [`hash_with_salt!`](crate::hash_with_salt).
+pub fn deserialize_with_encoder<'de, T: HashShape, D: Deserializer<'de>>(
+    encoder: &HashIdsEncoder,
+    deserializer: D,
+) -> Result<T, D::Error> {
+    T::deserialize_with(deserializer, |s| {
+        report_decode_error(s, encoder.decode_numeric(s))
+            .map_err(|e| format!("Failed to decode hash: {}", e))
+    })
+}
+
+/// Serializes the borrowed field as one-way digests.
+///
+/// Used by the code generated for `#[derive(HashIds)]` on `#[hash(redact)]` fields.
+#[cfg(feature = "hmac")]
+pub struct Redacted<'a, T>(pub &'a T);
+
+#[cfg(feature = "hmac")]
+impl<T: HashShape> Serialize for Redacted<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Digests never give way to raw values, whatever the profile.
+        with_profile(SerializationProfile::Public, || {
+            self.0.serialize_with(redact, serializer)
+        })
+    }
+}
+
+/// Serialization functions for a single field, for `hash` options that can't be expressed
+/// as a plain `with` module.
+///
+/// The macros point serde at an implementation of this trait, either one of the types
+/// below or a hidden type generated for the field (e.g. for `encode_with`).
+pub trait FieldWith<T> {
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error>;
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error>;
+}
+
+/// Hashes a field with the active options, like the built-in `with` modules.
+pub struct Hashed;
+
+impl<T: HashShape> FieldWith<T> for Hashed {
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        value.serialize_with(encode, serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        T::deserialize_with(deserializer, decode_field_values)
+    }
+}
+
+/// Fails to serialize a field that holds 0, then hashes it with `W`.
+///
+/// Used for `#[hash(nonzero)]`, so the hash of a placeholder id such as an unset primary
+/// key is never published. Deserialization is left to `W`.
+pub struct NonZero<W = Hashed>(PhantomData<W>);
+
+impl<T: HashShape, W: FieldWith<T>> FieldWith<T> for NonZero<W> {
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        if value.contains_zero() {
+            return Err(zero_rejected());
+        }
+        W::serialize(value, serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        W::deserialize(deserializer)
+    }
+}
+
+/// Passes `decoded` through if every value is in `range`, for `#[hash(range = "...")]`.
+///
+/// `expected` is the range as written on the field, for the error message. Values are
+/// checked as `T`, the field's numeric type, so a value that doesn't fit in `T` is out of
+/// range, too.
+#[doc(hidden)]
+pub fn check_range<T, E: Display>(
+    decoded: Result<Vec<u64>, E>,
+    range: impl RangeBounds<T>,
+    expected: &str,
+) -> Result<Vec<u64>, String>
+where
+    T: TryFrom<u64> + PartialOrd,
+{
+    let values = decoded.map_err(|e| e.to_string())?;
+    match values
+        .iter()
+        .find(|&&value| !T::try_from(value).is_ok_and(|value| range.contains(&value)))
+    {
+        Some(value) => Err(format!(
+            "decoded value {} is outside the allowed range {}",
+            value, expected
+        )),
+        None => Ok(values),
+    }
+}
+
+/// Encodes a field with a minimum hash length of `N`, overriding the global setting.
+///
+/// Used for `#[hash(min_length = N)]`. Decoding uses the same minimum length, which the
+/// hardened decoder needs to verify the hash.
+pub struct MinLength<const N: usize>;
+
+impl<T: HashShape, const N: usize> FieldWith<T> for MinLength<N> {
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        value.serialize_with(|v| encode_with_min_length(v, N), serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        T::deserialize_with(deserializer, |s| {
+            report_decode_error(s, decode_numeric_with_min_length(s, N))
+        })
+    }
+}
+
+/// Masks all but `keep` digits of `value` with `*`, keeping the last digits when
+/// `from_end` is set and the first ones otherwise, e.g. `"****1234"`.
+///
+/// Every digit is masked when the value has no more than `keep` digits, so short values
+/// are never shown in full.
+pub fn mask_single(value: u64, keep: usize, from_end: bool) -> String {
+    mask_digits(value.to_string(), keep, from_end)
+}
+
+/// Masks the value held by the limbs of one hash, see [`HashNumeric::to_limbs`].
+fn mask_limbs(limbs: &[u64], keep: usize, from_end: bool) -> String {
+    mask_digits(limb_digits(limbs), keep, from_end)
+}
+
+/// The decimal digits of the value held by the limbs of one hash.
+fn limb_digits(limbs: &[u64]) -> String {
+    match *limbs {
+        [high, low] => join_limbs(high, low).to_string(),
+        _ => limbs.iter().map(u64::to_string).collect(),
+    }
+}
+
+fn mask_digits(digits: String, keep: usize, from_end: bool) -> String {
+    let hidden = if digits.len() > keep { digits.len() - keep } else { digits.len() };
+    let mask = "*".repeat(hidden);
+    if hidden == digits.len() {
+        mask
+    } else if from_end {
+        mask + &digits[hidden..]
+    } else {
+        digits[..keep].to_string() + &mask
+    }
+}
+
+/// Serializes a field with all but `KEEP` digits masked, see [`mask_single`].
+///
+/// Used for `#[hash(mask = "lastN")]` and `#[hash(mask = "firstN")]`. Masking can't be
+/// undone, so deserialization only accepts the raw numeric value.
+pub struct Masked<const KEEP: usize, const FROM_END: bool>;
+
+impl<T, const KEEP: usize, const FROM_END: bool> FieldWith<T> for Masked<KEEP, FROM_END>
+where
+    T: HashShape + DeserializeOwned,
+{
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        with_profile(SerializationProfile::Public, || {
+            value.serialize_with(|v| mask_limbs(v, KEEP, FROM_END), serializer)
+        })
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        T::deserialize(deserializer)
+    }
+}
+
+/// Serializes a field as its decimal digits in a string, e.g. `"9007199254740993"`, and
+/// parses it back, without hashing.
+///
+/// Used for `#[hash(stringify_only)]`, for JavaScript clients that can't hold ids above
+/// 2^53 as numbers but have no need to hide them. The hash settings of the active options
+/// don't apply, and the field is written the same under every profile.
+pub struct Stringified;
+
+impl<T: DecimalShape> FieldWith<T> for Stringified {
+    fn serialize<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        value.to_decimal().serialize(serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        T::from_decimal(T::Decimal::deserialize(deserializer)?)
+    }
+}
+
+/// Field shapes accepted by [`Stringified`]: the [`HashShape`]s, with every numeric value
+/// written as a string of its digits.
+pub trait DecimalShape: Sized {
+    /// The same shape holding strings, e.g. `Vec<String>` for a `Vec<u64>`.
+    type Decimal: Serialize + DeserializeOwned;
+
+    fn to_decimal(&self) -> Self::Decimal;
+
+    fn from_decimal<E: serde::de::Error>(decimal: Self::Decimal) -> Result<Self, E>;
+}
+
+impl<T: HashNumeric> DecimalShape for T {
+    type Decimal = String;
+
+    fn to_decimal(&self) -> String {
+        limb_digits(&self.to_limbs())
+    }
+
+    fn from_decimal<E: serde::de::Error>(decimal: String) -> Result<Self, E> {
+        // `u128::from_str` also takes a leading `+`, which no client writes for an id.
+        let value = Some(&decimal)
+            .filter(|decimal| decimal.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|decimal| decimal.parse::<u128>().ok())
+            .ok_or_else(|| E::custom(format!("Invalid numeric string: {}", decimal)))?;
+        T::try_from_limbs([(value >> 64) as u64, value as u64]).ok_or_else(|| {
+            E::custom(format!(
+                "{} does not fit in {}",
+                value,
+                std::any::type_name::<T>()
+            ))
+        })
+    }
+}
+
+impl<T: DecimalShape> DecimalShape for Vec<T> {
+    type Decimal = Vec<T::Decimal>;
+
+    fn to_decimal(&self) -> Self::Decimal {
+        self.iter().map(T::to_decimal).collect()
+    }
+
+    fn from_decimal<E: serde::de::Error>(decimal: Self::Decimal) -> Result<Self, E> {
+        decimal.into_iter().map(T::from_decimal).collect()
+    }
+}
+
+impl<T: DecimalShape> DecimalShape for Option<T> {
+    type Decimal = Option<T::Decimal>;
+
+    fn to_decimal(&self) -> Self::Decimal {
+        self.as_ref().map(T::to_decimal)
+    }
+
+    fn from_decimal<E: serde::de::Error>(decimal: Self::Decimal) -> Result<Self, E> {
+        decimal.map(T::from_decimal).transpose()
+    }
+}
+
+/// Serializes the borrowed field through a [`FieldWith`] implementation.
+#[doc(hidden)]
+pub struct SerializeWith<'a, T, W>(&'a T, PhantomData<W>);
+
+impl<'a, T, W> SerializeWith<'a, T, W> {
+    pub fn new(value: &'a T) -> Self {
+        Self(value, PhantomData)
+    }
+}
+
+impl<T, W: FieldWith<T>> Serialize for SerializeWith<'_, T, W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        W::serialize(self.0, serializer)
+    }
+}
+
+/// Deserializes a field through a [`FieldWith`] implementation.
+#[doc(hidden)]
+pub struct DeserializeWith<T, W>(T, PhantomData<W>);
+
+impl<T, W> DeserializeWith<T, W> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<'de, T, W: FieldWith<T>> Deserialize<'de> for DeserializeWith<T, W> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        W::deserialize(deserializer).map(|value| Self(value, PhantomData))
+    }
+}
+
+/// Serializes two fields as one hash of both values.
+///
+/// Used by the code generated for `#[derive(HashIds)]` on `#[hash(pack_with = "...")]`
+/// fields, e.g. for composite keys exposed as a single token. If either value is larger
+/// than `u64::MAX`, the hash holds both limbs of each value instead, like
+/// [`encode_numeric`] does for a single `u128`.
+#[doc(hidden)]
+pub struct Packed<'a, A, B>(pub &'a A, pub &'a B);
+
+impl<A: HashNumeric, B: HashNumeric> Serialize for Packed<'_, A, B> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let limbs = (self.0.to_limbs(), self.1.to_limbs());
+        if (limbs.0 == [0, 0] || limbs.1 == [0, 0])
+            && active_setting(|options| options.reject_zero)
+        {
+            return Err(zero_rejected());
+        }
+        let values = match limbs {
+            ([0, first], [0, second]) => vec![first, second],
+            ([first_high, first_low], [second_high, second_low]) => {
+                vec![first_high, first_low, second_high, second_low]
+            }
+        };
+        serialize_hash(&encode(&values), serializer)
+    }
+}
+
+/// Deserializes the two values written by [`Packed`], naming the field in errors.
+#[doc(hidden)]
+pub struct PackedField<'a, A, B>(&'a str, PhantomData<fn() -> (A, B)>);
+
+impl<'a, A, B> PackedField<'a, A, B> {
+    pub fn new(field: &'a str) -> Self {
+        Self(field, PhantomData)
+    }
+}
+
+impl<'de, A: HashNumeric, B: HashNumeric> DeserializeSeed<'de> for PackedField<'_, A, B> {
+    type Value = (A, B);
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(A, B), D::Error> {
+        use serde::de::Error;
+
+        let hash = String::deserialize(deserializer)?;
+        let values = report_decode_error(&hash, decode(&hash))
+            .map_err(|e| D::Error::custom(format!("Failed to decode hash: {}", e)))?;
+        let field = Some(self.0);
+        match values[..] {
+            [first, second] => Ok((narrow(first, field)?, narrow(second, field)?)),
+            [first_high, first_low, second_high, second_low]
+                if first_high != 0 || second_high != 0 =>
+            {
+                Ok((
+                    numeric_from_limbs(first_high, first_low, field)?,
+                    numeric_from_limbs(second_high, second_low, field)?,
+                ))
+            }
+            _ => Err(D::Error::custom(format!(
+                "expected 2 values in packed field '{}', found {}",
+                self.0,
+                values.len()
+            ))),
+        }
+    }
+}
+
+/// Returns the error reported when a redacted field shows up in the input.
+#[doc(hidden)]
+pub fn redacted_field<T, E: serde::de::Error>(field: &str) -> Result<T, E> {
+    Err(E::custom(format!(
+        "field '{}' is redacted and cannot be deserialized",
+        field
+    )))
+}
 
 /// Serde `with` module for plain numeric fields (`u8`, `u16`, `u32`, `u64`, `u128`, `usize`).
 ///
@@ -30,16 +877,13 @@ pub mod numeric {
         value: &T,
         serializer: S,
     ) -> Result<S::Ok, S::Error> {
-        let encoded = encode_single(value.to_u64());
-        serializer.serialize_str(&encoded)
+        value.serialize_with(encode, serializer)
     }
 
     pub fn deserialize<'de, T: HashNumeric, D: Deserializer<'de>>(
         deserializer: D,
     ) -> Result<T, D::Error> {
-        let s = String::deserialize(deserializer)?;
-        let decoded = decode_single(&s).map_err(serde::de::Error::custom)?;
-        Ok(T::from_u64(decoded))
+        T::deserialize_with(deserializer, decode_field_values)
     }
 }
 
@@ -53,21 +897,95 @@ pub mod vec_numeric {
         value: &[T],
         serializer: S,
     ) -> Result<S::Ok, S::Error> {
-        let encoded: Vec<String> = value.iter().map(|v| encode_single(v.to_u64())).collect();
-        encoded.serialize(serializer)
+        serializer.collect_seq(value.iter().map(|v| ShapeWith(v, &encode)))
+    }
+
+    pub fn deserialize<'de, T: HashNumeric, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<T>, D::Error> {
+        Vec::deserialize_with(deserializer, decode_field_values)
+    }
+}
+
+/// Serde `with` module for long `Vec<T>` lists of clustered ids, written as one hash.
+///
+/// Each value is stored as the difference to the one before it, packed into a varint
+/// stream, and the stream is hashed with [`crate::hashids::encode_bytes`]. Runs of nearly
+/// sequential ids take one or two characters each instead of a full hash, so a page of
+/// thousands of ids shrinks to a fraction of the size of [`vec_numeric`]. The list comes
+/// back in its original order, duplicates and all; values don't have to be sorted, but
+/// large jumps between neighbours cost more. `u128` values above `u64::MAX` fail to
+/// serialize.
+///
+/// Usage: `#[serde(with = "serde_hash::serde_impl::vec_numeric_compact")]`
+pub mod vec_numeric_compact {
+    use super::*;
+    use crate::hashids::{decode_bytes, encode_bytes};
+
+    pub fn serialize<T: HashNumeric, S: Serializer>(
+        value: &[T],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut stream = Vec::with_capacity(value.len());
+        let mut previous = 0u64;
+        for v in value {
+            let [0, v] = v.to_limbs() else {
+                return Err(serde::ser::Error::custom(
+                    "vec_numeric_compact only holds values up to u64::MAX",
+                ));
+            };
+            // Zigzag the wrapping difference so small steps in either direction stay small.
+            let delta = v.wrapping_sub(previous) as i64;
+            write_varint(&mut stream, ((delta << 1) ^ (delta >> 63)) as u64);
+            previous = v;
+        }
+        serialize_hash(&encode_bytes(&stream), serializer)
     }
 
     pub fn deserialize<'de, T: HashNumeric, D: Deserializer<'de>>(
         deserializer: D,
     ) -> Result<Vec<T>, D::Error> {
-        let strings = Vec::<String>::deserialize(deserializer)?;
-        strings
-            .into_iter()
-            .map(|s| {
-                let decoded = decode_single(&s).map_err(serde::de::Error::custom)?;
-                Ok(T::from_u64(decoded))
-            })
-            .collect()
+        let hash = String::deserialize(deserializer)?;
+        let stream = report_decode_error(&hash, decode_bytes(&hash))
+            .map_err(serde::de::Error::custom)?;
+        let mut values = Vec::new();
+        let mut previous = 0u64;
+        let mut bytes = stream.iter();
+        while bytes.len() > 0 {
+            let zigzag = read_varint(&mut bytes).ok_or_else(|| {
+                serde::de::Error::custom("compact hash holds a truncated value")
+            })?;
+            let delta = (zigzag >> 1) as i64 ^ -((zigzag & 1) as i64);
+            previous = previous.wrapping_add(delta as u64);
+            values.push(narrow(previous, None)?);
+        }
+        Ok(values)
+    }
+
+    /// Appends `value` as a LEB128 varint: seven bits per byte, low bits first.
+    fn write_varint(stream: &mut Vec<u8>, mut value: u64) {
+        while value >= 0x80 {
+            stream.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        stream.push(value as u8);
+    }
+
+    /// Reads a varint written by [`write_varint`], or `None` if the stream ends inside
+    /// one or it doesn't fit in a `u64`.
+    fn read_varint(bytes: &mut std::slice::Iter<u8>) -> Option<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *bytes.next()?;
+            if shift == 63 && byte > 1 {
+                return None;
+            }
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
     }
 }
 
@@ -81,29 +999,94 @@ pub mod option_numeric {
         value: &Option<T>,
         serializer: S,
     ) -> Result<S::Ok, S::Error> {
-        match value {
-            Some(v) => {
-                let encoded = encode_single(v.to_u64());
-                serializer.serialize_some(&encoded)
-            }
-            None => serializer.serialize_none(),
-        }
+        value.serialize_with(encode, serializer)
     }
 
     pub fn deserialize<'de, T: HashNumeric, D: Deserializer<'de>>(
         deserializer: D,
     ) -> Result<Option<T>, D::Error> {
-        let opt = Option::<String>::deserialize(deserializer)?;
-        match opt {
-            Some(s) => {
-                let decoded = decode_single(&s).map_err(serde::de::Error::custom)?;
-                Ok(Some(T::from_u64(decoded)))
-            }
-            None => Ok(None),
+        Option::deserialize_with(deserializer, decode_field_values)
+    }
+}
+
+/// Decodes a hash for the lenient modules, or returns `None` if it doesn't decode to a
+/// value that fits in `T`. Decode failures still reach the decode error hook.
+fn decode_lenient<T: HashNumeric>(hash: &str) -> Option<T> {
+    let values = decode_field_values(hash).ok()?;
+    numeric_from_values::<T, serde::de::value::Error>(&values, None).ok()
+}
+
+/// Serde `with` module for numeric fields that fall back to `T::default()` instead of
+/// failing when their hash doesn't decode.
+///
+/// Ingestion pipelines use this so one corrupted hash doesn't reject a whole batch. The
+/// input must still be a string; only the hash itself is treated leniently. See
+/// [`Lenient`] for a fallback other than the default, and [`numeric_lenient::option`]
+/// to get `None` instead.
+///
+/// Usage: `#[serde(with = "serde_hash::serde_impl::numeric_lenient")]`
+pub mod numeric_lenient {
+    use super::*;
+
+    pub fn serialize<T: HashNumeric, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        numeric::serialize(value, serializer)
+    }
+
+    pub fn deserialize<'de, T: HashNumeric + Default, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(decode_lenient(&s).unwrap_or_default())
+    }
+
+    /// Serde `with` module for `Option<T>` fields that read as `None` when their hash
+    /// doesn't decode.
+    ///
+    /// Usage: `#[serde(with = "serde_hash::serde_impl::numeric_lenient::option")]`
+    pub mod option {
+        use super::*;
+
+        pub fn serialize<T: HashNumeric, S: Serializer>(
+            value: &Option<T>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            option_numeric::serialize(value, serializer)
+        }
+
+        pub fn deserialize<'de, T: HashNumeric, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<T>, D::Error> {
+            let opt = Option::<String>::deserialize(deserializer)?;
+            Ok(opt.and_then(|s| decode_lenient(&s)))
         }
     }
 }
 
+/// Like [`numeric_lenient`], but falls back to `SENTINEL` instead of the default, e.g. to
+/// tell a corrupted hash apart from a real id of 0.
+///
+/// Usage: `#[serde(with = "serde_hash::serde_impl::Lenient::<{ u64::MAX }>")]`
+pub struct Lenient<const SENTINEL: u64>;
+
+impl<const SENTINEL: u64> Lenient<SENTINEL> {
+    pub fn serialize<T: HashNumeric, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        numeric::serialize(value, serializer)
+    }
+
+    pub fn deserialize<'de, T: HashNumeric, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(decode_lenient(&s).unwrap_or_else(|| T::from_u64(SENTINEL)))
+    }
+}
+
 /// Serde `with` module for `Option<Vec<T>>` where `T` is a numeric type.
 ///
 /// Usage: `#[serde(with = "serde_hash::serde_impl::option_vec_numeric")]`
@@ -114,32 +1097,105 @@ pub mod option_vec_numeric {
         value: &Option<Vec<T>>,
         serializer: S,
     ) -> Result<S::Ok, S::Error> {
-        match value {
-            Some(vec) => {
-                let encoded: Vec<String> =
-                    vec.iter().map(|v| encode_single(v.to_u64())).collect();
-                serializer.serialize_some(&encoded)
-            }
-            None => serializer.serialize_none(),
-        }
+        value.serialize_with(encode, serializer)
     }
 
     pub fn deserialize<'de, T: HashNumeric, D: Deserializer<'de>>(
         deserializer: D,
     ) -> Result<Option<Vec<T>>, D::Error> {
-        let opt = Option::<Vec<String>>::deserialize(deserializer)?;
-        match opt {
-            Some(strings) => {
-                let result: Result<Vec<T>, _> = strings
-                    .into_iter()
-                    .map(|s| {
-                        let decoded = decode_single(&s).map_err(serde::de::Error::custom)?;
-                        Ok(T::from_u64(decoded))
-                    })
-                    .collect();
-                Ok(Some(result?))
-            }
-            None => Ok(None),
-        }
+        Option::deserialize_with(deserializer, decode_field_values)
+    }
+}
+
+/// Serde `with` module for `Vec<Vec<T>>` where `T` is a numeric type, e.g. adjacency
+/// lists of IDs.
+///
+/// Usage: `#[serde(with = "serde_hash::serde_impl::vec_vec_numeric")]`
+pub mod vec_vec_numeric {
+    use super::*;
+
+    pub fn serialize<T: HashNumeric, S: Serializer>(
+        value: &[Vec<T>],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(value.iter().map(Encoded))
+    }
+
+    pub fn deserialize<'de, T: HashNumeric, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Vec<T>>, D::Error> {
+        Decoded::deserialize(deserializer).map(|decoded| decoded.0)
+    }
+}
+
+/// Serde `with` module for any nesting of `Vec` and `Option` around a numeric type,
+/// such as `Option<Vec<Vec<u64>>>`.
+///
+/// Usage: `#[serde(with = "serde_hash::serde_impl::nested")]`
+pub mod nested {
+    use super::*;
+
+    pub fn serialize<T: HashShape, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Encoded(value).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: HashShape, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        Decoded::deserialize(deserializer).map(|decoded| decoded.0)
+    }
+}
+
+/// Serde `with` module for `#[hash(redact)]` fields of any supported shape.
+///
+/// Values are serialized as salted one-way digests (see [`crate::hashids::redact_single`]).
+/// Deserialization always fails, since the original value can't be recovered.
+///
+/// Usage: `#[serde(with = "serde_hash::serde_impl::redact")]`
+#[cfg(feature = "hmac")]
+pub mod redact {
+    use super::*;
+
+    pub fn serialize<T: HashShape, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Redacted(value).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: HashShape, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        serde::de::IgnoredAny::deserialize(deserializer)?;
+        Err(serde::de::Error::custom(
+            "redacted fields cannot be deserialized",
+        ))
+    }
+}
+
+/// Serde `with` module for `#[hash(redact, accept_raw)]` fields of any supported shape.
+///
+/// Serializes like [`redact`], but deserializes the raw numeric value, so internal
+/// producers can still send unredacted payloads.
+///
+/// Usage: `#[serde(with = "serde_hash::serde_impl::redact_accept_raw")]`
+#[cfg(feature = "hmac")]
+pub mod redact_accept_raw {
+    use super::*;
+
+    pub fn serialize<T: HashShape, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Redacted(value).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: HashShape + Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::deserialize(deserializer)
     }
 }
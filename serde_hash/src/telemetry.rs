@@ -0,0 +1,81 @@
+//! Counters and histograms reported through the [`metrics`](https://docs.rs/metrics) crate.
+//!
+//! With the `metrics` feature enabled, every encoder records the metrics below with
+//! whichever recorder the application installed (e.g. a Prometheus exporter). Decode
+//! failures are worth a dashboard: a spike usually means someone is guessing ids.
+//! Without the feature nothing is recorded.
+//!
+//! With the `trace-derive` feature, the `Serialize` and `Deserialize` impls generated by
+//! `#[derive(HashIds)]` run inside a [`tracing`](https://docs.rs/tracing) span at trace
+//! level, named `serde_hash`, with the struct as `ty`, the direction as `op`
+//! (`"serialize"` or `"deserialize"`) and the number of hashed fields as `hashed_fields`.
+//! Failures are logged as a trace event inside the span, so slow or failing payloads can
+//! be traced back to the struct being (de)serialized.
+
+/// Counter of values encoded into hashes.
+pub const ENCODES: &str = "serde_hash_encodes_total";
+/// Counter of hashes decoded, successfully or not.
+pub const DECODES: &str = "serde_hash_decodes_total";
+/// Counter of hashes that failed to decode.
+pub const DECODE_FAILURES: &str = "serde_hash_decode_failures_total";
+/// Histogram of the time taken by each encode, in seconds.
+pub const ENCODE_SECONDS: &str = "serde_hash_encode_seconds";
+
+/// Runs `encode`, recording it as one encode and timing it.
+pub(crate) fn record_encode(encode: impl FnOnce() -> String) -> String {
+    #[cfg(feature = "metrics")]
+    {
+        let start = std::time::Instant::now();
+        let hash = encode();
+        metrics::histogram!(ENCODE_SECONDS).record(start.elapsed().as_secs_f64());
+        metrics::counter!(ENCODES).increment(1);
+        hash
+    }
+    #[cfg(not(feature = "metrics"))]
+    encode()
+}
+
+/// Records one decode, and one failure if `result` is an error.
+pub(crate) fn record_decode<T, E>(result: Result<T, E>) -> Result<T, E> {
+    #[cfg(feature = "metrics")]
+    {
+        metrics::counter!(DECODES).increment(1);
+        if result.is_err() {
+            metrics::counter!(DECODE_FAILURES).increment(1);
+        }
+    }
+    result
+}
+
+/// The span around a derived `Serialize` or `Deserialize` impl, see the
+/// [module documentation](self). Does nothing without the `trace-derive` feature.
+#[doc(hidden)]
+pub struct DeriveSpan {
+    #[cfg(feature = "trace-derive")]
+    _span: tracing::span::EnteredSpan,
+}
+
+impl DeriveSpan {
+    /// Enters the span for `op` on the struct `ty`, which has `hashed_fields` hashed fields.
+    pub fn enter(ty: &'static str, op: &'static str, hashed_fields: usize) -> Self {
+        #[cfg(feature = "trace-derive")]
+        {
+            let span = tracing::trace_span!("serde_hash", ty, op, hashed_fields);
+            Self { _span: span.entered() }
+        }
+        #[cfg(not(feature = "trace-derive"))]
+        {
+            let _ = (ty, op, hashed_fields);
+            Self {}
+        }
+    }
+
+    /// Logs `result` if it failed, then leaves the span.
+    pub fn finish<T, E: std::fmt::Display>(self, result: Result<T, E>) -> Result<T, E> {
+        #[cfg(feature = "trace-derive")]
+        if let Err(e) = &result {
+            tracing::trace!(error = %e, "failed");
+        }
+        result
+    }
+}
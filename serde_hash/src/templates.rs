@@ -0,0 +1,107 @@
+//! Template filters that render ids as hashes.
+//!
+//! Server-rendered pages link to the same resources as the JSON API, so their URLs have
+//! to carry the same hashes. With the `tera` or `askama` feature, a `hashid` filter
+//! encodes an id with the global options (or the task's [`crate::context`]) right in the
+//! template: `<a href="/users/{{ user.id | hashid }}">`.
+//!
+//! For other engines, [`crate::HashDisplay`] renders an id as its hash with `Display`.
+
+/// A [Tera](https://docs.rs/tera) filter, enabled with the `tera` feature.
+///
+/// # Example
+/// ```
+/// use serde_hash::hashids::{SerdeHashOptions, encode_single};
+///
+/// SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+///
+/// let mut tera = tera::Tera::default();
+/// serde_hash::templates::tera::register(&mut tera);
+/// tera.add_raw_template("link", "/users/{{ id | hashid }}").unwrap();
+///
+/// let mut context = tera::Context::new();
+/// context.insert("id", &42);
+/// let link = tera.render("link", &context).unwrap();
+/// assert_eq!(link, format!("/users/{}", encode_single(42)));
+/// ```
+#[cfg(feature = "tera")]
+pub mod tera {
+    use crate::hashids::encode;
+    use crate::serde_impl::encode_numeric;
+    use ::tera::{Kwargs, State, Tera};
+
+    /// Encodes an unsigned integer into its hash. Values that aren't unsigned integers
+    /// fail to render.
+    pub fn hashid(id: u128, _: Kwargs, _: &State) -> String {
+        encode_numeric(id, encode)
+    }
+
+    /// Registers [`hashid`] under the name `hashid`.
+    pub fn register(tera: &mut Tera) {
+        tera.register_filter("hashid", hashid);
+    }
+}
+
+/// An [Askama](https://docs.rs/askama) filter, enabled with the `askama` feature.
+///
+/// Askama looks custom filters up in a `filters` module next to the template, so
+/// re-export [`hashid`] from there.
+///
+/// # Example
+/// ```
+/// use askama::Template;
+/// use serde_hash::hashids::{SerdeHashOptions, encode_single};
+///
+/// mod filters {
+///     pub use serde_hash::templates::askama::hashid;
+/// }
+///
+/// #[derive(Template)]
+/// #[template(source = "/users/{{ id|hashid }}", ext = "txt")]
+/// struct Link {
+///     id: u64,
+/// }
+///
+/// SerdeHashOptions::new().with_salt("replace-with-a-random-salt").build();
+///
+/// let link = Link { id: 42 }.render().unwrap();
+/// assert_eq!(link, format!("/users/{}", encode_single(42)));
+/// ```
+#[cfg(feature = "askama")]
+pub mod askama {
+    use crate::hashids::encode;
+    use crate::serde_impl::encode_numeric;
+
+    /// Ids the [`hashid`] filter accepts: the unsigned integer types, and references to
+    /// them as handed out by askama's loops.
+    pub trait TemplateId {
+        /// Encodes the id with the active options.
+        fn encode_id(&self) -> String;
+    }
+
+    macro_rules! impl_template_id {
+        ($($t:ty),*) => {
+            $(
+                impl TemplateId for $t {
+                    fn encode_id(&self) -> String {
+                        encode_numeric(*self, encode)
+                    }
+                }
+            )*
+        }
+    }
+
+    impl_template_id!(u8, u16, u32, u64, u128, usize);
+
+    impl<T: TemplateId + ?Sized> TemplateId for &T {
+        fn encode_id(&self) -> String {
+            (**self).encode_id()
+        }
+    }
+
+    /// Encodes an id into its hash.
+    #[::askama::filter_fn]
+    pub fn hashid(id: impl TemplateId, _: &dyn ::askama::Values) -> ::askama::Result<String> {
+        Ok(id.encode_id())
+    }
+}
@@ -0,0 +1,108 @@
+//! A predictable stand-in for the real encoder in unit tests of downstream services.
+//!
+//! [`MockCodec`] writes `42` as `"h42"` instead of a salted hash, so tests can assert on
+//! serialized JSON without knowing real hashes and without racing other tests to set up
+//! the global options. Install it through a context rather than [`SerdeHashOptions::build`]:
+//! with [`set_options_provider`](crate::provider::set_options_provider) for the whole test
+//! binary, or with `with_context_sync` (with the `tokio` feature) for a single test.
+//!
+//! # Example
+//! ```
+//! use serde::Serialize;
+//! use serde_hash::serde_hash;
+//! use serde_hash::testing::MockCodec;
+//!
+//! #[serde_hash]
+//! #[derive(Serialize)]
+//! pub struct User {
+//!     #[serde(hash)]
+//!     pub id: u64,
+//! }
+//!
+//! serde_hash::provider::set_options_provider(MockCodec::new().context()).unwrap();
+//! assert_eq!(serde_json::to_string(&User { id: 42 }).unwrap(), r#"{"id":"h42"}"#);
+//! ```
+//!
+//! Only available with the `testing` feature.
+
+use crate::context::HashContext;
+use crate::error::{Result, SerdeHashError};
+use crate::hashids::SerdeHashOptions;
+
+/// Writes hashes as a prefix followed by the values, e.g. `"h42"` or `"h1-2"` for several
+/// values, and reads them back.
+///
+/// Unlike [`SerdeHashOptions::with_placeholders`], the format is short enough to spell
+/// out in expected JSON, and it doesn't touch the global options.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockCodec {
+    prefix: String,
+}
+
+impl Default for MockCodec {
+    fn default() -> Self {
+        Self {
+            prefix: "h".to_string(),
+        }
+    }
+}
+
+impl MockCodec {
+    /// Creates a codec writing `"h<value>"`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the prefix written in front of the values, `"h"` by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix, e.g. `"usr_"` to make hashes look like prefixed ids.
+    ///
+    /// # Returns
+    ///
+    /// Self with the updated prefix for method chaining.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Encodes `values` as the prefix followed by the values separated by `-`.
+    pub fn encode(&self, values: &[u64]) -> String {
+        let values: Vec<String> = values.iter().map(u64::to_string).collect();
+        format!("{}{}", self.prefix, values.join("-"))
+    }
+
+    /// Decodes a hash written by [`MockCodec::encode`].
+    ///
+    /// # Returns
+    ///
+    /// * The values of the hash.
+    /// * An error if the hash doesn't start with the prefix or holds anything but values.
+    pub fn decode(&self, hash: &str) -> Result<Vec<u64>> {
+        let invalid = || SerdeHashError::InvalidHash(format!("{} (not a mock hash)", hash));
+        let values = hash.strip_prefix(&self.prefix).ok_or_else(invalid)?;
+        if values.is_empty() {
+            return Ok(Vec::new());
+        }
+        values
+            .split('-')
+            .map(|value| value.parse().map_err(|_| invalid()))
+            .collect()
+    }
+
+    /// Returns a context hashing with this codec, to install with
+    /// [`set_options_provider`](crate::provider::set_options_provider) or scope to a
+    /// single test with `with_context_sync`.
+    pub fn context(&self) -> HashContext {
+        self.options()
+            .into_context()
+            .expect("the default options with a mock codec are valid")
+    }
+
+    /// Returns the default options with this codec in place of the real encoder, for
+    /// tests that need other settings too.
+    pub fn options(&self) -> SerdeHashOptions {
+        SerdeHashOptions::new().with_mock_codec(self.clone())
+    }
+}
@@ -0,0 +1,86 @@
+//! Hashes as URL path segments and query values.
+//!
+//! Hashes only need escaping if the alphabet has characters outside the unreserved URL set
+//! (`A-Z`, `a-z`, `0-9`, `-`, `.`, `_` and `~`). Such a hash breaks in subtle ways when put
+//! into a URL as-is: `/` splits a path segment, `+` turns into a space in query strings
+//! and `#` cuts the URL short. Either require a URL-safe alphabet with
+//! [`SerdeHashOptions::with_url_safe`], or build URLs with [`encode_url_component`], which
+//! percent-encodes what has to be.
+//!
+//! # Example
+//! ```
+//! use serde_hash::hashids::SerdeHashOptions;
+//! use serde_hash::url::{decode_url_component, encode_url_component};
+//!
+//! SerdeHashOptions::new()
+//!     .with_salt("replace-with-a-random-salt")
+//!     .with_alphabet("abcdefghijklmnopqrstuvwxyz+/#?")
+//!     .build();
+//!
+//! let segment = encode_url_component(42);
+//! assert!(segment.chars().all(|c| c.is_ascii_alphanumeric() || c == '%'));
+//! assert_eq!(decode_url_component(&segment).unwrap(), 42);
+//! ```
+//!
+//! [`SerdeHashOptions::with_url_safe`]: crate::hashids::SerdeHashOptions::with_url_safe
+
+use crate::alphabet::is_url_safe;
+use crate::error::{Result, SerdeHashError};
+use crate::hashids::{decode_single, encode_single};
+use std::fmt::Write;
+
+/// Percent-encodes every character of `hash` that isn't an unreserved URL character.
+/// Hashes of URL-safe alphabets are returned unchanged.
+pub fn percent_encode(hash: &str) -> String {
+    let mut encoded = String::with_capacity(hash.len());
+    for c in hash.chars() {
+        if is_url_safe(c) {
+            encoded.push(c);
+        } else {
+            for byte in c.encode_utf8(&mut [0; 4]).bytes() {
+                write!(encoded, "%{:02X}", byte).unwrap();
+            }
+        }
+    }
+    encoded
+}
+
+/// Reverses [`percent_encode`].
+///
+/// # Returns
+///
+/// * The hash, or an error for a malformed escape or one that isn't valid UTF-8.
+pub fn percent_decode(component: &str) -> Result<String> {
+    let mut bytes = Vec::with_capacity(component.len());
+    let mut rest = component.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte != b'%' {
+            bytes.push(byte);
+            rest = tail;
+            continue;
+        }
+        let escape = tail
+            .get(..2)
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            .ok_or_else(|| {
+                SerdeHashError::InvalidHash(format!("invalid percent-encoding in '{}'", component))
+            })?;
+        bytes.push(escape);
+        rest = &tail[2..];
+    }
+    String::from_utf8(bytes).map_err(|_| {
+        SerdeHashError::InvalidHash(format!("'{}' doesn't decode to UTF-8", component))
+    })
+}
+
+/// Encodes `data` into a hash that can be used as a URL path segment or query value.
+pub fn encode_url_component(data: u64) -> String {
+    percent_encode(&encode_single(data))
+}
+
+/// Decodes a path segment or query value written by [`encode_url_component`].
+pub fn decode_url_component(component: impl AsRef<str>) -> Result<u64> {
+    decode_single(percent_decode(component.as_ref())?)
+}
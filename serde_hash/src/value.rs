@@ -0,0 +1,108 @@
+//! Hashing ids inside `serde_json::Value` payloads by key pattern.
+//!
+//! Legacy endpoints often pass through JSON that was never given a Rust type, so there is
+//! no field to annotate with `#[serde(hash)]`. [`encode_ids`] and [`decode_ids`] instead
+//! rewrite every value whose key matches the active options' field patterns (see
+//! [`SerdeHashOptions::with_field_patterns`](crate::hashids::SerdeHashOptions::with_field_patterns)),
+//! at any depth of the payload.
+//!
+//! A matching key may hold an id, an array of ids (nested to any depth) or `null`.
+//!
+//! # Example
+//! ```
+//! use serde_hash::hashids::SerdeHashOptions;
+//! use serde_json::json;
+//!
+//! SerdeHashOptions::new()
+//!     .with_salt("replace-with-a-random-salt")
+//!     .with_field_patterns(["id", "*_id"])
+//!     .build();
+//!
+//! let mut payload = json!({ "id": 1, "owner_id": 2, "items": [{ "id": 3, "count": 4 }] });
+//! serde_hash::value::encode_ids(&mut payload).unwrap();
+//! assert!(payload["owner_id"].is_string());
+//! assert_eq!(payload["items"][0]["count"], 4);
+//!
+//! serde_hash::value::decode_ids(&mut payload).unwrap();
+//! assert_eq!(payload, json!({ "id": 1, "owner_id": 2, "items": [{ "id": 3, "count": 4 }] }));
+//! ```
+
+use crate::error::{Result, SerdeHashError};
+use crate::hashids::current_encoder;
+use crate::patterns::FieldPatterns;
+use crate::serde_impl::decode_field;
+use serde_json::Value;
+
+/// Replaces every id under a matching key with its hash.
+///
+/// # Arguments
+///
+/// * `value` - The payload to rewrite in place.
+///
+/// # Returns
+///
+/// An error if a matching key holds something other than an unsigned integer, an array of
+/// them or `null`. The payload may be partially rewritten when an error is returned.
+pub fn encode_ids(value: &mut Value) -> Result<()> {
+    let encoder = current_encoder();
+    rewrite(value, encoder.field_patterns(), &|id| match id {
+        Value::Number(number) => match number.as_u64() {
+            Some(number) => Ok(Value::from(encoder.encode_single(number))),
+            None => Err(SerdeHashError::InvalidValue(format!(
+                "expected an unsigned integer id, found {}",
+                number
+            ))),
+        },
+        other => Err(SerdeHashError::InvalidValue(format!("expected an id, found {}", other))),
+    })
+}
+
+/// Replaces every hash under a matching key with its numeric id, undoing [`encode_ids`].
+///
+/// # Arguments
+///
+/// * `value` - The payload to rewrite in place.
+///
+/// # Returns
+///
+/// An error if a matching key holds something other than a hash, an array of them or
+/// `null`, or if a hash fails to decode. The payload may be partially rewritten when an
+/// error is returned.
+pub fn decode_ids(value: &mut Value) -> Result<()> {
+    let encoder = current_encoder();
+    rewrite(value, encoder.field_patterns(), &|hash| match hash {
+        Value::String(hash) => Ok(Value::from(decode_field(hash)?)),
+        other => Err(SerdeHashError::InvalidHash(format!("expected a hash, found {}", other))),
+    })
+}
+
+/// Searches `value` for matching keys and rewrites the ids below them with `id`.
+pub(crate) fn rewrite(
+    value: &mut Value,
+    patterns: &FieldPatterns,
+    id: &impl Fn(&Value) -> Result<Value>,
+) -> Result<()> {
+    match value {
+        Value::Object(map) => map.iter_mut().try_for_each(|(key, child)| {
+            if patterns.matches(key) {
+                rewrite_ids(child, id).map_err(|e| e.context(format!("in field '{}'", key)))
+            } else {
+                rewrite(child, patterns, id)
+            }
+        }),
+        Value::Array(values) => values.iter_mut().try_for_each(|v| rewrite(v, patterns, id)),
+        _ => Ok(()),
+    }
+}
+
+/// Rewrites an id, or every id in a (nested) array.
+fn rewrite_ids(value: &mut Value, id: &impl Fn(&Value) -> Result<Value>) -> Result<()> {
+    match value {
+        Value::Array(values) => values.iter_mut().try_for_each(|v| rewrite_ids(v, id)),
+        Value::Null => Ok(()),
+        other => {
+            *other = id(other)?;
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,71 @@
+mod test_alphabet {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::alphabet::{Alphabet, validate_alphabet};
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::serde_hash;
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TestData {
+        #[serde(hash)]
+        pub id: u64,
+    }
+
+    #[test]
+    fn test_presets_are_valid() {
+        for preset in [
+            Alphabet::Base62,
+            Alphabet::Base58NoLookalikes,
+            Alphabet::LowercaseHex,
+            Alphabet::UrlSafe,
+        ] {
+            assert_eq!(validate_alphabet(preset.as_str()), Ok(()), "{:?}", preset);
+        }
+    }
+
+    #[cfg(feature = "alphabet-macro")]
+    #[test]
+    fn test_alphabet_macro() {
+        const CROCKFORD: &Alphabet = serde_hash::alphabet!("0123456789ABCDEFGHJKMNPQRSTVWXYZ");
+        assert_eq!(*CROCKFORD, Alphabet::Custom("0123456789ABCDEFGHJKMNPQRSTVWXYZ"));
+        assert_eq!(validate_alphabet(CROCKFORD.as_str()), Ok(()));
+
+        let options = SerdeHashOptions::new().with_alphabet(CROCKFORD);
+        assert_eq!(options.alphabet(), CROCKFORD.as_str());
+        assert_eq!(options.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_invalid_alphabets() {
+        assert_eq!(
+            validate_alphabet("abcdef"),
+            Err(SerdeHashError::AlphabetTooShort { unique: 6 })
+        );
+        assert_eq!(
+            validate_alphabet("abcdefghijklmnopa"),
+            Err(SerdeHashError::AlphabetDuplicate('a'))
+        );
+        assert_eq!(
+            validate_alphabet("abcdefghijklmnop "),
+            Err(SerdeHashError::AlphabetContainsSpace)
+        );
+        let result = SerdeHashOptions::new().with_alphabet("aabbcc").try_build();
+        assert_eq!(result, Err(SerdeHashError::AlphabetDuplicate('a')));
+    }
+
+    #[test]
+    fn test_hex_preset_round_trip() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_alphabet_preset(Alphabet::LowercaseHex)
+            .build();
+        let data = TestData { id: 158674 };
+        let json = serde_json::to_string(&data).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(value["id"].as_str().unwrap().chars().all(|c| c.is_ascii_hexdigit()));
+        let deserialized: TestData = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, data);
+    }
+}
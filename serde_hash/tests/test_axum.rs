@@ -0,0 +1,110 @@
+#![cfg(all(feature = "axum", feature = "cursor"))]
+
+// The example doubles as the test fixture, so it can't drift away from the crate.
+#[allow(dead_code)]
+#[path = "../../examples/axum_crud.rs"]
+mod axum_crud;
+
+mod test_axum {
+    use super::axum_crud::{Item, Page, Store, app};
+    use axum::Router;
+    use axum::body::{Body, to_bytes};
+    use axum::http::{Method, Request, StatusCode};
+    use serde::de::DeserializeOwned;
+    use serde_hash::hashids::{SerdeHashOptions, encode_single};
+    use tower::ServiceExt;
+
+    fn setup() -> Router {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        app(Store::default())
+    }
+
+    async fn send(
+        app: &Router,
+        method: Method,
+        uri: &str,
+        body: Option<&str>,
+    ) -> (StatusCode, Vec<u8>) {
+        let request = Request::builder().method(method).uri(uri);
+        let request = match body {
+            Some(body) => request
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        };
+        let response = app.clone().oneshot(request.unwrap()).await.unwrap();
+        let status = response.status();
+        (status, to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec())
+    }
+
+    fn json<T: DeserializeOwned>(body: &[u8]) -> T {
+        serde_json::from_slice(body).unwrap()
+    }
+
+    async fn create(app: &Router, name: &str) -> Item {
+        let body = format!(r#"{{"name":"{}"}}"#, name);
+        let (status, body) = send(app, Method::POST, "/items", Some(&body)).await;
+        assert_eq!(status, StatusCode::CREATED);
+        json(&body)
+    }
+
+    #[tokio::test]
+    async fn test_crud_with_hashed_ids() {
+        let app = setup();
+        let item = create(&app, "first").await;
+        assert_eq!(item.id, 1);
+
+        let uri = format!("/items/{}", encode_single(1));
+        let (status, body) = send(&app, Method::GET, &uri, None).await;
+        assert_eq!(status, StatusCode::OK);
+        let text = String::from_utf8(body.clone()).unwrap();
+        assert_eq!(text, r#"{"id":"mP5XbpEaQ9","name":"first"}"#);
+        assert_eq!(json::<Item>(&body), item);
+
+        let (status, body) = send(&app, Method::PUT, &uri, Some(r#"{"name":"renamed"}"#)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json::<Item>(&body).name, "renamed");
+
+        let (status, _) = send(&app, Method::DELETE, &uri, None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = send(&app, Method::GET, &uri, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_hash_is_not_found() {
+        let app = setup();
+        create(&app, "first").await;
+        for uri in ["/items/invalid", "/items/1"] {
+            let (status, body) = send(&app, Method::GET, uri, None).await;
+            assert_eq!(status, StatusCode::NOT_FOUND, "{}", uri);
+            assert!(body.is_empty(), "{}", uri);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_pages_with_cursors() {
+        let app = setup();
+        for name in ["a", "b", "c"] {
+            create(&app, name).await;
+        }
+        let mut uri = "/items?limit=2".to_string();
+        let mut names = Vec::new();
+        loop {
+            let (status, body) = send(&app, Method::GET, &uri, None).await;
+            assert_eq!(status, StatusCode::OK);
+            let page: Page = json(&body);
+            names.extend(page.items.into_iter().map(|item| item.name));
+            match page.next {
+                Some(cursor) => uri = format!("/items?limit=2&cursor={}", cursor),
+                None => break,
+            }
+        }
+        assert_eq!(names, ["a", "b", "c"]);
+    }
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "json")]
+
+mod test_bulk {
+    use serde_hash::bulk::{BulkDecoder, BulkFormat, BulkStats};
+    use serde_hash::hashids::SerdeHashOptions;
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    fn decode(decoder: &BulkDecoder, format: BulkFormat, input: &str) -> (String, BulkStats) {
+        let mut output = Vec::new();
+        let stats = decoder.decode(format, input.as_bytes(), &mut output).unwrap();
+        (String::from_utf8(output).unwrap(), stats)
+    }
+
+    #[test]
+    fn test_ndjson() {
+        setup();
+        let input = concat!(
+            r#"{"id":"qKknODM7Ej","user":{"owner_id":"qKknODM7Ej"},"note":"qKknODM7Ej"}"#,
+            "\n\n",
+            r#"{"id":null,"tags":[{"tag_id":["qKknODM7Ej"]}]}"#,
+        );
+        let decoder = BulkDecoder::new(["id", "*_id"]).unwrap();
+        let (output, stats) = decode(&decoder, BulkFormat::Ndjson, input);
+        assert_eq!(
+            output,
+            concat!(
+                r#"{"id":158674,"note":"qKknODM7Ej","user":{"owner_id":158674}}"#,
+                "\n\n",
+                r#"{"id":null,"tags":[{"tag_id":[158674]}]}"#,
+                "\n",
+            )
+        );
+        assert_eq!(stats, BulkStats { records: 2, decoded: 3, invalid: 0 });
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        setup();
+        let input = "{\"id\":\"qKknODM7Ej\"}\n{\"id\":\"not a hash\"}\n";
+        let decoder = BulkDecoder::new(["id"]).unwrap();
+        let mut output = Vec::new();
+        let err = decoder.decode(BulkFormat::Ndjson, input.as_bytes(), &mut output).unwrap_err();
+        assert!(format!("{:#}", err).contains("in record 2"), "{:#}", err);
+        // Records before the failing one were written
+        assert_eq!(output, b"{\"id\":158674}\n");
+
+        let (output, stats) =
+            decode(&decoder.with_keep_invalid(true), BulkFormat::Ndjson, input);
+        assert_eq!(output, "{\"id\":158674}\n{\"id\":\"not a hash\"}\n");
+        assert_eq!(stats, BulkStats { records: 2, decoded: 1, invalid: 1 });
+    }
+
+    #[test]
+    fn test_malformed_record() {
+        setup();
+        let decoder = BulkDecoder::new(["id"]).unwrap();
+        let mut output = Vec::new();
+        let err = decoder.decode(BulkFormat::Ndjson, "{\"id\":".as_bytes(), &mut output);
+        assert!(format!("{:#}", err.unwrap_err()).contains("in record 1"));
+        assert!(BulkDecoder::new([""]).is_err());
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn test_csv() {
+        setup();
+        let input = "id,name,friend_ids\nqKknODM7Ej,Dan,qKknODM7Ej qKknODM7Ej\nqKknODM7Ej,\"Smith, Jo\",\n";
+        let decoder = BulkDecoder::new(["id", "*_ids"]).unwrap();
+        let (output, stats) = decode(&decoder, BulkFormat::Csv, input);
+        assert_eq!(
+            output,
+            "id,name,friend_ids\n158674,Dan,158674 158674\n158674,\"Smith, Jo\",\n"
+        );
+        assert_eq!(stats, BulkStats { records: 2, decoded: 4, invalid: 0 });
+    }
+
+    #[test]
+    fn test_files() {
+        setup();
+        let dir = std::env::temp_dir().join(format!("serde_hash_bulk_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (input, output) = (dir.join("in.ndjson"), dir.join("out.ndjson"));
+        std::fs::write(&input, "{\"id\":\"qKknODM7Ej\"}\n").unwrap();
+
+        let decoder = BulkDecoder::new(["id"]).unwrap();
+        let stats = decoder.decode_file(BulkFormat::Ndjson, &input, &output).unwrap();
+        assert_eq!(stats.records, 1);
+        assert_eq!(std::fs::read_to_string(&output).unwrap(), "{\"id\":158674}\n");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,46 @@
+mod test_bytes {
+    use serde_hash::hashids::{SerdeHashOptions, decode_bytes, encode, encode_bytes};
+    use std::sync::Once;
+
+    static INIT: Once = Once::new();
+
+    fn init() {
+        INIT.call_once(|| {
+            SerdeHashOptions::new()
+                .with_salt("hello world")
+                .with_weak_salt_allowed(true)
+                .with_min_length(10)
+                .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+                .build();
+        });
+    }
+
+    #[test]
+    fn test_round_trip() {
+        init();
+        let inputs: [&[u8]; 6] = [
+            b"",
+            b"\0",
+            b"\0\0\x01",
+            b"nonce",
+            &[0xff; 8],
+            b"a 32-byte digest is still short!",
+        ];
+        for bytes in inputs {
+            let hash = encode_bytes(bytes);
+            assert_eq!(decode_bytes(&hash).unwrap(), bytes, "round trip of {:?}", bytes);
+        }
+        assert_ne!(encode_bytes(b"\0"), encode_bytes(b"\0\0"));
+    }
+
+    #[test]
+    fn test_rejects_other_hashes() {
+        init();
+        // A length without its limbs, too many limbs, and nonzero padding.
+        assert!(decode_bytes(encode(&[3])).is_err());
+        assert!(decode_bytes(encode(&[1, 0, 0])).is_err());
+        assert!(decode_bytes(encode(&[1, 0x0001_0000_0000_0000])).is_err());
+        assert!(decode_bytes(encode(&[u64::MAX, 1])).is_err());
+        assert!(decode_bytes("not-a-hash").is_err());
+    }
+}
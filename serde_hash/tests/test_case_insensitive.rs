@@ -0,0 +1,45 @@
+mod test_case_insensitive {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::alphabet::Alphabet;
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::hashids::{SerdeHashOptions, decode_single, encode_single};
+    use serde_hash::serde_hash;
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Invoice {
+        #[serde(hash)]
+        pub id: u64,
+    }
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet_preset(Alphabet::LowercaseHex)
+            .with_case_insensitive_decode(true)
+            .build();
+    }
+
+    #[test]
+    fn test_decode_ignores_case() {
+        setup();
+        let hash = encode_single(158674);
+        assert_eq!(hash, hash.to_lowercase());
+        assert_eq!(decode_single(hash.to_uppercase()).unwrap(), 158674);
+
+        let json = format!(r#"{{"id":"{}"}}"#, hash.to_uppercase());
+        let invoice: Invoice = serde_json::from_str(&json).unwrap();
+        assert_eq!(invoice.id, 158674);
+    }
+
+    #[test]
+    fn test_rejects_mixed_case_alphabet() {
+        let result = SerdeHashOptions::new()
+            .with_alphabet_preset(Alphabet::Base62)
+            .with_case_insensitive_decode(true)
+            .validate();
+        assert_eq!(result, Err(SerdeHashError::AlphabetCaseSensitive('a')));
+    }
+}
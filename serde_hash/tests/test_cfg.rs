@@ -0,0 +1,65 @@
+mod test_cfg {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::{HashIds, HashedFields, serde_hash};
+
+    // `all()` always holds and `any()` never does, standing in for a feature such as
+    // `feature = "public-api"` being enabled or not.
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TestData {
+        #[hash(cfg(all()))]
+        pub id: u64,
+        #[hash(cfg(any()))]
+        pub internal_id: u64,
+        #[hash(cfg(any()), range = "1..=1000")]
+        pub group_id: Option<u64>,
+        #[hash(cfg(any()), alias_decode = "legacy_ids")]
+        pub ids: Vec<u64>,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestDerive {
+        #[hash(cfg(all()))]
+        pub id: u64,
+        #[hash(cfg(any()), min_length = 16)]
+        pub internal_id: u64,
+    }
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[test]
+    fn test_serde_hash_cfg() {
+        setup();
+        let data = TestData {
+            id: 158674,
+            internal_id: 158674,
+            group_id: Some(5000),
+            ids: vec![1, 2],
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(
+            json,
+            r#"{"id":"qKknODM7Ej","internal_id":158674,"group_id":5000,"ids":[1,2]}"#
+        );
+        assert_eq!(serde_json::from_str::<TestData>(&json).unwrap(), data);
+        assert_eq!(TestData::HASHED_FIELDS, ["id"]);
+    }
+
+    #[test]
+    fn test_derive_cfg() {
+        setup();
+        let data = TestDerive { id: 158674, internal_id: 1 };
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(json, r#"{"id":"qKknODM7Ej","internal_id":1}"#);
+        assert_eq!(serde_json::from_str::<TestDerive>(&json).unwrap(), data);
+        assert_eq!(TestDerive::HASHED_FIELDS, ["id"]);
+    }
+}
@@ -0,0 +1,80 @@
+mod test_checksum {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::encoder::HashIdsEncoder;
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::hashids::{SerdeHashOptions, decode, decode_single, encode, encode_single};
+    use serde_hash::serde_hash;
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Ticket {
+        #[serde(hash)]
+        pub id: u64,
+    }
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_checksum(true)
+            .build();
+    }
+
+    #[test]
+    fn test_check_character_is_appended() {
+        setup();
+        let hash = encode_single(158674);
+        assert_eq!(hash.len(), 11);
+        assert!(hash.starts_with("qKknODM7Ej"));
+        assert_eq!(decode_single(&hash).unwrap(), 158674);
+        assert_eq!(decode(encode(&[1, 2, 3])).unwrap(), vec![1, 2, 3]);
+
+        let json = serde_json::to_string(&Ticket { id: 158674 }).unwrap();
+        assert_eq!(json, format!(r#"{{"id":"{}"}}"#, hash));
+        assert_eq!(serde_json::from_str::<Ticket>(&json).unwrap().id, 158674);
+    }
+
+    #[test]
+    fn test_typos_are_rejected() {
+        setup();
+        let hash = encode_single(158674);
+        let chars: Vec<char> = hash.chars().collect();
+
+        for i in 0..chars.len() {
+            let mut substituted = chars.clone();
+            substituted[i] = if chars[i] == 'a' { 'b' } else { 'a' };
+            let substituted: String = substituted.into_iter().collect();
+            assert!(decode_single(&substituted).is_err(), "{}", substituted);
+        }
+        for i in 0..chars.len() - 1 {
+            if chars[i] == chars[i + 1] {
+                continue;
+            }
+            let mut swapped = chars.clone();
+            swapped.swap(i, i + 1);
+            let swapped: String = swapped.into_iter().collect();
+            assert!(decode_single(&swapped).is_err(), "{}", swapped);
+        }
+        assert!(decode_single(&hash[..hash.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_checksum_with_constant_time_decode() {
+        let encoder = HashIdsEncoder::new(
+            &SerdeHashOptions::new()
+                .with_salt("hello world")
+                .with_checksum(true)
+                .with_constant_time_decode(true),
+        )
+        .unwrap();
+        let hash = encoder.encode_single(42);
+        assert_eq!(encoder.decode_single(&hash).unwrap(), 42);
+
+        let mut tampered = hash.clone();
+        let last = tampered.pop().unwrap();
+        tampered.push(if last == 'a' { 'b' } else { 'a' });
+        let error = encoder.decode_single(&tampered).unwrap_err();
+        assert_eq!(error, SerdeHashError::InvalidHash(tampered));
+    }
+}
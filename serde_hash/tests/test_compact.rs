@@ -0,0 +1,81 @@
+mod test_compact {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::hashids::SerdeHashOptions;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Page {
+        #[serde(with = "serde_hash::serde_impl::vec_numeric_compact")]
+        pub ids: Vec<u64>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct PlainPage {
+        #[serde(with = "serde_hash::serde_impl::vec_numeric")]
+        pub ids: Vec<u64>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct SmallPage {
+        #[serde(with = "serde_hash::serde_impl::vec_numeric_compact")]
+        pub ids: Vec<u16>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct WidePage {
+        #[serde(with = "serde_hash::serde_impl::vec_numeric_compact")]
+        pub ids: Vec<u128>,
+    }
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[test]
+    fn test_sequential_ids_shrink() {
+        setup();
+        let ids: Vec<u64> = (158_000..160_000).filter(|id| id % 7 != 0).collect();
+        let compact = serde_json::to_string(&Page { ids: ids.clone() }).unwrap();
+        let plain = serde_json::to_string(&PlainPage { ids: ids.clone() }).unwrap();
+        assert!(compact.len() * 5 < plain.len(), "{} vs {}", compact.len(), plain.len());
+
+        let value: serde_json::Value = serde_json::from_str(&compact).unwrap();
+        assert!(value["ids"].is_string());
+        assert_eq!(serde_json::from_str::<Page>(&compact).unwrap().ids, ids);
+    }
+
+    #[test]
+    fn test_order_and_jumps_are_kept() {
+        setup();
+        for ids in [vec![], vec![0], vec![5, 3, 3, 9, u64::MAX, 0, u64::MAX - 1, 1]] {
+            let page = Page { ids };
+            let json = serde_json::to_string(&page).unwrap();
+            assert_eq!(serde_json::from_str::<Page>(&json).unwrap(), page, "{}", json);
+        }
+    }
+
+    #[test]
+    fn test_values_must_fit() {
+        setup();
+        let json = serde_json::to_string(&Page { ids: vec![1, 70000] }).unwrap();
+        let error = serde_json::from_str::<SmallPage>(&json).unwrap_err();
+        assert!(error.to_string().contains("70000"), "{}", error);
+
+        let page = SmallPage { ids: vec![1, 2, u16::MAX] };
+        let json = serde_json::to_string(&page).unwrap();
+        assert_eq!(serde_json::from_str::<SmallPage>(&json).unwrap(), page);
+
+        assert!(serde_json::to_string(&WidePage { ids: vec![1, u128::MAX] }).is_err());
+    }
+
+    #[test]
+    fn test_invalid_hash_is_rejected() {
+        setup();
+        assert!(serde_json::from_str::<Page>(r#"{"ids":"invalid"}"#).is_err());
+        assert!(serde_json::from_str::<Page>(r#"{"ids":[1,2]}"#).is_err());
+    }
+}
@@ -0,0 +1,58 @@
+mod test_concurrency {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::hashids::{SerdeHashOptions, current_encoder, encode_single_with_min_length};
+    use serde_hash::serde_hash;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    const THREADS: usize = 16;
+    const ITERATIONS: u64 = 2_000;
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct User {
+        #[serde(hash)]
+        pub id: u64,
+        #[hash(min_length = 16)]
+        pub group: u64,
+    }
+
+    /// Every thread races to build the options and then hammers the cached encoders, so
+    /// the first-time initialization of the options and of both encoder caches overlaps
+    /// with reads from other threads.
+    #[test]
+    fn test_concurrent_build_and_use() {
+        let barrier = Arc::new(Barrier::new(THREADS));
+        let handles: Vec<_> = (0..THREADS)
+            .map(|thread| {
+                let barrier = barrier.clone();
+                thread::spawn(move || {
+                    barrier.wait();
+                    // Only the first call wins; the others race it and must be ignored.
+                    SerdeHashOptions::new()
+                        .with_salt(if thread == 0 { "hello world" } else { "other salt" })
+                        .with_weak_salt_allowed(true)
+                        .with_min_length(10)
+                        .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+                        .build();
+
+                    let encoder = current_encoder();
+                    let mut hashes = Vec::new();
+                    for i in 0..ITERATIONS {
+                        let user = User { id: i, group: i * 7 };
+                        let json = serde_json::to_string(&user).unwrap();
+                        assert_eq!(serde_json::from_str::<User>(&json).unwrap(), user);
+                        assert!(Arc::ptr_eq(&encoder, &current_encoder()));
+                        if i % 500 == 0 {
+                            hashes.push((encoder.encode_single(i), encode_single_with_min_length(i, 16)));
+                        }
+                    }
+                    hashes
+                })
+            })
+            .collect();
+
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert!(results.windows(2).all(|pair| pair[0] == pair[1]));
+    }
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "hmac")]
+mod test_consistency {
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::{consistency_token, verify_consistency_token};
+
+    fn setup() {
+        options().build();
+    }
+
+    fn options() -> SerdeHashOptions {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+    }
+
+    #[test]
+    fn test_token_is_stable() {
+        setup();
+        let token = consistency_token();
+        assert_eq!(token, options().consistency_token());
+        // Other processes and releases compute the same token from the same options.
+        assert_eq!(token, "v1-4b13237fef78212cf305104452669b9f");
+        assert!(!token.contains("hello"));
+        assert!(verify_consistency_token(&token).is_ok());
+    }
+
+    #[test]
+    fn test_settings_that_change_hashes_change_the_token() {
+        let token = options().consistency_token();
+        let drifted = [
+            options().with_salt("hello world!"),
+            options().with_min_length(11),
+            options().with_alphabet("abcdefghijklmnopqrstuvwxyz1234567890"),
+            options().with_js_compat(true),
+            options().with_checksum(true),
+            options().with_exact_length(12),
+            options().with_case_insensitive_decode(true),
+            options().with_crockford_decode(true),
+            options().with_msgpack_ext(7),
+            options().with_field_patterns(["*_id"]),
+        ];
+        for options in drifted {
+            assert_ne!(options.consistency_token(), token, "{:?}", options);
+        }
+        assert_eq!(options().with_max_length(20).consistency_token(), token);
+        assert_eq!(options().with_reject_zero(true).consistency_token(), token);
+    }
+
+    #[test]
+    fn test_exact_length_replaces_min_length() {
+        let exact = options().with_exact_length(12).consistency_token();
+        assert_eq!(options().with_min_length(4).with_exact_length(12).consistency_token(), exact);
+        assert_ne!(options().with_exact_length(13).consistency_token(), exact);
+        // A minimum and an exact length of the same size give different hashes.
+        assert_ne!(options().with_min_length(12).consistency_token(), exact);
+    }
+
+    #[test]
+    fn test_field_patterns_are_compared_as_a_set() {
+        let token = options().with_field_patterns(["*_id", "id"]).consistency_token();
+        assert_eq!(options().with_field_patterns(["id", "*_id"]).consistency_token(), token);
+        assert_ne!(options().with_field_patterns(["*_id"]).consistency_token(), token);
+        assert_ne!(options().with_field_patterns(["*_id,id"]).consistency_token(), token);
+    }
+
+    #[test]
+    fn test_mismatch_names_both_tokens() {
+        setup();
+        let other = options().with_min_length(11).consistency_token();
+        assert_eq!(
+            verify_consistency_token(&other),
+            Err(SerdeHashError::ConsistencyMismatch {
+                expected: other,
+                actual: consistency_token(),
+            })
+        );
+    }
+}
@@ -0,0 +1,49 @@
+mod test_constant_time {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::hashids::{SerdeHashOptions, decode, decode_single, encode, encode_single};
+    use serde_hash::serde_hash;
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct User {
+        #[serde(hash)]
+        pub id: u64,
+        #[hash(min_length = 16)]
+        pub group: u64,
+    }
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_constant_time_decode(true)
+            .build();
+    }
+
+    #[test]
+    fn test_valid_hashes_decode() {
+        setup();
+        assert_eq!(decode_single(encode_single(158674)).unwrap(), 158674);
+        assert_eq!(decode(encode(&[1, 2, 3])).unwrap(), vec![1, 2, 3]);
+
+        let user = User { id: 7, group: 8 };
+        let json = serde_json::to_string(&user).unwrap();
+        assert_eq!(serde_json::from_str::<User>(&json).unwrap(), user);
+    }
+
+    #[test]
+    fn test_invalid_hashes_share_one_error() {
+        setup();
+        let hash = encode_single(158674);
+        let mut chars: Vec<char> = hash.chars().collect();
+        chars.swap(0, 1);
+        let tampered: String = chars.into_iter().collect();
+
+        for input in [tampered.as_str(), &hash[1..], &format!("{}-", hash), ""] {
+            let error = decode(input).unwrap_err();
+            assert_eq!(error, SerdeHashError::InvalidHash(input.to_string()));
+        }
+    }
+}
@@ -0,0 +1,51 @@
+mod test_crockford {
+    use serde_hash::alphabet::Alphabet;
+    use serde_hash::encoder::HashIdsEncoder;
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::hashids::SerdeHashOptions;
+
+    fn setup() -> HashIdsEncoder {
+        let options = SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet_preset(Alphabet::Crockford32);
+        assert!(options.crockford_decode);
+        HashIdsEncoder::new(&options).unwrap()
+    }
+
+    /// Writes a hash the way a person might copy it from a receipt.
+    fn misread(hash: &str) -> String {
+        let mut written = String::new();
+        for (i, c) in hash.chars().enumerate() {
+            if i > 0 && i % 4 == 0 {
+                written.push('-');
+            }
+            written.push(match c {
+                '0' => 'o',
+                '1' => 'l',
+                c => c.to_ascii_lowercase(),
+            });
+        }
+        written
+    }
+
+    #[test]
+    fn test_decode_maps_ambiguous_characters() {
+        let encoder = setup();
+        for value in [0, 1, 10, 158674, u64::MAX] {
+            let hash = encoder.encode(&[value]);
+            assert!(hash.chars().all(|c| Alphabet::Crockford32.as_str().contains(c)), "{}", hash);
+            assert_eq!(encoder.decode(misread(&hash)).unwrap(), [value], "{}", hash);
+        }
+    }
+
+    #[test]
+    fn test_rejects_alphabet_with_mapped_characters() {
+        let result = SerdeHashOptions::new()
+            .with_alphabet_preset(Alphabet::Base58NoLookalikes)
+            .with_crockford_decode(true)
+            .validate();
+        assert_eq!(result, Err(SerdeHashError::AlphabetNotCrockford('L')));
+    }
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "csv")]
+
+mod test_csv {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::{HashIds, serde_hash};
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Export {
+        #[serde(hash)]
+        pub id: u64,
+        pub name: String,
+        #[serde(hash)]
+        pub manager: Option<u32>,
+        #[serde(with = "serde_hash::csv::vec_numeric")]
+        pub reports: Vec<u64>,
+        #[serde(with = "serde_hash::csv::option_vec_numeric")]
+        pub groups: Option<Vec<u16>>,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct LegacyExport {
+        #[hash]
+        pub id: u64,
+        pub name: String,
+        #[hash]
+        pub manager: Option<u32>,
+    }
+
+    #[test]
+    fn test_round_trip_with_headers() {
+        setup();
+        let rows = vec![
+            Export {
+                id: 158674,
+                name: "Dan Smith".to_string(),
+                manager: Some(7),
+                reports: vec![1, 2, 3],
+                groups: Some(vec![4]),
+            },
+            Export {
+                id: 2,
+                name: "Alice".to_string(),
+                manager: None,
+                reports: vec![],
+                groups: None,
+            },
+        ];
+
+        let mut buffer = Vec::new();
+        serde_hash::csv::to_writer(&mut buffer, &rows).unwrap();
+        let text = String::from_utf8(buffer.clone()).unwrap();
+        assert!(text.starts_with("id,name,manager,reports,groups\n"));
+        assert!(text.contains("qKknODM7Ej"));
+
+        let decoded: Vec<Export> = serde_hash::csv::from_reader(buffer.as_slice()).unwrap();
+        assert_eq!(decoded, rows);
+    }
+
+    #[test]
+    fn test_derive_with_headers() {
+        setup();
+        let rows = vec![LegacyExport {
+            id: 158674,
+            name: "Dan Smith".to_string(),
+            manager: None,
+        }];
+
+        let mut buffer = Vec::new();
+        serde_hash::csv::to_writer(&mut buffer, &rows).unwrap();
+        let decoded: Vec<LegacyExport> = serde_hash::csv::from_reader(buffer.as_slice()).unwrap();
+        assert_eq!(decoded, rows);
+    }
+
+    #[test]
+    fn test_derive_without_headers() {
+        setup();
+        let data = "qKknODM7Ej,Dan Smith,\n";
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(data.as_bytes());
+        let decoded: Vec<LegacyExport> = reader.deserialize().collect::<csv::Result<_>>().unwrap();
+        assert_eq!(
+            decoded,
+            vec![LegacyExport {
+                id: 158674,
+                name: "Dan Smith".to_string(),
+                manager: None,
+            }]
+        );
+    }
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "cursor")]
+
+mod test_cursor {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::cursor::{Cursor, Direction};
+    use serde_hash::hashids::{SerdeHashOptions, encode};
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Page {
+        pub items: Vec<String>,
+        pub next: Option<Cursor<u32>>,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        setup();
+        let cursor = Cursor::with_timestamp(158674u64, 1_700_000_000, Direction::Backward);
+        let token = cursor.encode();
+        assert_eq!(Cursor::<u64>::decode(&token).unwrap(), cursor);
+        assert_eq!(token.parse::<Cursor<u64>>().unwrap(), cursor);
+        assert_eq!(cursor.to_string(), token);
+    }
+
+    #[test]
+    fn test_wide_positions() {
+        setup();
+        for position in [1u128 << 64, u128::MAX] {
+            let cursor = Cursor::with_timestamp(position, 7, Direction::Forward);
+            assert_eq!(Cursor::<u128>::decode(cursor.encode()).unwrap(), cursor);
+            assert!(Cursor::<u64>::decode(cursor.encode()).is_err());
+        }
+        // Positions that fit in a u64 keep the three-value layout
+        let cursor = Cursor::with_timestamp(42u128, 7, Direction::Forward);
+        assert_eq!(cursor.encode(), encode(&[42, 7, 0]));
+        assert!(Cursor::<u128>::decode(encode(&[0, 42, 7, 0])).is_err());
+    }
+
+    #[test]
+    fn test_new_uses_current_time() {
+        setup();
+        let cursor = Cursor::forward(5u16);
+        assert_eq!(cursor.direction, Direction::Forward);
+        assert!(cursor.timestamp > 1_700_000_000);
+        assert_eq!(Cursor::backward(5u16).direction, Direction::Backward);
+    }
+
+    #[test]
+    fn test_serde() {
+        setup();
+        let page = Page {
+            items: vec!["a".to_string()],
+            next: Some(Cursor::with_timestamp(42, 7, Direction::Forward)),
+        };
+        let json = serde_json::to_string(&page).unwrap();
+        assert_eq!(
+            json,
+            format!(r#"{{"items":["a"],"next":"{}"}}"#, encode(&[42, 7, 0]))
+        );
+        assert_eq!(serde_json::from_str::<Page>(&json).unwrap(), page);
+    }
+
+    #[test]
+    fn test_invalid_cursors() {
+        setup();
+        assert!(Cursor::<u64>::decode("not a cursor").is_err());
+        assert!(Cursor::<u64>::decode(encode(&[1, 2])).is_err());
+        assert!(Cursor::<u64>::decode(encode(&[1, 2, 2])).is_err());
+        assert!(serde_json::from_str::<Cursor<u64>>(&format!("\"{}\"", encode(&[1]))).is_err());
+    }
+}
@@ -0,0 +1,52 @@
+mod test_decode_hook {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::{HashIds, serde_hash};
+    use std::sync::Mutex;
+
+    static REJECTED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    fn record(hash: &str, error: &SerdeHashError) {
+        assert!(matches!(error, SerdeHashError::InvalidHash(_)));
+        REJECTED.lock().unwrap().push(hash.to_string());
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct User {
+        #[serde(hash)]
+        pub id: u64,
+        #[serde(hash)]
+        pub friends: Vec<u64>,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct LegacyUser {
+        #[hash]
+        pub id: u64,
+    }
+
+    #[test]
+    fn test_hook_sees_failed_decodes() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .build();
+        serde_hash::set_decode_error_hook(record);
+
+        let valid = r#"{"id":"qKknODM7Ej","friends":[]}"#;
+        assert!(serde_json::from_str::<User>(valid).is_ok());
+        assert!(REJECTED.lock().unwrap().is_empty());
+
+        let json = r#"{"id":"qKknODM7Ej","friends":["not-a-hash"]}"#;
+        assert!(serde_json::from_str::<User>(json).is_err());
+        assert!(serde_json::from_str::<LegacyUser>(r#"{"id":"guess"}"#).is_err());
+        assert_eq!(*REJECTED.lock().unwrap(), vec!["not-a-hash", "guess"]);
+
+        serde_hash::hook::clear_decode_error_hook();
+        assert!(serde_json::from_str::<LegacyUser>(r#"{"id":"again"}"#).is_err());
+        assert_eq!(REJECTED.lock().unwrap().len(), 2);
+    }
+}
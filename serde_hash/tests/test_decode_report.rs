@@ -0,0 +1,38 @@
+mod test_decode_report {
+    use serde_hash::encoder::{DecodeBackend, HashIdsEncoder};
+    use serde_hash::hashids::SerdeHashOptions;
+
+    fn options() -> SerdeHashOptions {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+    }
+
+    #[test]
+    fn test_report() {
+        let encoder = HashIdsEncoder::new(&options()).unwrap();
+        let (value, report) = encoder.decode_single_with_report("qKknODM7Ej").unwrap();
+        assert_eq!(value, 158674);
+        assert_eq!(report.backend, DecodeBackend::Hashids);
+        assert_eq!(report.length, 10);
+        assert!(encoder.decode_single_with_report("invalid").is_err());
+    }
+
+    #[test]
+    fn test_report_names_the_backend() {
+        let encoder = HashIdsEncoder::new(&options().with_js_compat(true)).unwrap();
+        let hash = encoder.encode_single(158674);
+        let (value, report) = encoder.decode_single_with_report(&hash).unwrap();
+        assert_eq!(value, 158674);
+        assert_eq!(report.backend, DecodeBackend::HashidsJs);
+    }
+
+    #[test]
+    fn test_length_counts_the_check_character() {
+        let encoder = HashIdsEncoder::new(&options().with_checksum(true)).unwrap();
+        let hash = encoder.encode_single(158674);
+        let (_, report) = encoder.decode_single_with_report(&hash).unwrap();
+        assert_eq!(report.length, 11);
+    }
+}
@@ -0,0 +1,40 @@
+mod test_display {
+    use serde::Serialize;
+    use serde_hash::HashDisplay;
+    use serde_hash::hashids::SerdeHashOptions;
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[derive(Serialize)]
+    struct Row {
+        id: HashDisplay,
+        name: &'static str,
+    }
+
+    #[test]
+    fn test_display() {
+        setup();
+        assert_eq!(HashDisplay(158674).to_string(), "qKknODM7Ej");
+        assert_eq!(
+            format!("<a href=\"/users/{}\">", HashDisplay::from(158674)),
+            "<a href=\"/users/qKknODM7Ej\">"
+        );
+    }
+
+    #[test]
+    fn test_serialize() {
+        setup();
+        let row = Row { id: HashDisplay(158674), name: "Dan" };
+        assert_eq!(
+            serde_json::to_string(&row).unwrap(),
+            r#"{"id":"qKknODM7Ej","name":"Dan"}"#
+        );
+    }
+}
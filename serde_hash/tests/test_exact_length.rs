@@ -0,0 +1,54 @@
+mod test_exact_length {
+    use serde_hash::encoder::HashIdsEncoder;
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::hashids::SerdeHashOptions;
+
+    fn options() -> SerdeHashOptions {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+    }
+
+    #[test]
+    fn test_every_single_value_has_the_exact_length() {
+        for options in [
+            options().with_exact_length(16),
+            options().with_exact_length(16).with_checksum(true),
+        ] {
+            let encoder = HashIdsEncoder::new(&options).unwrap();
+            for value in [0, 1, 158674, u32::MAX as u64, u64::MAX] {
+                let hash = encoder.encode_single(value);
+                assert_eq!(hash.len(), 16, "{}", hash);
+                assert_eq!(encoder.decode_single(&hash).unwrap(), value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rejects_lengths_that_cannot_fit() {
+        let error = HashIdsEncoder::new(&options().with_exact_length(8)).unwrap_err();
+        let SerdeHashError::ExactLengthTooShort { length: 8, required } = error else {
+            panic!("unexpected error {:?}", error);
+        };
+        assert!(required > 8);
+        assert!(HashIdsEncoder::new(&options().with_exact_length(required)).is_ok());
+        assert!(
+            HashIdsEncoder::new(&options().with_exact_length(required).with_checksum(true))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_try_encode_reports_long_hashes() {
+        let encoder = HashIdsEncoder::new(&options().with_exact_length(14)).unwrap();
+        assert_eq!(encoder.try_encode(&[1, 2]).unwrap().len(), 14);
+
+        let long = [u64::MAX, u64::MAX];
+        let error = encoder.try_encode(&long).unwrap_err();
+        assert!(matches!(error, SerdeHashError::ExactLengthExceeded { exact: 14, .. }));
+        // `encode` still returns the hash, so it keeps decoding.
+        assert_eq!(encoder.decode(encoder.encode(&long)).unwrap(), long);
+    }
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "expiry")]
+
+mod test_expiry {
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::expiry::{
+        decode_with_expiry, decode_with_expiry_at, encode_with_expiry, encode_with_expiry_at,
+    };
+    use serde_hash::hashids::SerdeHashOptions;
+    use std::time::Duration;
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[test]
+    fn test_round_trip_before_expiry() {
+        setup();
+        let token = encode_with_expiry(158674, Duration::from_secs(60));
+        assert_eq!(decode_with_expiry(&token).unwrap(), 158674);
+
+        let token = encode_with_expiry(7, Duration::MAX);
+        assert_eq!(decode_with_expiry(&token).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_rejects_expired_hashes() {
+        setup();
+        let token = encode_with_expiry_at(158674, 1_700_000_000);
+        assert_eq!(decode_with_expiry_at(&token, 1_700_000_000).unwrap(), 158674);
+
+        let error = decode_with_expiry_at(&token, 1_700_000_001).unwrap_err();
+        assert_eq!(error, SerdeHashError::Expired { expires_at: 1_700_000_000 });
+        assert!(decode_with_expiry(&token).is_err());
+    }
+
+    #[test]
+    fn test_rejects_other_hashes() {
+        setup();
+        assert!(decode_with_expiry("qKknODM7Ej").is_err());
+        assert!(decode_with_expiry(serde_hash::hashids::encode(&[1, 2, 3])).is_err());
+        assert!(decode_with_expiry("!!").is_err());
+    }
+}
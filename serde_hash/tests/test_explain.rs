@@ -0,0 +1,56 @@
+mod test_explain {
+    use serde_hash::hashids::SerdeHashOptions;
+
+    fn init() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[test]
+    fn test_explain_valid_hash() {
+        init();
+        let explanation = serde_hash::explain("qKknODM7Ej");
+        assert!(explanation.is_valid());
+        assert!(explanation.matches_alphabet());
+        assert!(explanation.meets_min_length());
+        assert_eq!(explanation.value_count(), Some(1));
+        assert_eq!(explanation.values, Some(vec![158674]));
+        assert!(explanation.to_string().contains("1 value(s) [158674]"));
+    }
+
+    #[test]
+    fn test_explain_invalid_hash() {
+        init();
+        let explanation = serde_hash::explain("qKk-n!-");
+        assert!(!explanation.is_valid());
+        assert_eq!(explanation.invalid_chars, ['-', '!']);
+        assert!(!explanation.meets_min_length());
+        assert_eq!(explanation.length, 7);
+        assert_eq!(explanation.min_length, 10);
+        assert!(explanation.error.is_some());
+
+        let report = explanation.to_string();
+        assert!(report.contains("invalid characters ['-', '!']"), "{}", report);
+        assert!(report.contains("too short"), "{}", report);
+        assert!(report.contains("failed"), "{}", report);
+    }
+
+    #[test]
+    fn test_explain_wrong_salt() {
+        init();
+        let other = SerdeHashOptions::new()
+            .with_salt("another salt")
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890");
+        let hash = serde_hash::hashids::encode_with(&other, &[158674]).unwrap();
+        let explanation = serde_hash::explain(&hash);
+        // Looks fine on the surface, but doesn't decode the same way.
+        assert!(explanation.matches_alphabet());
+        assert!(explanation.meets_min_length());
+        assert_ne!(explanation.values, Some(vec![158674]));
+    }
+}
@@ -0,0 +1,310 @@
+mod test_formats {
+    use serde::de::DeserializeOwned;
+    use serde::{Deserialize, Serialize};
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::{HashIds, serde_hash};
+    use std::collections::BTreeMap;
+    use std::fmt::Debug;
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct User {
+        #[serde(hash)]
+        pub id: u64,
+        pub name: String,
+        #[serde(hash)]
+        pub friends: Vec<u32>,
+        #[serde(hash)]
+        pub manager: Option<u64>,
+        #[serde(hash)]
+        pub groups: Option<Vec<u16>>,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct LegacyUser {
+        #[hash]
+        pub id: u64,
+        pub name: String,
+        #[hash]
+        pub friends: Vec<u32>,
+        #[hash]
+        pub manager: Option<u64>,
+        #[hash]
+        pub groups: Option<Vec<u16>>,
+    }
+
+    fn user(manager: Option<u64>) -> User {
+        User {
+            id: 158674,
+            name: "Dan Smith".to_string(),
+            friends: vec![1, 2],
+            manager,
+            groups: manager.map(|_| vec![3]),
+        }
+    }
+
+    fn legacy_user(manager: Option<u64>) -> LegacyUser {
+        LegacyUser {
+            id: 158674,
+            name: "Dan Smith".to_string(),
+            friends: vec![1, 2],
+            manager,
+            groups: manager.map(|_| vec![3]),
+        }
+    }
+
+    /// A format name and a function that round trips a value through it.
+    type Format<T> = (&'static str, fn(&T) -> T);
+
+    fn json<T: Serialize + DeserializeOwned>(value: &T) -> T {
+        serde_json::from_slice(&serde_json::to_vec(value).unwrap()).unwrap()
+    }
+
+    fn yaml<T: Serialize + DeserializeOwned>(value: &T) -> T {
+        serde_yaml::from_str(&serde_yaml::to_string(value).unwrap()).unwrap()
+    }
+
+    fn toml<T: Serialize + DeserializeOwned>(value: &T) -> T {
+        toml::from_str(&toml::to_string(value).unwrap()).unwrap()
+    }
+
+    fn cbor<T: Serialize + DeserializeOwned>(value: &T) -> T {
+        let mut buffer = Vec::new();
+        ciborium::into_writer(value, &mut buffer).unwrap();
+        ciborium::from_reader(buffer.as_slice()).unwrap()
+    }
+
+    fn msgpack<T: Serialize + DeserializeOwned>(value: &T) -> T {
+        rmp_serde::from_slice(&rmp_serde::to_vec(value).unwrap()).unwrap()
+    }
+
+    fn msgpack_named<T: Serialize + DeserializeOwned>(value: &T) -> T {
+        rmp_serde::from_slice(&rmp_serde::to_vec_named(value).unwrap()).unwrap()
+    }
+
+    fn bincode<T: Serialize + DeserializeOwned>(value: &T) -> T {
+        bincode::deserialize(&bincode::serialize(value).unwrap()).unwrap()
+    }
+
+    fn assert_round_trips<T>(value: T, formats: &[Format<T>])
+    where
+        T: Serialize + DeserializeOwned + Debug + PartialEq,
+    {
+        for (format, round_trip) in formats {
+            assert_eq!(round_trip(&value), value, "round trip through {}", format);
+        }
+    }
+
+    #[test]
+    fn test_hash_attribute_formats() {
+        setup();
+        let formats: &[Format<User>] = &[
+            ("json", json),
+            ("yaml", yaml),
+            ("toml", toml),
+            ("cbor", cbor),
+            ("msgpack", msgpack),
+            ("msgpack (named)", msgpack_named),
+            ("bincode", bincode),
+        ];
+        assert_round_trips(user(Some(7)), formats);
+        assert_round_trips(user(None), formats);
+    }
+
+    #[test]
+    fn test_derive_self_describing_formats() {
+        setup();
+        let formats: &[Format<LegacyUser>] = &[
+            ("json", json),
+            ("yaml", yaml),
+            ("toml", toml),
+            ("cbor", cbor),
+            ("msgpack (named)", msgpack_named),
+        ];
+        assert_round_trips(legacy_user(Some(7)), formats);
+        assert_round_trips(legacy_user(None), formats);
+    }
+
+    #[test]
+    fn test_derive_binary_formats() {
+        setup();
+        let formats: &[Format<LegacyUser>] = &[("msgpack", msgpack), ("bincode", bincode)];
+        assert_round_trips(legacy_user(Some(7)), formats);
+        assert_round_trips(legacy_user(None), formats);
+    }
+
+    #[test]
+    fn test_hashes_match_across_formats() {
+        setup();
+        let yaml = serde_yaml::to_string(&user(None)).unwrap();
+        let toml = toml::to_string(&user(None)).unwrap();
+        assert!(yaml.contains("id: qKknODM7Ej"));
+        assert!(toml.contains("id = \"qKknODM7Ej\""));
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Flattened {
+        pub revision: u32,
+        #[serde(flatten)]
+        pub user: User,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct FlattenedLegacy {
+        pub revision: u32,
+        #[serde(flatten)]
+        pub user: LegacyUser,
+    }
+
+    #[test]
+    fn test_flatten_targets() {
+        setup();
+        let flattened = Flattened {
+            revision: 3,
+            user: user(Some(7)),
+        };
+        let value = serde_json::to_value(&flattened).unwrap();
+        assert_eq!(value["id"], "qKknODM7Ej");
+        assert_eq!(value["revision"], 3);
+
+        let legacy = FlattenedLegacy {
+            revision: 3,
+            user: legacy_user(None),
+        };
+        let value = serde_json::to_value(&legacy).unwrap();
+        assert_eq!(value["id"], "qKknODM7Ej");
+
+        // Flattening buffers the content, so only self-describing formats apply.
+        let formats: &[Format<Flattened>] = &[
+            ("json", json),
+            ("yaml", yaml),
+            ("toml", toml),
+            ("cbor", cbor),
+            ("msgpack (named)", msgpack_named),
+        ];
+        assert_round_trips(flattened, formats);
+        let formats: &[Format<FlattenedLegacy>] = &[
+            ("json", json),
+            ("yaml", yaml),
+            ("toml", toml),
+            ("cbor", cbor),
+            ("msgpack (named)", msgpack_named),
+        ];
+        assert_round_trips(legacy, formats);
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Team {
+        #[serde(hash)]
+        pub id: u64,
+        pub founded: toml::value::Datetime,
+        pub lead: LegacyUser,
+        pub members: Vec<User>,
+        pub by_name: BTreeMap<String, LegacyUser>,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct Organization {
+        pub team: Team,
+        #[hash]
+        pub id: u64,
+        pub founded: toml::value::Datetime,
+        pub teams: Vec<Team>,
+        #[hash]
+        pub parent: Option<u64>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(tag = "kind")]
+    pub enum Tagged {
+        User(LegacyUser),
+        Team(Team),
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(untagged)]
+    pub enum Untagged {
+        User(LegacyUser),
+        Revision { revision: u32 },
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Document {
+        pub tagged: Vec<Tagged>,
+        pub untagged: Vec<Untagged>,
+    }
+
+    fn team() -> Team {
+        Team {
+            id: 42,
+            founded: "1979-05-27T07:32:00Z".parse().unwrap(),
+            lead: legacy_user(Some(7)),
+            members: vec![user(None), user(Some(7))],
+            by_name: [("dan".to_string(), legacy_user(None))].into(),
+        }
+    }
+
+    fn organization() -> Organization {
+        Organization {
+            team: team(),
+            id: 158674,
+            founded: "1979-05-27".parse().unwrap(),
+            teams: vec![team(), team()],
+            parent: None,
+        }
+    }
+
+    fn toml_value<T: Serialize + DeserializeOwned>(value: &T) -> T {
+        toml::Value::try_from(value).unwrap().try_into().unwrap()
+    }
+
+    fn yaml_value<T: Serialize + DeserializeOwned>(value: &T) -> T {
+        serde_yaml::from_value(serde_yaml::to_value(value).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_nested_tables() {
+        setup();
+        let formats: &[Format<Organization>] = &[
+            ("json", json),
+            ("yaml", yaml),
+            ("yaml (value)", yaml_value),
+            ("toml", toml),
+            ("toml (value)", toml_value),
+        ];
+        assert_round_trips(organization(), formats);
+
+        // Hashed structs end up in sub-tables and arrays of tables, next to datetimes.
+        let toml = toml::to_string(&organization()).unwrap();
+        assert!(toml.contains("[team.lead]\nid = \"qKknODM7Ej\""));
+        assert!(toml.contains("[[teams.members]]\nid = \"qKknODM7Ej\""));
+        assert!(toml.contains("[team.by_name.dan]\nid = \"qKknODM7Ej\""));
+    }
+
+    #[test]
+    fn test_buffered_nested_tables() {
+        setup();
+        let document = Document {
+            tagged: vec![Tagged::User(legacy_user(Some(7))), Tagged::Team(team())],
+            untagged: vec![Untagged::User(legacy_user(None)), Untagged::Revision { revision: 3 }],
+        };
+        let formats: &[Format<Document>] = &[
+            ("json", json),
+            ("yaml", yaml),
+            ("yaml (value)", yaml_value),
+            ("toml", toml),
+            ("toml (value)", toml_value),
+        ];
+        assert_round_trips(document, formats);
+    }
+}
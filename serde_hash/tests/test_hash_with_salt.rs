@@ -0,0 +1,95 @@
+mod test_hash_with_salt {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::hashids::{SerdeHashOptions, try_current_encoder};
+    use serde_hash::serde_hash;
+
+    serde_hash::hash_with_salt!(mod user_ids, salt = "users", min_length = 10);
+    serde_hash::hash_with_salt!(
+        mod order_ids,
+        salt = "orders",
+        min_length = 6,
+        alphabet = "0123456789abcdef",
+    );
+    serde_hash::hash_with_salt!(mod broken_ids, salt = "broken", alphabet = "abc");
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Order {
+        #[serde(with = "order_ids::numeric")]
+        pub id: u64,
+        #[serde(with = "user_ids::numeric")]
+        pub customer: u32,
+        #[serde(with = "user_ids::vec_numeric")]
+        pub watchers: Vec<u64>,
+        #[serde(with = "order_ids::option_numeric")]
+        pub parent: Option<u64>,
+        #[serde(with = "order_ids::option_vec_numeric")]
+        pub children: Option<Vec<u16>>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug)]
+    pub struct Broken {
+        #[serde(with = "broken_ids::numeric")]
+        pub id: u64,
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Global {
+        #[serde(hash)]
+        pub id: u64,
+    }
+
+    #[test]
+    fn test_separate_configurations() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .build();
+
+        let order = Order {
+            id: 158674,
+            customer: 158674,
+            watchers: vec![1, 2],
+            parent: None,
+            children: Some(vec![3]),
+        };
+        let json = serde_json::to_value(&order).unwrap();
+
+        let id = json["id"].as_str().unwrap();
+        let customer = json["customer"].as_str().unwrap();
+        assert_eq!(id, order_ids::encoder().encode_single(158674));
+        assert_eq!(customer, user_ids::encoder().encode_single(158674));
+        assert_ne!(id, customer);
+        assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(customer.len(), 10);
+
+        let global = serde_json::to_value(Global { id: 158674 }).unwrap();
+        assert_eq!(global["id"], "qKknODM7Ej");
+        assert_eq!(try_current_encoder().unwrap().encode_single(158674), "qKknODM7Ej");
+
+        let decoded: Order = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, order);
+    }
+
+    #[test]
+    fn test_rejects_other_configuration() {
+        let hash = user_ids::encoder().encode_single(42);
+        let json = format!(
+            r#"{{"id":"{hash}","customer":"{hash}","watchers":[],"parent":null,"children":null}}"#
+        );
+        assert!(serde_json::from_str::<Order>(&json).is_err());
+    }
+
+    #[test]
+    fn test_invalid_configuration_is_an_error() {
+        let error = SerdeHashError::AlphabetTooShort { unique: 3 };
+        assert_eq!(broken_ids::try_encoder().unwrap_err(), error);
+
+        let serialized = serde_json::to_string(&Broken { id: 1 }).unwrap_err();
+        assert_eq!(serialized.to_string(), error.to_string());
+        let deserialized = serde_json::from_str::<Broken>(r#"{"id":"abc"}"#).unwrap_err();
+        assert!(deserialized.to_string().starts_with(&error.to_string()), "{}", deserialized);
+    }
+}
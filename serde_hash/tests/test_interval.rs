@@ -0,0 +1,99 @@
+#![cfg(feature = "interval")]
+
+mod test_interval {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::hashids::{SerdeHashOptions, encode};
+    use serde_hash::interval::{decode_interval, encode_interval};
+    use std::ops::{Range, RangeInclusive};
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Window {
+        #[serde(with = "serde_hash::interval")]
+        pub ids: Range<u64>,
+        #[serde(with = "serde_hash::interval")]
+        pub days: RangeInclusive<u16>,
+        #[serde(with = "serde_hash::interval")]
+        pub span: (u32, u32),
+        #[serde(default, with = "serde_hash::interval::option")]
+        pub page: Option<Range<u64>>,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        setup();
+        let hash = encode_interval(&(100u64..200));
+        assert_eq!(hash, encode(&[100, 200]));
+        assert_eq!(decode_interval::<Range<u64>>(&hash).unwrap(), 100..200);
+        assert_eq!(decode_interval::<(u64, u64)>(&hash).unwrap(), (100, 200));
+        assert_eq!(
+            decode_interval::<RangeInclusive<u8>>(&hash).unwrap(),
+            100..=200
+        );
+    }
+
+    #[test]
+    fn test_wide_endpoints() {
+        setup();
+        let range = (1u128 << 64)..(1u128 << 65);
+        let hash = encode_interval(&range);
+        assert_eq!(hash, encode(&[1, 0, 2, 0]));
+        assert_eq!(decode_interval::<Range<u128>>(&hash).unwrap(), range);
+        assert!(decode_interval::<Range<u64>>(&hash).is_err());
+
+        let range = 5..=u128::MAX;
+        let hash = encode_interval(&range);
+        assert_eq!(decode_interval::<RangeInclusive<u128>>(&hash).unwrap(), range);
+        // Endpoints that fit in a u64 keep the two-value layout
+        assert_eq!(encode_interval(&(100u128..200)), encode(&[100, 200]));
+        assert!(decode_interval::<Range<u128>>(encode(&[0, 100, 0, 200])).is_err());
+        assert!(decode_interval::<Range<u128>>(encode(&[2, 0, 1, 0])).is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        setup();
+        let window = Window {
+            ids: 1..158674,
+            days: 3..=9,
+            span: (5, 5),
+            page: None,
+        };
+        let json = serde_json::to_string(&window).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"ids":"{}","days":"{}","span":"{}","page":null}}"#,
+                encode(&[1, 158674]),
+                encode(&[3, 9]),
+                encode(&[5, 5])
+            )
+        );
+        assert_eq!(serde_json::from_str::<Window>(&json).unwrap(), window);
+
+        let window = Window {
+            page: Some(10..20),
+            ..window
+        };
+        let json = serde_json::to_string(&window).unwrap();
+        assert_eq!(serde_json::from_str::<Window>(&json).unwrap(), window);
+    }
+
+    #[test]
+    fn test_invalid_intervals() {
+        setup();
+        assert!(decode_interval::<Range<u64>>(encode(&[1])).is_err());
+        assert!(decode_interval::<Range<u64>>(encode(&[1, 2, 3])).is_err());
+        assert!(decode_interval::<Range<u64>>(encode(&[2, 1])).is_err());
+        assert!(decode_interval::<Range<u8>>(encode(&[1, 300])).is_err());
+        assert!(decode_interval::<Range<u64>>("!!").is_err());
+    }
+}
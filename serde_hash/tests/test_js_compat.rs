@@ -0,0 +1,88 @@
+mod test_js_compat {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::encoder::HashIdsEncoder;
+    use serde_hash::interop::hashids_js;
+    use serde_hash::serde_hash;
+
+    const NO_SEPARATORS: &str = "abdegjklmnopqrvwxyzABDEGJKLMNOPQRVWXYZ1234567890";
+    const HEX: &str = "0123456789abcdef";
+
+    /// `(salt, min_length, alphabet, values, hash)` as produced by hashids.js 2.x, e.g.
+    /// `new Hashids('this is my salt', 0, '0123456789abcdef').encode(1, 2, 3)`.
+    const VECTORS: &[(&str, usize, &str, &[u64], &str)] = &[
+        ("this is my salt", 0, hashids_js::DEFAULT_ALPHABET, &[12345], "NkK9"),
+        ("this is my salt", 0, hashids_js::DEFAULT_ALPHABET, &[683, 94108, 123, 5], "aBMswoO2UB3Sj"),
+        ("this is my salt", 8, hashids_js::DEFAULT_ALPHABET, &[1], "gB0NV05e"),
+        ("", 0, hashids_js::DEFAULT_ALPHABET, &[1, 2, 3], "o2fXhV"),
+        ("My Project", 0, hashids_js::DEFAULT_ALPHABET, &[1, 2, 3], "Z4UrtW"),
+        ("hello world", 10, hashids_js::DEFAULT_ALPHABET, &[158674], "qKknODM7Ej"),
+        ("this is my salt", 0, HEX, &[1234567], "b332db5"),
+        ("this is my salt", 0, HEX, &[1, 2, 3], "68c508"),
+        ("this is my salt", 0, HEX, &[7452, 2967, 21401], "27dd71dda90a4762"),
+        ("", 0, NO_SEPARATORS, &[7452, 2967, 21401], "X50Yg6VPoAO4"),
+        ("hello world", 10, NO_SEPARATORS, &[158674, 7], "xOzAPVKnVO"),
+        ("hello world", 10, NO_SEPARATORS, &[1, 2, 3], "M9O5ybJdzE"),
+    ];
+
+    fn encoder(salt: &str, min_length: usize, alphabet: &str, js_compat: bool) -> HashIdsEncoder {
+        let options = hashids_js::options(salt, min_length)
+            .with_alphabet(alphabet)
+            .with_js_compat(js_compat);
+        HashIdsEncoder::new(&options).unwrap()
+    }
+
+    #[test]
+    fn test_vectors() {
+        for &(salt, min_length, alphabet, values, hash) in VECTORS {
+            let encoder = encoder(salt, min_length, alphabet, true);
+            assert_eq!(encoder.encode(values), hash, "encoding {:?}", values);
+            assert_eq!(encoder.decode(hash).unwrap(), values, "decoding {}", hash);
+        }
+    }
+
+    #[test]
+    fn test_default_mode_differs() {
+        // Without compatibility, separators topped up from the alphabet are shuffled
+        // differently, which shows once a hash holds several values.
+        let default = encoder("this is my salt", 0, HEX, false);
+        assert_eq!(default.encode(&[1, 2, 3]), "68c518");
+        // Single values and alphabets with enough separators are unaffected.
+        assert_eq!(default.encode(&[1234567]), "b332db5");
+        let default = encoder("this is my salt", 0, hashids_js::DEFAULT_ALPHABET, false);
+        assert_eq!(default.encode(&[683, 94108, 123, 5]), "aBMswoO2UB3Sj");
+    }
+
+    #[test]
+    fn test_rejects_non_canonical_hashes() {
+        // hashids.js returns no values for hashes it wouldn't have produced itself.
+        let js = encoder("hello world", 10, hashids_js::DEFAULT_ALPHABET, true);
+        assert!(js.decode("nope").is_err());
+        assert!(js.decode("qKknODM7Ej").is_ok());
+        let default = encoder("hello world", 10, hashids_js::DEFAULT_ALPHABET, false);
+        assert!(default.decode("nope").is_ok());
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Account {
+        #[serde(hash)]
+        pub id: u64,
+        #[serde(hash)]
+        pub members: Vec<u64>,
+    }
+
+    #[test]
+    fn test_global_options() {
+        hashids_js::options("this is my salt", 0)
+            .with_alphabet(HEX)
+            .with_weak_salt_allowed(true)
+            .build();
+        let account = Account {
+            id: 1234567,
+            members: vec![1],
+        };
+        let json = serde_json::to_string(&account).unwrap();
+        assert!(json.starts_with(r#"{"id":"b332db5","#), "{}", json);
+        assert_eq!(serde_json::from_str::<Account>(&json).unwrap(), account);
+    }
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "json")]
+
+mod test_json_helpers {
+    use serde_hash::HashIds;
+    use serde_hash::hashids::SerdeHashOptions;
+
+    #[derive(HashIds, Debug, PartialEq)]
+    #[hash(helpers)]
+    pub struct User {
+        #[hash]
+        pub id: u64,
+        pub name: String,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    #[hash(helpers)]
+    pub struct UserRef<'a> {
+        #[hash]
+        pub id: u64,
+        pub name: &'a str,
+    }
+
+    #[test]
+    fn test_public_json_helpers() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let user = User {
+            id: 158674,
+            name: "Dan".to_string(),
+        };
+        let json = user.to_public_json().unwrap();
+        assert_eq!(json, r#"{"id":"qKknODM7Ej","name":"Dan"}"#);
+        assert_eq!(User::from_public_json(&json).unwrap(), user);
+        assert!(User::from_public_json(r#"{"id":"!!","name":"Dan"}"#).is_err());
+
+        let user = UserRef::from_public_json(&json).unwrap();
+        assert_eq!(user, UserRef { id: 158674, name: "Dan" });
+        assert_eq!(user.to_public_json().unwrap(), json);
+    }
+}
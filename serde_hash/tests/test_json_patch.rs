@@ -0,0 +1,75 @@
+#![cfg(feature = "json-patch")]
+
+mod test_json_patch {
+    use serde_hash::hashids::{SerdeHashOptions, encode_single};
+    use serde_hash::json_patch::PatchIds;
+    use serde_json::json;
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    fn ids() -> PatchIds {
+        PatchIds::new()
+            .with_field("id")
+            .with_field("manager")
+            .with_field("friends")
+            .with_keyed_collection("members")
+    }
+
+    #[test]
+    fn test_decodes_values_and_paths() {
+        setup();
+        let (a, b, c) = (encode_single(1), encode_single(2), encode_single(3));
+        let mut patch = json!([
+            { "op": "replace", "path": "/manager", "value": "qKknODM7Ej" },
+            { "op": "replace", "path": "/manager", "value": null },
+            { "op": "add", "path": "/friends/-", "value": a },
+            { "op": "replace", "path": "/friends", "value": [a, b] },
+            { "op": "test", "path": "/friends/0", "value": a },
+            { "op": "replace", "path": "/members/qKknODM7Ej/role", "value": "admin" },
+            { "op": "move", "from": "/members/qKknODM7Ej", "path": format!("/members/{}", c) },
+            { "op": "add", "path": "/members", "value": { (b.clone()): { "id": b, "role": "owner" } } },
+            { "op": "add", "path": "/team", "value": { "name": "core", "manager": c } },
+            { "op": "replace", "path": "/name", "value": "qKknODM7Ej" },
+        ]);
+        ids().decode(&mut patch).unwrap();
+        assert_eq!(
+            patch,
+            json!([
+                { "op": "replace", "path": "/manager", "value": 158674 },
+                { "op": "replace", "path": "/manager", "value": null },
+                { "op": "add", "path": "/friends/-", "value": 1 },
+                { "op": "replace", "path": "/friends", "value": [1, 2] },
+                { "op": "test", "path": "/friends/0", "value": 1 },
+                { "op": "replace", "path": "/members/158674/role", "value": "admin" },
+                { "op": "move", "from": "/members/158674", "path": "/members/3" },
+                { "op": "add", "path": "/members", "value": { "2": { "id": 2, "role": "owner" } } },
+                { "op": "add", "path": "/team", "value": { "name": "core", "manager": 3 } },
+                { "op": "replace", "path": "/name", "value": "qKknODM7Ej" },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_patches() {
+        setup();
+        let mut not_a_patch = json!({ "op": "replace" });
+        assert!(ids().decode(&mut not_a_patch).is_err());
+
+        let mut bad_hash = json!([{ "op": "replace", "path": "/manager", "value": "!!" }]);
+        let error = ids().decode(&mut bad_hash).unwrap_err();
+        assert!(format!("{:#}", error).contains("operation 0"));
+
+        let mut not_a_hash = json!([{ "op": "replace", "path": "/manager", "value": 5 }]);
+        assert!(ids().decode(&mut not_a_hash).is_err());
+
+        let mut bad_key = json!([{ "op": "remove", "path": "/members/!!" }]);
+        assert!(ids().decode(&mut bad_key).is_err());
+    }
+}
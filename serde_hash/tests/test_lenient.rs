@@ -0,0 +1,62 @@
+mod test_lenient {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::hashids::SerdeHashOptions;
+    use std::sync::Once;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Record {
+        #[serde(with = "serde_hash::serde_impl::numeric_lenient")]
+        pub id: u64,
+        #[serde(with = "serde_hash::serde_impl::numeric_lenient::option")]
+        pub parent: Option<u32>,
+        #[serde(with = "serde_hash::serde_impl::Lenient::<{ u16::MAX as u64 }>")]
+        pub group: u16,
+    }
+
+    static INIT: Once = Once::new();
+
+    fn init() {
+        INIT.call_once(|| {
+            SerdeHashOptions::new()
+                .with_salt("hello world")
+                .with_weak_salt_allowed(true)
+                .with_min_length(10)
+                .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+                .build();
+        });
+    }
+
+    #[test]
+    fn test_valid_hashes_round_trip() {
+        init();
+        let record = Record {
+            id: 158674,
+            parent: Some(7),
+            group: 3,
+        };
+        let json = serde_json::to_string(&record).unwrap();
+        assert!(json.contains(r#""id":"qKknODM7Ej""#));
+        assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record);
+    }
+
+    #[test]
+    fn test_corrupted_hashes_fall_back() {
+        init();
+        let json = r#"[
+            {"id":"qKknODM7Ej","parent":null,"group":"qKknODM7Ej"},
+            {"id":"corrupted!","parent":"corrupted!","group":"corrupted!"}
+        ]"#;
+        let records: Vec<Record> = serde_json::from_str(json).unwrap();
+        // 158674 doesn't fit in a u16 either, so the first group gets the sentinel too.
+        assert_eq!(
+            records,
+            [
+                Record { id: 158674, parent: None, group: u16::MAX },
+                Record { id: 0, parent: None, group: u16::MAX },
+            ]
+        );
+
+        // Only the hash is lenient: a value of the wrong type still fails.
+        assert!(serde_json::from_str::<Record>(r#"{"id":5,"parent":null,"group":"x"}"#).is_err());
+    }
+}
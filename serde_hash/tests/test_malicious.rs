@@ -0,0 +1,134 @@
+mod test_malicious {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::alphabet::Alphabet;
+    use serde_hash::encoder::HashIdsEncoder;
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::serde_hash;
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct User {
+        #[serde(hash)]
+        pub id: u64,
+    }
+
+    fn options(alphabet: Alphabet, min_length: usize) -> SerdeHashOptions {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_min_length(min_length)
+            .with_alphabet_preset(alphabet)
+    }
+
+    /// A small deterministic generator, so failures are reproducible.
+    fn lcg(state: &mut u64) -> u64 {
+        *state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        *state
+    }
+
+    #[test]
+    fn test_round_trips_match_hash_ids() {
+        let mut state = 1;
+        for alphabet in [
+            Alphabet::Base62,
+            Alphabet::Base58NoLookalikes,
+            Alphabet::LowercaseHex,
+            Alphabet::UrlSafe,
+        ] {
+            for min_length in [0, 10, 40] {
+                // The last round trip decodes every value at once.
+                let options = options(alphabet, min_length).with_max_decoded_values(204);
+                let encoder = HashIdsEncoder::new(&options).unwrap();
+                let reference = hash_ids::HashIds::builder()
+                    .with_salt("hello world")
+                    .with_min_length(min_length)
+                    .with_alphabet(alphabet.as_str())
+                    .finish()
+                    .unwrap();
+                let mut values = vec![0, 1, u64::MAX, u64::MAX - 1];
+                values.extend((0..200).map(|_| lcg(&mut state) >> (lcg(&mut state) % 64)));
+                for value in &values {
+                    let hash = encoder.encode_single(*value);
+                    assert_eq!(hash, reference.encode(&[*value]));
+                    assert_eq!(encoder.decode_single(&hash).unwrap(), *value, "{}", hash);
+                }
+                let hash = encoder.encode(&values);
+                assert_eq!(hash, reference.encode(&values));
+                assert_eq!(encoder.decode(&hash).unwrap(), values);
+            }
+        }
+    }
+
+    #[test]
+    fn test_short_inputs_match_hash_ids() {
+        // Inputs this short can't overflow, so the reference decoder is safe to call.
+        let alphabet = Alphabet::Base62.as_str();
+        let reference = hash_ids::HashIds::builder()
+            .with_salt("hello world")
+            .with_alphabet(alphabet)
+            .finish()
+            .unwrap();
+        let encoder = HashIdsEncoder::new(&options(Alphabet::Base62, 0)).unwrap();
+        let chars: Vec<char> = alphabet.chars().collect();
+        let mut state = 7;
+        for _ in 0..5000 {
+            let length = (lcg(&mut state) % 6) as usize;
+            let input: String = (0..length)
+                .map(|_| chars[(lcg(&mut state) % chars.len() as u64) as usize])
+                .collect();
+            let expected = reference.decode(&input).ok();
+            assert_eq!(encoder.decode(&input).ok(), expected, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_adversarial_inputs_are_errors() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .build();
+        let encoder = HashIdsEncoder::new(&options(Alphabet::Base62, 10)).unwrap();
+        let valid = encoder.encode_single(158674);
+
+        let inputs = [
+            "z".repeat(64),
+            "9".repeat(100_000),
+            Alphabet::Base62.as_str().repeat(1000),
+            format!("{}\0", valid),
+            valid.replace('K', "\u{039A}"), // Greek capital kappa
+            valid.replace('O', "\u{041E}"), // Cyrillic capital O
+            "\u{202E}qKknODM7Ej".to_string(),
+            "💥".repeat(16),
+        ];
+        for input in &inputs {
+            assert!(encoder.decode_single(input).is_err(), "{:?}", input);
+            let json = serde_json::to_string(&serde_json::json!({ "id": input })).unwrap();
+            assert!(serde_json::from_str::<User>(&json).is_err());
+        }
+    }
+
+    #[test]
+    fn test_hashes_with_too_many_values_are_rejected() {
+        let values: Vec<u64> = (0..1000).collect();
+        let long = HashIdsEncoder::new(&options(Alphabet::Base62, 0).with_max_decoded_values(1000))
+            .unwrap()
+            .encode(&values);
+
+        let encoder = HashIdsEncoder::new(&options(Alphabet::Base62, 0)).unwrap();
+        let error = encoder.decode(&long).unwrap_err();
+        assert_eq!(error, SerdeHashError::TooManyValues { count: 1000, max: 16 });
+        assert_eq!(encoder.decode(encoder.encode(&values[..16])).unwrap(), &values[..16]);
+
+        // Single values don't decode more than they need.
+        let pair = encoder.encode(&[1, 2]);
+        let error = encoder.decode_single(&pair).unwrap_err();
+        assert!(error.to_string().contains("at most 1"), "{}", error);
+
+        // Byte strings need a value per 8 bytes.
+        let bytes = [7u8; 256];
+        assert_eq!(encoder.decode_bytes(encoder.encode_bytes(&bytes)).unwrap(), bytes);
+    }
+}
@@ -0,0 +1,92 @@
+mod test_max_length {
+    use serde::Serialize;
+    use serde_hash::encoder::HashIdsEncoder;
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::hashids::{SerdeHashOptions, encode_single};
+    use serde_hash::ordered::OrderedHash;
+    use serde_hash::{HashIds, serde_hash};
+
+    fn options() -> SerdeHashOptions {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+    }
+
+    fn setup() {
+        options().with_max_length(10).build();
+    }
+
+    #[serde_hash]
+    #[derive(Serialize)]
+    struct Order {
+        #[serde(hash)]
+        id: u64,
+    }
+
+    #[test]
+    fn test_serializing_a_longer_hash_fails() {
+        setup();
+        let json = serde_json::to_string(&Order { id: 158674 }).unwrap();
+        assert_eq!(json, r#"{"id":"qKknODM7Ej"}"#);
+
+        let error = serde_json::to_string(&Order { id: u64::MAX }).unwrap_err();
+        assert!(
+            error.to_string().contains("hash of 13 characters exceeds the maximum length of 10"),
+            "{}",
+            error
+        );
+        // The infallible encode still returns the hash.
+        assert_eq!(encode_single(u64::MAX), "aPre3aJN3mpQP");
+    }
+
+    #[derive(HashIds)]
+    struct OrderLine {
+        #[hash(pack_with = "line")]
+        order: u64,
+        line: u16,
+    }
+
+    #[derive(Serialize)]
+    struct Page {
+        #[serde(with = "serde_hash::serde_impl::vec_numeric_compact")]
+        ids: Vec<u64>,
+    }
+
+    #[test]
+    fn test_every_hash_serializer_enforces_the_limit() {
+        setup();
+        let error = serde_json::to_string(&OrderLine { order: u64::MAX, line: 3 }).unwrap_err();
+        assert!(error.to_string().contains("exceeds the maximum length of 10"), "{}", error);
+
+        let error = serde_json::to_string(&Page { ids: (1..100).collect() }).unwrap_err();
+        assert!(error.to_string().contains("exceeds the maximum length of 10"), "{}", error);
+
+        let error = serde_json::to_string(&OrderedHash::from(u64::MAX)).unwrap_err();
+        assert!(error.to_string().contains("exceeds the maximum length of 10"), "{}", error);
+        assert!(serde_json::to_string(&OrderedHash::from(158674u64)).is_ok());
+    }
+
+    #[test]
+    fn test_try_encode_enforces_the_limit() {
+        let encoder = HashIdsEncoder::new(&options().with_max_length(12)).unwrap();
+        assert_eq!(encoder.try_encode(&[1, 2, 3]).unwrap(), "Vk2RTySVko");
+        assert_eq!(
+            encoder.try_encode(&[u64::MAX]),
+            Err(SerdeHashError::MaxLengthExceeded { length: 13, max: 12 })
+        );
+    }
+
+    #[test]
+    fn test_rejects_limit_below_minimum() {
+        assert_eq!(
+            options().with_max_length(8).validate(),
+            Err(SerdeHashError::MaxLengthBelowMinimum { max: 8, min: 10 })
+        );
+        assert_eq!(
+            options().with_exact_length(16).with_max_length(14).validate(),
+            Err(SerdeHashError::MaxLengthBelowMinimum { max: 14, min: 16 })
+        );
+    }
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "metrics")]
+
+mod test_metrics {
+    use metrics::{
+        Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+        SharedString, Unit,
+    };
+    use serde_hash::encoder::HashIdsEncoder;
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::telemetry;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    /// Records every counter increment and histogram sample by metric name.
+    #[derive(Default)]
+    struct TestRecorder {
+        values: Arc<Mutex<HashMap<String, Vec<f64>>>>,
+    }
+
+    struct Handle(String, Arc<Mutex<HashMap<String, Vec<f64>>>>);
+
+    impl Handle {
+        fn push(&self, value: f64) {
+            self.1
+                .lock()
+                .unwrap()
+                .entry(self.0.clone())
+                .or_default()
+                .push(value);
+        }
+    }
+
+    impl CounterFn for Handle {
+        fn increment(&self, value: u64) {
+            self.push(value as f64);
+        }
+
+        fn absolute(&self, _value: u64) {}
+    }
+
+    impl HistogramFn for Handle {
+        fn record(&self, value: f64) {
+            self.push(value);
+        }
+    }
+
+    impl TestRecorder {
+        fn handle(&self, key: &Key) -> Arc<Handle> {
+            Arc::new(Handle(key.name().to_string(), self.values.clone()))
+        }
+
+        fn count(&self, name: &str) -> usize {
+            self.values.lock().unwrap().get(name).map_or(0, Vec::len)
+        }
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            Counter::from_arc(self.handle(key))
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::from_arc(self.handle(key))
+        }
+    }
+
+    #[test]
+    fn test_counts_encodes_and_decodes() {
+        let encoder =
+            HashIdsEncoder::new(&SerdeHashOptions::new().with_salt("hello world")).unwrap();
+        let recorder = TestRecorder::default();
+        metrics::with_local_recorder(&recorder, || {
+            let hash = encoder.encode_single(158674);
+            encoder.encode(&[1, 2]);
+            assert_eq!(encoder.decode_single(&hash).unwrap(), 158674);
+            assert!(encoder.decode_single("!!").is_err());
+            assert!(encoder.decode_single(encoder.encode(&[1, 2])).is_err());
+        });
+
+        assert_eq!(recorder.count(telemetry::ENCODES), 3);
+        assert_eq!(recorder.count(telemetry::ENCODE_SECONDS), 3);
+        assert_eq!(recorder.count(telemetry::DECODES), 3);
+        assert_eq!(recorder.count(telemetry::DECODE_FAILURES), 2);
+    }
+}
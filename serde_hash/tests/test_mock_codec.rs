@@ -0,0 +1,88 @@
+#![cfg(feature = "testing")]
+
+mod test_mock_codec {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::encoder::{DecodeBackend, HashIdsEncoder};
+    use serde_hash::provider::set_options_provider;
+    use serde_hash::serde_hash;
+    use serde_hash::testing::MockCodec;
+    use std::sync::Once;
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct User {
+        #[serde(hash)]
+        pub id: u64,
+        #[serde(hash)]
+        pub group_ids: Vec<u32>,
+        #[hash(min_length = 16)]
+        pub share_id: Option<u64>,
+        pub name: String,
+    }
+
+    fn setup() {
+        static INSTALL: Once = Once::new();
+        INSTALL.call_once(|| set_options_provider(MockCodec::new().context()).unwrap());
+    }
+
+    #[test]
+    fn test_serialization() {
+        setup();
+        let user = User {
+            id: 42,
+            group_ids: vec![1, 2],
+            share_id: Some(7),
+            name: "Dan".to_string(),
+        };
+        let json = serde_json::to_string(&user).unwrap();
+        assert_eq!(
+            json,
+            r#"{"id":"h42","group_ids":["h1","h2"],"share_id":"h7","name":"Dan"}"#
+        );
+        assert_eq!(serde_json::from_str::<User>(&json).unwrap(), user);
+        assert!(serde_json::from_str::<User>(r#"{"id":"qKknODM7Ej"}"#).is_err());
+    }
+
+    #[test]
+    fn test_codec() {
+        let codec = MockCodec::new().with_prefix("usr_");
+        assert_eq!(codec.encode(&[42]), "usr_42");
+        assert_eq!(codec.encode(&[1, 2]), "usr_1-2");
+        assert_eq!(codec.decode("usr_1-2").unwrap(), [1, 2]);
+        assert_eq!(codec.decode("usr_").unwrap(), Vec::<u64>::new());
+        assert!(codec.decode("h42").is_err());
+        assert!(codec.decode("usr_1-x").is_err());
+        assert!(codec.decode("usr_1--2").is_err());
+    }
+
+    #[test]
+    fn test_encoder_uses_codec() {
+        let encoder = HashIdsEncoder::new(&MockCodec::new().options().with_min_length(16)).unwrap();
+        assert_eq!(encoder.encode_single(158674), "h158674");
+        let (value, report) = encoder.decode_single_with_report("h158674").unwrap();
+        assert_eq!(value, 158674);
+        assert_eq!(report.backend, DecodeBackend::Mock);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_scoped_context() {
+        use serde_hash::context::with_context_sync;
+
+        setup();
+        let context = MockCodec::new().with_prefix("grp_").context();
+        let json = with_context_sync(context, || {
+            serde_json::to_string(&User {
+                id: 42,
+                group_ids: vec![3],
+                share_id: None,
+                name: "Dan".to_string(),
+            })
+            .unwrap()
+        });
+        assert_eq!(
+            json,
+            r#"{"id":"grp_42","group_ids":["grp_3"],"share_id":null,"name":"Dan"}"#
+        );
+    }
+}
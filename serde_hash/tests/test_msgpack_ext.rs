@@ -0,0 +1,122 @@
+mod test_msgpack_ext {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::{HashIds, serde_hash};
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .with_msgpack_ext(7)
+            .build();
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct User {
+        #[hash]
+        id: u64,
+        #[hash]
+        friends: Vec<u32>,
+        #[hash]
+        manager: Option<u128>,
+        name: String,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    struct LegacyUser {
+        #[hash]
+        id: u64,
+        name: String,
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Id {
+        #[hash]
+        id: u64,
+    }
+
+    /// A struct with the same keys as [`Id`], holding what's written as-is.
+    #[derive(Serialize)]
+    struct RawId<T> {
+        id: T,
+    }
+
+    /// fixext 8 of type 7 holding 158674.
+    const EXT: [u8; 10] = [0xd7, 7, 0, 0, 0, 0, 0, 0x02, 0x6b, 0xd2];
+
+    fn user() -> User {
+        User {
+            id: 158674,
+            friends: vec![1, 2],
+            manager: Some(u64::MAX as u128 + 1),
+            name: "Dan Smith".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_writes_extension_values() {
+        setup();
+        let bytes = rmp_serde::to_vec(&Id { id: 158674 }).unwrap();
+        assert_eq!(bytes[0], 0x91);
+        assert_eq!(bytes[1..], EXT);
+
+        let bytes = rmp_serde::to_vec_named(&user()).unwrap();
+        assert!(bytes.windows(EXT.len()).any(|window| window == EXT));
+        // u128 values above u64::MAX take 16 bytes.
+        let wide = [[0xd8, 7, 0, 0, 0, 0, 0, 0, 0, 1].as_slice(), &[0; 8]].concat();
+        assert!(bytes.windows(wide.len()).any(|window| window == wide));
+    }
+
+    #[test]
+    fn test_round_trips() {
+        setup();
+        let bytes = rmp_serde::to_vec(&user()).unwrap();
+        assert_eq!(rmp_serde::from_slice::<User>(&bytes).unwrap(), user());
+        let bytes = rmp_serde::to_vec_named(&user()).unwrap();
+        assert_eq!(rmp_serde::from_slice::<User>(&bytes).unwrap(), user());
+
+        let legacy = LegacyUser { id: 158674, name: "Dan Smith".to_string() };
+        let bytes = rmp_serde::to_vec_named(&legacy).unwrap();
+        assert!(bytes.windows(EXT.len()).any(|window| window == EXT));
+        assert_eq!(rmp_serde::from_slice::<LegacyUser>(&bytes).unwrap(), legacy);
+    }
+
+    #[test]
+    fn test_human_readable_formats_stay_hashed() {
+        setup();
+        let json = serde_json::to_string(&Id { id: 158674 }).unwrap();
+        assert_eq!(json, r#"{"id":"qKknODM7Ej"}"#);
+        assert_eq!(serde_json::from_str::<Id>(&json).unwrap(), Id { id: 158674 });
+    }
+
+    #[test]
+    fn test_reads_hash_strings() {
+        setup();
+        let bytes = rmp_serde::to_vec(&RawId { id: "qKknODM7Ej" }).unwrap();
+        assert_eq!(rmp_serde::from_slice::<Id>(&bytes).unwrap(), Id { id: 158674 });
+    }
+
+    #[test]
+    fn test_rejects_other_values() {
+        setup();
+        let mut bytes = rmp_serde::to_vec(&Id { id: 158674 }).unwrap();
+        bytes[2] = 8;
+        let error = rmp_serde::from_slice::<Id>(&bytes).unwrap_err();
+        assert!(error.to_string().contains("expected MessagePack extension type 7, found 8"));
+
+        let bytes = rmp_serde::to_vec(&RawId { id: 158674 }).unwrap();
+        assert!(rmp_serde::from_slice::<Id>(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_reserved_types() {
+        let options = SerdeHashOptions::new().with_msgpack_ext(-1);
+        assert_eq!(options.validate(), Err(SerdeHashError::ReservedExtType(-1)));
+        assert!(SerdeHashOptions::new().with_msgpack_ext(127).validate().is_ok());
+    }
+}
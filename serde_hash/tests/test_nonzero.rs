@@ -0,0 +1,67 @@
+mod test_nonzero {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::{HashIds, serde_hash};
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Order {
+        #[hash(nonzero)]
+        id: u64,
+        #[hash(nonzero, min_length = 16)]
+        customer_ids: Vec<u32>,
+        #[hash(nonzero)]
+        parent_id: Option<u64>,
+        #[hash]
+        batch: u64,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    struct Invoice {
+        #[hash(nonzero, suffix = "_hash")]
+        id: u64,
+        #[hash]
+        batch: u64,
+    }
+
+    fn order(id: u64, customer_ids: Vec<u32>, parent_id: Option<u64>) -> Order {
+        Order { id, customer_ids, parent_id, batch: 0 }
+    }
+
+    #[test]
+    fn test_nonzero_rejects_zero() {
+        setup();
+        for order in [
+            order(0, vec![1], None),
+            order(1, vec![2, 0], None),
+            order(1, vec![2], Some(0)),
+        ] {
+            let error = serde_json::to_string(&order).unwrap_err();
+            assert!(error.to_string().contains("refusing to hash 0"), "{}", error);
+        }
+        let error = serde_json::to_string(&Invoice { id: 0, batch: 1 }).unwrap_err();
+        assert!(error.to_string().contains("refusing to hash 0"), "{}", error);
+    }
+
+    #[test]
+    fn test_nonzero_round_trips_other_values() {
+        setup();
+        let order = order(158674, vec![1, 2], None);
+        let json = serde_json::to_string(&order).unwrap();
+        assert!(json.contains("\"id\":\"qKknODM7Ej\""), "{}", json);
+        assert_eq!(serde_json::from_str::<Order>(&json).unwrap(), order);
+
+        let invoice = Invoice { id: 158674, batch: 0 };
+        let json = serde_json::to_string(&invoice).unwrap();
+        assert_eq!(serde_json::from_str::<Invoice>(&json).unwrap(), invoice);
+    }
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "hmac")]
+mod test_orderable {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::orderable::{Orderable, decode_orderable, encode_orderable};
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Row {
+        #[serde(with = "serde_hash::orderable")]
+        pub id: u32,
+        #[serde(with = "serde_hash::orderable::option")]
+        pub parent: Option<u64>,
+        pub key: Orderable<u128>,
+    }
+
+    #[test]
+    fn test_strings_sort_like_ids() {
+        setup();
+        let ids = [0, 1, 2, 9, 10, 61, 62, 158674, u32::MAX as u64, u64::MAX - 1, u64::MAX];
+        let encoded: Vec<String> = ids.iter().map(|&id| encode_orderable(id)).collect();
+        let mut sorted = encoded.clone();
+        sorted.sort();
+        assert_eq!(sorted, encoded);
+        assert!(encoded.iter().all(|hash| hash.len() == encoded[0].len()));
+        for (&id, hash) in ids.iter().zip(&encoded) {
+            assert_eq!(decode_orderable(hash).unwrap(), id);
+        }
+
+        let mut wide: Vec<Orderable<u128>> = [u128::MAX, 5, u64::MAX as u128 + 1]
+            .map(Orderable::new)
+            .to_vec();
+        wide.sort_by_key(|id| id.to_string());
+        let values: Vec<u128> = wide.iter().map(|id| id.value()).collect();
+        assert_eq!(values, [5, u64::MAX as u128 + 1, u128::MAX]);
+    }
+
+    #[test]
+    fn test_rejects_invalid_strings() {
+        setup();
+        let hash = encode_orderable(158674);
+        assert!(decode_orderable(&hash[1..]).is_err());
+        assert!(decode_orderable(format!("{}!", &hash[1..])).is_err());
+
+        // Neighbouring strings fall between strides.
+        let mut tampered: Vec<char> = hash.chars().collect();
+        let last = tampered.last_mut().unwrap();
+        *last = if *last == 'z' { 'y' } else { 'z' };
+        assert!(decode_orderable(tampered.iter().collect::<String>()).is_err());
+
+        assert!(Orderable::<u8>::decode(&hash).is_err());
+        assert!(Orderable::<u128>::decode(&hash).is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        setup();
+        let row = Row {
+            id: 158674,
+            parent: None,
+            key: Orderable::new(1 << 100),
+        };
+        let json = serde_json::to_string(&row).unwrap();
+        assert!(json.contains(&format!(r#""id":"{}""#, encode_orderable(158674))), "{}", json);
+        assert_eq!(serde_json::from_str::<Row>(&json).unwrap(), row);
+    }
+}
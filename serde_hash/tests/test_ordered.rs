@@ -0,0 +1,83 @@
+mod test_ordered {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::ordered::OrderedHash;
+    use serde_hash::profile::{SerializationProfile, with_profile};
+    use serde_hash::{HashedId, PublicId};
+    use std::collections::{BTreeMap, HashSet};
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Page {
+        pub ids: Vec<OrderedHash<u16>>,
+        pub last: Option<OrderedHash<u64>>,
+    }
+
+    #[test]
+    fn test_sorts_numerically() {
+        setup();
+        let mut ids: Vec<OrderedHash<u64>> = [100, 2, 158674, 30].map(OrderedHash::new).to_vec();
+        ids.sort();
+        assert_eq!(ids.iter().map(|id| id.value()).collect::<Vec<_>>(), [2, 30, 100, 158674]);
+
+        let mut hashes: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        hashes.sort();
+        assert_ne!(hashes, ids.iter().map(|id| id.to_string()).collect::<Vec<_>>());
+
+        let map: BTreeMap<OrderedHash<u64>, &str> = [(30.into(), "c"), (2.into(), "a")].into();
+        assert_eq!(map.values().copied().collect::<Vec<_>>(), ["a", "c"]);
+        let set: HashSet<OrderedHash<u64>> = [2.into(), 2.into()].into();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_display_and_parse() {
+        setup();
+        let id = OrderedHash::new(158674u64);
+        assert_eq!(id.to_string(), "qKknODM7Ej");
+        assert_eq!("qKknODM7Ej".parse::<OrderedHash<u64>>().unwrap(), id);
+        assert_eq!(id.into_inner(), 158674);
+        assert!("qKknODM7Ej".parse::<OrderedHash<u16>>().is_err());
+        assert!("!!".parse::<OrderedHash<u64>>().is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        setup();
+        let page = Page {
+            ids: vec![1.into(), 2.into()],
+            last: Some(158674.into()),
+        };
+        let json = serde_json::to_string(&page).unwrap();
+        assert!(json.contains(r#""last":"qKknODM7Ej""#), "{}", json);
+        assert_eq!(serde_json::from_str::<Page>(&json).unwrap(), page);
+
+        let err = serde_json::from_str::<Page>(r#"{"ids":["qKknODM7Ej"],"last":null}"#).unwrap_err();
+        assert!(err.to_string().contains("does not fit in u16"), "{}", err);
+    }
+
+    #[test]
+    fn test_serializes_like_other_ids() {
+        setup();
+        let id = OrderedHash::new(158674u64);
+        assert_eq!(id.as_public_id(), HashedId(158674u64).as_public_id());
+        assert_eq!(OrderedHash::<u64>::parse_public_id("qKknODM7Ej").unwrap(), id);
+        for profile in [SerializationProfile::Internal, SerializationProfile::Debug] {
+            let (ordered, hashed) = with_profile(profile, || {
+                let ordered = serde_json::to_string(&id).unwrap();
+                (ordered, serde_json::to_string(&HashedId(158674u64)).unwrap())
+            });
+            assert_eq!(ordered, hashed);
+        }
+        let json = with_profile(SerializationProfile::Internal, || serde_json::to_string(&id));
+        assert_eq!(json.unwrap(), "158674");
+    }
+}
@@ -0,0 +1,40 @@
+mod test_prelude {
+    use serde_hash::prelude::*;
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct User {
+        #[serde(hash)]
+        id: u64,
+        #[serde(with = "vec_numeric")]
+        team_ids: Vec<u64>,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    struct Invoice {
+        #[hash]
+        id: u64,
+    }
+
+    #[test]
+    fn test_prelude_covers_the_macros() {
+        setup();
+        let user = User { id: 158674, team_ids: vec![1] };
+        let json = serde_json::to_string(&user).unwrap();
+        assert_eq!(json, r#"{"id":"qKknODM7Ej","team_ids":["mP5XbpEaQ9"]}"#);
+        assert_eq!(serde_json::from_str::<User>(&json).unwrap(), user);
+
+        let json = serde_json::to_string(&Invoice { id: 158674 }).unwrap();
+        assert_eq!(json, r#"{"id":"qKknODM7Ej"}"#);
+        assert_eq!(serde_json::from_str::<Invoice>(&json).unwrap(), Invoice { id: 158674 });
+    }
+}
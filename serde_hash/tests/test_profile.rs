@@ -0,0 +1,163 @@
+mod test_profile {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::profile::{Profiled, SerializationProfile, current_profile, with_profile};
+    use serde_hash::{HashIds, serde_hash};
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct User {
+        #[serde(hash)]
+        pub id: u64,
+        #[serde(hash)]
+        pub manager: Option<u32>,
+        #[serde(hash)]
+        pub groups: Vec<u32>,
+        #[serde(hash)]
+        pub wide: u128,
+        #[cfg(feature = "hmac")]
+        #[hash(redact)]
+        pub owner: u64,
+        #[hash(mask = "last2")]
+        pub card: u64,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct Account {
+        #[hash]
+        pub id: u64,
+        #[hash(min_length = 16)]
+        pub parent: Option<u64>,
+    }
+
+    fn user() -> User {
+        User {
+            id: 158674,
+            manager: None,
+            groups: vec![158674],
+            wide: 158674,
+            #[cfg(feature = "hmac")]
+            owner: 158674,
+            card: 158674,
+        }
+    }
+
+    #[test]
+    fn test_public_is_the_default() {
+        setup();
+        assert_eq!(current_profile(), SerializationProfile::Public);
+        let user = user();
+        assert_eq!(
+            serde_json::to_value(Profiled::new(SerializationProfile::Public, &user)).unwrap(),
+            serde_json::to_value(&user).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_internal_round_trip() {
+        setup();
+        let user = user();
+        let json =
+            serde_json::to_value(Profiled::new(SerializationProfile::Internal, &user)).unwrap();
+        assert_eq!(json["id"], 158674);
+        assert_eq!(json["groups"], serde_json::json!([158674]));
+        assert_eq!(json["wide"], 158674);
+        // Redacted and masked fields never leak the raw value
+        #[cfg(feature = "hmac")]
+        assert_eq!(json["owner"], serde_json::to_value(&user).unwrap()["owner"]);
+        assert_eq!(json["card"], "****74");
+
+        #[cfg(feature = "hmac")]
+        {
+            let read = with_profile(SerializationProfile::Internal, || {
+                let mut json = json.clone();
+                json["owner"] = 158674.into();
+                json["card"] = 158674.into();
+                serde_json::from_value::<User>(json)
+            });
+            assert!(read.is_err(), "redacted fields can't be read back");
+        }
+
+        let account = Account { id: 158674, parent: Some(7) };
+        let json = with_profile(SerializationProfile::Internal, || {
+            serde_json::to_string(&account).unwrap()
+        });
+        assert_eq!(json, r#"{"id":158674,"parent":7}"#);
+        let read = with_profile(SerializationProfile::Internal, || {
+            serde_json::from_str::<Account>(&json).unwrap()
+        });
+        assert_eq!(read, account);
+        // Outside the profile, numbers are rejected again
+        assert!(serde_json::from_str::<Account>(&json).is_err());
+    }
+
+    #[test]
+    fn test_internal_wide_values() {
+        setup();
+        #[serde_hash]
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Wide {
+            #[serde(hash)]
+            value: u128,
+        }
+        let wide = Wide { value: u128::MAX };
+        let bytes = with_profile(SerializationProfile::Internal, || {
+            bincode::serialize(&wide).unwrap()
+        });
+        assert_eq!(bytes, u128::MAX.to_le_bytes());
+        let read = with_profile(SerializationProfile::Internal, || {
+            bincode::deserialize::<Wide>(&bytes).unwrap()
+        });
+        assert_eq!(read, wide);
+    }
+
+    #[test]
+    fn test_internal_rejects_out_of_range() {
+        setup();
+        #[serde_hash]
+        #[derive(Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Small {
+            #[serde(hash)]
+            value: u8,
+        }
+        let err = with_profile(SerializationProfile::Internal, || {
+            serde_json::from_str::<Small>(r#"{"value":300}"#).unwrap_err()
+        });
+        assert!(err.to_string().contains("does not fit in u8"), "{}", err);
+    }
+
+    #[test]
+    fn test_debug() {
+        setup();
+        let json =
+            serde_json::to_value(Profiled::new(SerializationProfile::Debug, &user())).unwrap();
+        assert_eq!(json["id"], "158674 (qKknODM7Ej)");
+        assert_eq!(json["manager"], serde_json::Value::Null);
+        assert_eq!(json["groups"], serde_json::json!(["158674 (qKknODM7Ej)"]));
+    }
+
+    #[test]
+    fn test_profiles_nest_and_restore() {
+        setup();
+        with_profile(SerializationProfile::Internal, || {
+            with_profile(SerializationProfile::Debug, || {
+                assert_eq!(current_profile(), SerializationProfile::Debug);
+            });
+            assert_eq!(current_profile(), SerializationProfile::Internal);
+        });
+        let _ = std::panic::catch_unwind(|| {
+            with_profile(SerializationProfile::Internal, || panic!("inside a profile"))
+        });
+        assert_eq!(current_profile(), SerializationProfile::Public);
+    }
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "proto")]
+
+mod test_proto {
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::hashids::{SerdeHashOptions, encode_single};
+    use serde_hash::proto::ProtoId;
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    mod pb {
+        #[derive(Clone, PartialEq, Default, Debug)]
+        pub struct Order {
+            pub public_id: String,
+            pub customer_id: Option<String>,
+            pub item_ids: Vec<String>,
+            pub note: String,
+            pub created_at: i64,
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Order {
+        id: u64,
+        customer: Option<u16>,
+        items: Vec<u32>,
+        note: String,
+    }
+
+    serde_hash::proto_bridge! {
+        Order => pb::Order {
+            ids { id => public_id, customer => customer_id, items => item_ids }
+            fields { note => note }
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        setup();
+        let order = Order { id: 158674, customer: Some(7), items: vec![1, 2], note: "gift".into() };
+        let message = pb::Order::from(&order);
+        assert_eq!(message.public_id, "qKknODM7Ej");
+        assert_eq!(message.customer_id, Some(encode_single(7)));
+        assert_eq!(message.item_ids, [encode_single(1), encode_single(2)]);
+        assert_eq!(message.note, "gift");
+        assert_eq!(message.created_at, 0);
+        assert_eq!(Order::try_from(message).unwrap(), order);
+
+        let order = Order { customer: None, items: vec![], ..order };
+        assert_eq!(Order::try_from(pb::Order::from(&order)).unwrap(), order);
+    }
+
+    #[test]
+    fn test_errors_name_the_field() {
+        setup();
+        let message = pb::Order { public_id: "invalid".into(), ..Default::default() };
+        let error = Order::try_from(message).unwrap_err();
+        assert!(error.to_string().contains("'public_id'"), "{:#}", error);
+
+        let message = pb::Order {
+            public_id: "qKknODM7Ej".into(),
+            customer_id: Some(encode_single(70000)),
+            ..Default::default()
+        };
+        let error = Order::try_from(message).unwrap_err();
+        assert_eq!(
+            error,
+            SerdeHashError::InvalidHash(
+                "hash decodes to 70000 which does not fit in u16 field 'customer_id'".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_proto_id() {
+        setup();
+        assert_eq!(158674u64.to_proto(), "qKknODM7Ej");
+        assert_eq!(u64::from_proto("qKknODM7Ej".into(), "id").unwrap(), 158674);
+        let ids = vec![Some(1u32), None];
+        assert_eq!(Vec::<Option<u32>>::from_proto(ids.to_proto(), "ids").unwrap(), ids);
+    }
+}
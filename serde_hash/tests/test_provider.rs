@@ -0,0 +1,69 @@
+mod test_provider {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::context::HashContext;
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::hashids::{SerdeHashOptions, decode_single, encode_single};
+    use serde_hash::provider::set_options_provider;
+    use serde_hash::serde_hash;
+    use std::cell::Cell;
+    use std::sync::LazyLock;
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct User {
+        #[serde(hash)]
+        id: u64,
+    }
+
+    fn context(salt: &str) -> HashContext {
+        SerdeHashOptions::new()
+            .with_salt(salt)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .into_context()
+            .unwrap()
+    }
+
+    static DEFAULT: LazyLock<HashContext> = LazyLock::new(|| context("hello world"));
+    static OTHER: LazyLock<HashContext> = LazyLock::new(|| context("another salt"));
+
+    thread_local! {
+        /// Stands in for per-request state, e.g. which tenant a request belongs to.
+        static USE_OTHER: Cell<bool> = const { Cell::new(false) };
+    }
+
+    fn setup() {
+        let _ = set_options_provider(|| {
+            if USE_OTHER.get() { OTHER.clone() } else { DEFAULT.clone() }
+        });
+    }
+
+    #[test]
+    fn test_hashing_uses_the_provider() {
+        setup();
+        assert_eq!(encode_single(158674), "qKknODM7Ej");
+        let json = serde_json::to_string(&User { id: 158674 }).unwrap();
+        assert_eq!(json, r#"{"id":"qKknODM7Ej"}"#);
+
+        USE_OTHER.set(true);
+        let hash = encode_single(158674);
+        assert_eq!(hash, OTHER.encode_single(158674));
+        assert_ne!(hash, "qKknODM7Ej");
+        assert_eq!(decode_single(&hash).unwrap(), 158674);
+        USE_OTHER.set(false);
+    }
+
+    #[test]
+    fn test_provider_is_set_once() {
+        setup();
+        assert_eq!(
+            set_options_provider(DEFAULT.clone()),
+            Err(SerdeHashError::AlreadyConfigured)
+        );
+        let result = SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .try_build();
+        assert_eq!(result, Err(SerdeHashError::AlreadyConfigured));
+    }
+}
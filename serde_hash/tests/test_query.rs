@@ -0,0 +1,129 @@
+mod test_query {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::hashids::{SerdeHashOptions, encode_single};
+    use serde_hash::{HashIds, serde_hash};
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Query {
+        #[serde(hash)]
+        pub a: u64,
+        pub name: String,
+        #[serde(hash)]
+        pub manager: Option<u32>,
+        pub limit: Option<u8>,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct LegacyQuery {
+        #[hash]
+        pub a: u64,
+        pub name: String,
+        #[hash]
+        pub manager: Option<u32>,
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Filter {
+        #[serde(hash)]
+        pub ids: Vec<u64>,
+        pub owner: Owner,
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Owner {
+        #[serde(hash)]
+        pub id: u64,
+        #[serde(hash)]
+        pub team: Option<u64>,
+    }
+
+    #[test]
+    fn test_urlencoded() {
+        setup();
+        let query = "a=qKknODM7Ej&name=Dan";
+        let expected = Query {
+            a: 158674,
+            name: "Dan".to_string(),
+            manager: None,
+            limit: None,
+        };
+        assert_eq!(
+            serde_urlencoded::from_str::<Query>(query).unwrap(),
+            expected
+        );
+
+        let query = format!("name=Dan&manager={}&limit=5&a=qKknODM7Ej", encode_single(7));
+        let decoded: Query = serde_urlencoded::from_str(&query).unwrap();
+        assert_eq!(decoded.manager, Some(7));
+        assert_eq!(decoded.limit, Some(5));
+        assert_eq!(
+            serde_urlencoded::from_str::<Query>(&serde_urlencoded::to_string(&decoded).unwrap())
+                .unwrap(),
+            decoded
+        );
+
+        assert_eq!(
+            serde_urlencoded::from_str::<LegacyQuery>(query.as_str()).unwrap(),
+            LegacyQuery {
+                a: 158674,
+                name: "Dan".to_string(),
+                manager: Some(7),
+            }
+        );
+        assert!(serde_urlencoded::from_str::<Query>("a=%21%21&name=Dan").is_err());
+    }
+
+    #[test]
+    fn test_qs() {
+        setup();
+        let query = "a=qKknODM7Ej&name=Dan";
+        assert_eq!(
+            serde_qs::from_str::<LegacyQuery>(query).unwrap(),
+            LegacyQuery {
+                a: 158674,
+                name: "Dan".to_string(),
+                manager: None,
+            }
+        );
+
+        let (one, two) = (encode_single(1), encode_single(2));
+        let query = format!(
+            "ids[0]={}&ids[1]={}&owner[id]=qKknODM7Ej&owner[team]={}",
+            one, two, one
+        );
+        let expected = Filter {
+            ids: vec![1, 2],
+            owner: Owner {
+                id: 158674,
+                team: Some(1),
+            },
+        };
+        assert_eq!(serde_qs::from_str::<Filter>(&query).unwrap(), expected);
+        let encoded = serde_qs::to_string(&expected).unwrap();
+        assert_eq!(serde_qs::from_str::<Filter>(&encoded).unwrap(), expected);
+
+        let query = format!("ids[]={}&owner[id]=qKknODM7Ej", two);
+        assert_eq!(
+            serde_qs::from_str::<Filter>(&query).unwrap(),
+            Filter {
+                ids: vec![2],
+                owner: Owner {
+                    id: 158674,
+                    team: None,
+                },
+            }
+        );
+    }
+}
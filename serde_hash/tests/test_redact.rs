@@ -0,0 +1,72 @@
+#![cfg(feature = "hmac")]
+mod test_redact {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::{HashIds, serde_hash};
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TestDataRedacted {
+        #[hash(redact)]
+        pub user: u64,
+        #[hash(redact, accept_raw)]
+        pub sessions: Vec<u32>,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestDeriveRedacted {
+        #[hash(redact, accept_raw)]
+        pub user: Option<u64>,
+        pub event: String,
+    }
+
+    #[test]
+    fn test_redact() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let data = TestDataRedacted {
+            user: 158674,
+            sessions: vec![1, 2],
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let digest = value["user"].as_str().unwrap();
+        assert_eq!(digest.len(), 32);
+        assert!(digest.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(digest, serde_hash::hashids::encode_single(158674));
+        // Digests are stable
+        assert_eq!(json, serde_json::to_string(&data).unwrap());
+        // Redacted values can't be read back
+        let err = serde_json::from_str::<TestDataRedacted>(&json).unwrap_err();
+        assert!(err.to_string().contains("redacted"));
+    }
+
+    #[test]
+    fn test_derive_redact() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let data = TestDeriveRedacted {
+            user: Some(158674),
+            event: "login".to_string(),
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"user":"{}","event":"login"}}"#,
+                serde_hash::hashids::redact_single(158674)
+            )
+        );
+        let raw: TestDeriveRedacted =
+            serde_json::from_str(r#"{"user":158674,"event":"login"}"#).unwrap();
+        assert_eq!(raw, data);
+    }
+}
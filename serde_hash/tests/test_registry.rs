@@ -0,0 +1,85 @@
+#![cfg(feature = "registry")]
+
+mod test_registry {
+    use serde_hash::error::{SerdeHashError, WeakSaltError};
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::registry::SaltRegistry;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn registry() -> SaltRegistry<String> {
+        let base = SerdeHashOptions::new()
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890");
+        SaltRegistry::new(base).unwrap()
+    }
+
+    #[test]
+    fn test_tenants_hash_with_their_own_salt() {
+        let registry = registry();
+        registry.register("acme".to_string(), "hello world").unwrap();
+        registry.register("globex".to_string(), "another salt").unwrap();
+        assert_eq!(registry.len(), 2);
+
+        assert_eq!(registry.encode("acme", 158674).unwrap(), "qKknODM7Ej");
+        assert_eq!(registry.decode("acme", "qKknODM7Ej").unwrap(), 158674);
+        let hash = registry.encode("globex", 158674).unwrap();
+        assert_ne!(hash, "qKknODM7Ej");
+        assert_ne!(registry.decode("acme", &hash).ok(), Some(158674));
+        assert!(registry.encode("initech", 1).is_err());
+    }
+
+    #[test]
+    fn test_register_replaces_and_evict_removes() {
+        let registry = registry();
+        registry.register("acme".to_string(), "another salt").unwrap();
+        registry.register("acme".to_string(), "hello world").unwrap();
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.encode("acme", 158674).unwrap(), "qKknODM7Ej");
+
+        assert!(registry.evict("acme"));
+        assert!(!registry.evict("acme"));
+        assert!(registry.is_empty());
+        assert!(registry.context("acme").is_none());
+
+        registry.register("acme".to_string(), "hello world").unwrap();
+        registry.register("globex".to_string(), "another salt").unwrap();
+        registry.retain(|tenant| tenant != "globex");
+        assert!(registry.context("acme").is_some());
+        assert!(registry.context("globex").is_none());
+    }
+
+    #[test]
+    fn test_rejects_weak_salts() {
+        let registry = SaltRegistry::new(SerdeHashOptions::new()).unwrap();
+        assert_eq!(
+            registry.register("acme", "short"),
+            Err(SerdeHashError::WeakSalt(WeakSaltError::TooShort { length: 5, min: 16 }))
+        );
+        assert!(registry.is_empty());
+    }
+
+    #[test]
+    fn test_concurrent_updates() {
+        let registry = Arc::new(registry());
+        let threads: Vec<_> = (0..8u64)
+            .map(|i| {
+                let registry = registry.clone();
+                thread::spawn(move || {
+                    let tenant = format!("tenant-{}", i);
+                    for round in 0..50 {
+                        registry.register(tenant.clone(), format!("salt {} {}", i, round)).unwrap();
+                        let hash = registry.encode(&tenant, i).unwrap();
+                        assert_eq!(registry.decode(&tenant, &hash).unwrap(), i);
+                    }
+                    registry.evict(&tenant);
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert!(registry.is_empty());
+    }
+}
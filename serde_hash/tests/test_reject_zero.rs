@@ -0,0 +1,63 @@
+mod test_reject_zero {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::hashids::{SerdeHashOptions, decode_single, encode_single};
+    use serde_hash::{HashIds, serde_hash};
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .with_reject_zero(true)
+            .build();
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Order {
+        #[hash]
+        id: u64,
+        #[hash]
+        customer_ids: Vec<Option<u32>>,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    struct OrderLine {
+        #[hash(pack_with = "line")]
+        order: u64,
+        line: u16,
+    }
+
+    #[test]
+    fn test_rejects_zero_in_every_hash_field() {
+        setup();
+        for order in [
+            Order { id: 0, customer_ids: vec![] },
+            Order { id: 1, customer_ids: vec![None, Some(0)] },
+        ] {
+            let error = serde_json::to_string(&order).unwrap_err();
+            assert!(error.to_string().contains("refusing to hash 0"), "{}", error);
+        }
+        for line in [OrderLine { order: 0, line: 1 }, OrderLine { order: 1, line: 0 }] {
+            let error = serde_json::to_string(&line).unwrap_err();
+            assert!(error.to_string().contains("refusing to hash 0"), "{}", error);
+        }
+        assert!(serde_json::to_string(&OrderLine { order: 1, line: 1 }).is_ok());
+    }
+
+    #[test]
+    fn test_other_values_round_trip() {
+        setup();
+        let order = Order { id: 158674, customer_ids: vec![None, Some(1)] };
+        let json = serde_json::to_string(&order).unwrap();
+        assert_eq!(json, r#"{"id":"qKknODM7Ej","customer_ids":[null,"mP5XbpEaQ9"]}"#);
+        assert_eq!(serde_json::from_str::<Order>(&json).unwrap(), order);
+    }
+
+    #[test]
+    fn test_encoding_functions_accept_zero() {
+        setup();
+        assert_eq!(decode_single(encode_single(0)).unwrap(), 0);
+    }
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "rkyv")]
+
+mod test_rkyv {
+    use rkyv::rancor::Error;
+    use serde::{Deserialize, Serialize};
+    use serde_hash::HashDisplay;
+    use serde_hash::hashids::SerdeHashOptions;
+    #[cfg(feature = "hmac")]
+    use serde_hash::orderable::Orderable;
+    use serde_hash::ordered::OrderedHash;
+    use serde_hash::{HashIds, HashedId, serde_hash};
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+    #[derive(Debug, PartialEq)]
+    struct User {
+        #[hash]
+        id: u64,
+        #[hash]
+        team_ids: Vec<u32>,
+        name: String,
+    }
+
+    #[derive(HashIds, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize, Debug, PartialEq)]
+    #[serde(transparent)]
+    struct UserId {
+        #[hash]
+        id: u64,
+    }
+
+    #[test]
+    fn test_archives_store_raw_ids() {
+        setup();
+        let user = User { id: 158674, team_ids: vec![1, 2], name: "Alice".to_string() };
+        assert!(serde_json::to_string(&user).unwrap().contains("qKknODM7Ej"));
+
+        let bytes = rkyv::to_bytes::<Error>(&user).unwrap();
+        let archived = rkyv::access::<ArchivedUser, Error>(&bytes).unwrap();
+        assert_eq!(archived.id, 158674);
+        assert_eq!(archived.team_ids.as_slice(), [1, 2]);
+        assert_eq!(rkyv::deserialize::<User, Error>(archived).unwrap(), user);
+
+        let id = UserId { id: 158674 };
+        assert_eq!(serde_json::to_string(&id).unwrap(), "\"qKknODM7Ej\"");
+        let bytes = rkyv::to_bytes::<Error>(&id).unwrap();
+        let archived = rkyv::access::<ArchivedUserId, Error>(&bytes).unwrap();
+        assert_eq!(archived.id, 158674);
+    }
+
+    #[test]
+    fn test_library_newtypes_archive_the_number() {
+        setup();
+        let raw = rkyv::to_bytes::<Error>(&158674u64).unwrap();
+        for bytes in [
+            rkyv::to_bytes::<Error>(&OrderedHash::new(158674u64)).unwrap(),
+            #[cfg(feature = "hmac")]
+            rkyv::to_bytes::<Error>(&Orderable::new(158674u64)).unwrap(),
+            rkyv::to_bytes::<Error>(&HashDisplay(158674)).unwrap(),
+            rkyv::to_bytes::<Error>(&HashedId(158674u64)).unwrap(),
+        ] {
+            assert_eq!(bytes.as_slice(), raw.as_slice());
+        }
+
+        let id = OrderedHash::new(158674u64);
+        let bytes = rkyv::to_bytes::<Error>(&id).unwrap();
+        let archived = rkyv::access::<rkyv::Archived<OrderedHash<u64>>, Error>(&bytes).unwrap();
+        assert_eq!(rkyv::deserialize::<OrderedHash<u64>, Error>(archived).unwrap(), id);
+        assert_eq!(serde_json::to_string(&id).unwrap(), "\"qKknODM7Ej\"");
+    }
+}
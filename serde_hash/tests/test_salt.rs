@@ -0,0 +1,111 @@
+mod test_salt {
+    use serde_hash::hashids::SerdeHashOptions;
+    #[cfg(feature = "rand")]
+    use rand::SeedableRng;
+    #[cfg(feature = "rand")]
+    use rand::rngs::StdRng;
+    use serde_hash::error::{SerdeHashError, WeakSaltError};
+    #[cfg(feature = "hkdf")]
+    use serde_hash::salt::derive_salt;
+    #[cfg(feature = "rand")]
+    use serde_hash::salt::{generate_salt, generate_salt_len, generate_salt_with};
+    use serde_hash::salt::{TESTING_SALT, estimate_entropy, validate_salt};
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_generate_salt() {
+        let salt = generate_salt();
+        assert_eq!(salt.len(), 32);
+        assert!(salt.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_ne!(salt, generate_salt());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_generate_salt_len() {
+        for length in [0, 8, 64] {
+            let salt = generate_salt_len(length);
+            assert_eq!(salt.len(), length);
+            assert!(salt.chars().all(|c| c.is_ascii_alphanumeric()));
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_generate_salt_with() {
+        let salt = generate_salt_with(&mut StdRng::seed_from_u64(42));
+        assert_eq!(salt.len(), 32);
+        assert_eq!(salt, generate_salt_with(&mut StdRng::seed_from_u64(42)));
+        assert_ne!(salt, generate_salt_with(&mut StdRng::seed_from_u64(43)));
+    }
+
+    #[test]
+    fn test_validate_salt() {
+        assert_eq!(
+            validate_salt("hello world"),
+            Err(WeakSaltError::TooShort { length: 11, min: 16 })
+        );
+        assert_eq!(
+            validate_salt("abababababababababab"),
+            Err(WeakSaltError::TooFewDistinct { distinct: 2, min: 8 })
+        );
+        assert!(validate_salt("replace-with-a-random-salt").is_ok());
+        #[cfg(feature = "rand")]
+        assert!(validate_salt(&generate_salt()).is_ok());
+        #[cfg(feature = "hkdf")]
+        assert!(validate_salt(&derive_salt(b"master secret", "orders")).is_ok());
+
+        // Weak salts are rejected when building, unless explicitly allowed.
+        let err = SerdeHashOptions::new().with_salt("hello world").try_build().unwrap_err();
+        assert!(matches!(err, SerdeHashError::WeakSalt(WeakSaltError::TooShort { .. })));
+        assert!(err.to_string().contains("at least 16 characters"), "{}", err);
+    }
+
+    #[test]
+    fn test_testing_salt_is_opt_in() {
+        assert_eq!(validate_salt(TESTING_SALT), Err(WeakSaltError::TestingSalt));
+        assert_eq!(SerdeHashOptions::for_tests().salt(), TESTING_SALT);
+        assert_ne!(SerdeHashOptions::new().salt(), TESTING_SALT);
+
+        let err = SerdeHashOptions::new().with_salt(TESTING_SALT).try_build().unwrap_err();
+        assert_eq!(err, SerdeHashError::WeakSalt(WeakSaltError::TestingSalt));
+    }
+
+    #[test]
+    fn test_estimate_entropy() {
+        assert_eq!(estimate_entropy(""), 0.0);
+        assert_eq!(estimate_entropy("aaaaaaaa"), 0.0);
+        assert_eq!(estimate_entropy("abcdefgh"), 24.0);
+        #[cfg(feature = "rand")]
+        {
+            assert!(estimate_entropy(&generate_salt()) > 100.0);
+            assert!(estimate_entropy("hello world") < estimate_entropy(&generate_salt()));
+        }
+    }
+
+    #[cfg(feature = "hkdf")]
+    #[test]
+    fn test_derive_salt() {
+        // HKDF-SHA256 with no extract salt, info = context, 32 bytes of output.
+        assert_eq!(
+            derive_salt(b"master secret", "orders/production"),
+            "523fbfcdcd4a8628586b2deb03e71a8ed49f6115bae7afc12aaeef1923cb5cc2"
+        );
+        assert_ne!(
+            derive_salt(b"master secret", "orders/production"),
+            derive_salt(b"other secret", "orders/production")
+        );
+
+        let options = SerdeHashOptions::new().with_derived_salt("master secret", "orders/staging");
+        assert_eq!(options.salt(), derive_salt("master secret", "orders/staging"));
+    }
+
+    // The salt is wiped on drop in a private field, so the options themselves have no
+    // `Drop` impl and their public fields can be moved out with or without `zeroize`.
+    #[test]
+    fn test_public_fields_can_be_moved_out() {
+        let options = SerdeHashOptions::new().with_field_patterns(["*_id"]);
+        let SerdeHashOptions { field_patterns, min_length, .. } = options;
+        assert_eq!((field_patterns, min_length), (vec!["*_id".to_string()], 8));
+    }
+}
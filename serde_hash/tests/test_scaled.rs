@@ -0,0 +1,80 @@
+mod test_scaled {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::hashids::{SerdeHashOptions, encode_single};
+    use serde_hash::scaled::{decode_scaled, encode_scaled};
+    use serde_hash::{HashIds, serde_hash};
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Invoice {
+        #[serde(hash)]
+        pub id: u64,
+        #[hash(scale = 100)]
+        pub total: f64,
+        #[hash(scale = 100)]
+        pub discount: Option<f32>,
+        #[hash(scale = 1_000_000)]
+        pub route: Vec<Vec<f64>>,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct Position {
+        #[hash]
+        pub id: u64,
+        #[hash(scale = 1_000_000)]
+        pub latitude: f64,
+        #[hash(scale = 1_000_000, suffix = "_hash", omit_raw)]
+        pub longitude: f64,
+        #[hash(scale = 10)]
+        pub accuracy: Option<f64>,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        setup();
+        let invoice = Invoice {
+            id: 1,
+            total: 1586.74,
+            discount: Some(-0.5),
+            route: vec![vec![48.858844, -2.294351], vec![]],
+        };
+        let json = serde_json::to_value(&invoice).unwrap();
+        // 1586.74 is stored as the zigzag-encoded 158674
+        assert_eq!(json["total"], encode_single(158674 * 2));
+        assert_eq!(serde_json::from_value::<Invoice>(json).unwrap(), invoice);
+
+        let position = Position { id: 1, latitude: -33.868820, longitude: 151.209296, accuracy: None };
+        let json = serde_json::to_string(&position).unwrap();
+        assert!(json.contains("\"longitude_hash\""), "{}", json);
+        assert_eq!(serde_json::from_str::<Position>(&json).unwrap(), position);
+    }
+
+    #[test]
+    fn test_rounds_to_scale() {
+        setup();
+        let hash = encode_scaled(12.345678, 100).unwrap();
+        assert_eq!(decode_scaled(&hash, 100).unwrap(), 12.35);
+        assert_eq!(hash, encode_scaled(12.35, 100).unwrap());
+        assert_eq!(decode_scaled(encode_scaled(-0.01, 100).unwrap(), 100).unwrap(), -0.01);
+    }
+
+    #[test]
+    fn test_rejects_unrepresentable() {
+        setup();
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 1e18] {
+            assert!(encode_scaled(value, 100).is_err(), "{}", value);
+        }
+        let invoice = Invoice { id: 1, total: f64::NAN, discount: None, route: vec![] };
+        let err = serde_json::to_string(&invoice).unwrap_err();
+        assert!(err.to_string().contains("can't be hashed at a scale of 100"), "{}", err);
+    }
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "serde_with")]
+
+mod test_serde_as {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::serde_as::HashId;
+    use serde_hash::serde_hash;
+    use serde_with::serde_as;
+    use std::collections::BTreeMap;
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[serde_as]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Team {
+        #[serde_as(as = "HashId")]
+        pub id: u64,
+        #[serde_as(as = "Option<HashId>")]
+        pub owner: Option<u32>,
+        #[serde_as(as = "Vec<Option<HashId>>")]
+        pub members: Vec<Option<u64>>,
+        #[serde_as(as = "BTreeMap<HashId, _>")]
+        pub roles: BTreeMap<u64, String>,
+        #[serde_as(as = "[HashId; 2]")]
+        pub pair: [u16; 2],
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct Plain {
+        #[serde(hash)]
+        pub id: u64,
+    }
+
+    #[test]
+    fn test_nested_combinators() {
+        setup();
+        let team = Team {
+            id: 158674,
+            owner: None,
+            members: vec![Some(158674), None],
+            roles: BTreeMap::from([(158674, "owner".to_string())]),
+            pair: [1, 2],
+        };
+        let json = serde_json::to_value(&team).unwrap();
+        assert_eq!(json["id"], "qKknODM7Ej");
+        assert_eq!(json["owner"], serde_json::Value::Null);
+        assert_eq!(json["members"], serde_json::json!(["qKknODM7Ej", null]));
+        assert_eq!(json["roles"], serde_json::json!({ "qKknODM7Ej": "owner" }));
+        assert_eq!(serde_json::from_value::<Team>(json).unwrap(), team);
+    }
+
+    #[test]
+    fn test_matches_serde_hash() {
+        setup();
+        #[serde_as]
+        #[derive(Serialize)]
+        struct Adapted {
+            #[serde_as(as = "HashId")]
+            id: u64,
+        }
+        assert_eq!(
+            serde_json::to_string(&Adapted { id: 158674 }).unwrap(),
+            serde_json::to_string(&Plain { id: 158674 }).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid() {
+        setup();
+        #[serde_as]
+        #[derive(Deserialize, Debug)]
+        struct Small {
+            #[serde_as(as = "HashId")]
+            #[allow(dead_code)]
+            id: u8,
+        }
+        assert!(serde_json::from_str::<Small>(r#"{"id":"not a hash"}"#).is_err());
+
+        let err = serde_json::from_str::<Small>(r#"{"id":"qKknODM7Ej"}"#).unwrap_err();
+        assert!(err.to_string().contains("does not fit in u8"), "{}", err);
+    }
+}
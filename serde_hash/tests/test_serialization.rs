@@ -1,7 +1,8 @@
 mod test_serialization {
     use serde::{Deserialize, Serialize};
     use serde_hash::hashids::SerdeHashOptions;
-    use serde_hash::serde_hash;
+    use serde_hash::{HasHashId, HashIds, HashedFields, serde_hash};
+    use std::borrow::Cow;
 
     #[serde_hash]
     #[derive(Serialize, Deserialize, Debug, PartialEq)]
@@ -16,6 +17,7 @@ mod test_serialization {
     fn test_basic() {
         SerdeHashOptions::new()
             .with_salt("hello world")
+            .with_weak_salt_allowed(true)
             .with_min_length(10)
             .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
             .build();
@@ -33,6 +35,7 @@ mod test_serialization {
     fn test_deserialization() {
         SerdeHashOptions::new()
             .with_salt("hello world")
+            .with_weak_salt_allowed(true)
             .with_min_length(10)
             .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
             .build();
@@ -62,6 +65,7 @@ mod test_serialization {
     fn test_serde_rename_with_hash() {
         SerdeHashOptions::new()
             .with_salt("hello world")
+            .with_weak_salt_allowed(true)
             .with_min_length(10)
             .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
             .build();
@@ -81,6 +85,7 @@ mod test_serialization {
     fn test_alias_deserialization() {
         SerdeHashOptions::new()
             .with_salt("hello world")
+            .with_weak_salt_allowed(true)
             .with_min_length(10)
             .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
             .build();
@@ -107,6 +112,7 @@ mod test_serialization {
     fn test_option_some() {
         SerdeHashOptions::new()
             .with_salt("hello world")
+            .with_weak_salt_allowed(true)
             .with_min_length(10)
             .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
             .build();
@@ -120,6 +126,7 @@ mod test_serialization {
     fn test_option_none() {
         SerdeHashOptions::new()
             .with_salt("hello world")
+            .with_weak_salt_allowed(true)
             .with_min_length(10)
             .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
             .build();
@@ -142,6 +149,7 @@ mod test_serialization {
     fn test_vec_hash() {
         SerdeHashOptions::new()
             .with_salt("hello world")
+            .with_weak_salt_allowed(true)
             .with_min_length(10)
             .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
             .build();
@@ -152,4 +160,1411 @@ mod test_serialization {
         let deserialized: TestDataWithVec = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized.ids, vec![1, 2, 3]);
     }
+
+    // Test custom transform hooks
+    fn to_base36(mut value: u64) -> String {
+        let digits = b"0123456789abcdefghijklmnopqrstuvwxyz";
+        let mut out = Vec::new();
+        loop {
+            out.push(digits[(value % 36) as usize]);
+            value /= 36;
+            if value == 0 {
+                break;
+            }
+        }
+        out.reverse();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn from_base36(value: &str) -> Result<u64, std::num::ParseIntError> {
+        u64::from_str_radix(value, 36)
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TestDataCustomCodec {
+        #[hash(encode_with = "to_base36", decode_with = "from_base36")]
+        pub legacy: Option<u64>,
+        #[hash(decode_with = "from_base36")]
+        pub imported: u32,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestDeriveCustomCodec {
+        #[hash(encode_with = "to_base36", decode_with = "from_base36")]
+        pub legacy: Vec<u64>,
+    }
+
+    #[test]
+    fn test_custom_codec() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let data = TestDataCustomCodec {
+            legacy: Some(158674),
+            imported: 36,
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        // The default encoder is used when only decode_with is given
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"legacy":"3efm","imported":"{}"}}"#,
+                serde_hash::hashids::encode_single(36)
+            )
+        );
+        let imported: TestDataCustomCodec =
+            serde_json::from_str(r#"{"legacy":null,"imported":"10"}"#).unwrap();
+        assert_eq!(imported.imported, 36);
+
+        let data = TestDeriveCustomCodec {
+            legacy: vec![35, 36],
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(json, r#"{"legacy":["z","10"]}"#);
+        let deserialized: TestDeriveCustomCodec = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, data);
+    }
+
+    // Test per-field minimum length
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TestDataMinLength {
+        #[hash(min_length = 16)]
+        pub share: u64,
+        #[hash(min_length = 4)]
+        pub short: Option<Vec<u64>>,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestDeriveMinLength {
+        #[hash(min_length = 16)]
+        pub share: u64,
+        #[hash]
+        pub id: u64,
+    }
+
+    #[test]
+    fn test_min_length_override() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let data = TestDataMinLength {
+            share: 158674,
+            short: Some(vec![1]),
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["share"].as_str().unwrap().len(), 16);
+        assert_eq!(value["short"][0].as_str().unwrap().len(), 4);
+        let deserialized: TestDataMinLength = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, data);
+
+        let data = TestDeriveMinLength {
+            share: 158674,
+            id: 158674,
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["share"].as_str().unwrap().len(), 16);
+        assert_eq!(value["id"], "qKknODM7Ej");
+        let deserialized: TestDeriveMinLength = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, data);
+    }
+
+    // Test #[serde(alias)] with the derive
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestDeriveAlias {
+        #[hash]
+        #[serde(alias = "userId", alias = "user")]
+        pub user_id: u64,
+        #[serde(alias = "fullName")]
+        pub name: String,
+    }
+
+    #[test]
+    fn test_derive_alias() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let expected = TestDeriveAlias {
+            user_id: 158674,
+            name: "Dan Smith".to_string(),
+        };
+        for json in [
+            r#"{"user_id":"qKknODM7Ej","name":"Dan Smith"}"#,
+            r#"{"userId":"qKknODM7Ej","fullName":"Dan Smith"}"#,
+            r#"{"user":"qKknODM7Ej","name":"Dan Smith"}"#,
+        ] {
+            let deserialized: TestDeriveAlias = serde_json::from_str(json).unwrap();
+            assert_eq!(deserialized, expected);
+        }
+        // Serialization always uses the field name
+        let json = serde_json::to_string(&expected).unwrap();
+        assert!(json.contains("\"user_id\""));
+    }
+
+    // Test that the derive keeps declaration order
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestDeriveOrder {
+        pub name: String,
+        #[hash]
+        pub id: u64,
+        pub age: u8,
+        #[hash]
+        pub friends: Vec<u64>,
+        #[hash]
+        pub manager: Option<u64>,
+    }
+
+    #[test]
+    fn test_derive_field_order() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let data = TestDeriveOrder {
+            name: "Dan Smith".to_string(),
+            id: 158674,
+            age: 47,
+            friends: vec![],
+            manager: None,
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(
+            json,
+            r#"{"name":"Dan Smith","id":"qKknODM7Ej","age":47,"friends":[],"manager":null}"#
+        );
+    }
+
+    // Test nested vectors and options of hashed ids
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TestGraph {
+        #[serde(hash)]
+        pub adjacency: Vec<Vec<u64>>,
+        #[serde(with = "serde_hash::serde_impl::vec_vec_numeric")]
+        pub explicit: Vec<Vec<u32>>,
+        #[serde(hash)]
+        pub layers: Option<Vec<Vec<u16>>>,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestDeriveGraph {
+        #[hash]
+        pub adjacency: Vec<Vec<u64>>,
+        #[hash]
+        pub optional: Vec<Option<u64>>,
+    }
+
+    #[test]
+    fn test_nested_vec() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let data = TestGraph {
+            adjacency: vec![vec![158674], vec![]],
+            explicit: vec![vec![158674]],
+            layers: Some(vec![vec![1, 2]]),
+        };
+        let json = serde_json::to_value(&data).unwrap();
+        assert_eq!(json["adjacency"], serde_json::json!([["qKknODM7Ej"], []]));
+        assert_eq!(json["explicit"], serde_json::json!([["qKknODM7Ej"]]));
+        assert_eq!(serde_json::from_value::<TestGraph>(json).unwrap(), data);
+
+        let derived = TestDeriveGraph {
+            adjacency: vec![vec![158674, 1]],
+            optional: vec![Some(158674), None],
+        };
+        let json = serde_json::to_value(&derived).unwrap();
+        assert_eq!(json["optional"], serde_json::json!(["qKknODM7Ej", null]));
+        assert_eq!(serde_json::from_value::<TestDeriveGraph>(json).unwrap(), derived);
+    }
+
+    // Test serde's own attributes on the derive's unhashed fields
+    fn default_role() -> String {
+        "member".to_string()
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TestAudit {
+        pub created_by: String,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct TestDeriveSerdeAttrs {
+        #[hash]
+        pub user_id: u64,
+        #[serde(rename = "displayName", skip_serializing_if = "String::is_empty")]
+        pub name: String,
+        #[serde(default = "default_role")]
+        pub role: String,
+        #[serde(skip)]
+        pub cached: Option<u32>,
+        #[serde(flatten)]
+        pub audit: TestAudit,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    #[serde(default)]
+    pub struct TestDeriveDefaults {
+        #[hash]
+        pub id: u64,
+        #[hash(suffix = "_hash")]
+        pub owner: u32,
+        pub name: String,
+    }
+
+    impl Default for TestDeriveDefaults {
+        fn default() -> Self {
+            Self { id: 1, owner: 2, name: "anonymous".to_string() }
+        }
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    pub struct TestDeriveStrict {
+        #[hash]
+        pub id: u64,
+    }
+
+    #[test]
+    fn test_derive_serde_attributes() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let data = TestDeriveSerdeAttrs {
+            user_id: 158674,
+            name: String::new(),
+            role: "admin".to_string(),
+            cached: Some(7),
+            audit: TestAudit { created_by: "Dan".to_string() },
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(json, r#"{"userId":"qKknODM7Ej","role":"admin","created_by":"Dan"}"#);
+        assert_eq!(TestDeriveSerdeAttrs::HASHED_FIELDS, ["userId"]);
+
+        let json = r#"{"userId":"qKknODM7Ej","displayName":"Dan","created_by":"Ann"}"#;
+        let decoded: TestDeriveSerdeAttrs = serde_json::from_str(json).unwrap();
+        assert_eq!(decoded.name, "Dan");
+        assert_eq!(decoded.role, "member");
+        assert_eq!(decoded.cached, None);
+        assert_eq!(decoded.audit.created_by, "Ann");
+
+        let decoded: TestDeriveDefaults = serde_json::from_str(r#"{"owner":158674}"#).unwrap();
+        assert_eq!(decoded, TestDeriveDefaults { owner: 158674, ..TestDeriveDefaults::default() });
+        let decoded: TestDeriveDefaults = serde_json::from_str("{}").unwrap();
+        assert_eq!(decoded, TestDeriveDefaults::default());
+
+        let strict = r#"{"id":"qKknODM7Ej","extra":1}"#;
+        let error = serde_json::from_str::<TestDeriveStrict>(strict).unwrap_err();
+        assert!(error.to_string().contains("unknown field `extra`"), "{}", error);
+    }
+
+    // Test container-level renaming in the derive
+    #[derive(HashIds, Debug, PartialEq)]
+    #[hash(rename_all = "kebab-case")]
+    pub struct TestDeriveRenameAll {
+        #[hash]
+        pub user_id: u64,
+        #[serde(alias = "display_name")]
+        pub display_name: String,
+        pub r#type: u8,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    #[hash(rename_all = "camelCase")]
+    pub struct TestDeriveCamel {
+        #[hash]
+        pub parent_group_id: Option<u64>,
+    }
+
+    #[test]
+    fn test_derive_rename_all() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let data = TestDeriveRenameAll {
+            user_id: 158674,
+            display_name: "Dan Smith".to_string(),
+            r#type: 1,
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(
+            json,
+            r#"{"user-id":"qKknODM7Ej","display-name":"Dan Smith","type":1}"#
+        );
+        assert_eq!(serde_json::from_str::<TestDeriveRenameAll>(&json).unwrap(), data);
+
+        let aliased = r#"{"user-id":"qKknODM7Ej","display_name":"Dan Smith","type":1}"#;
+        assert_eq!(serde_json::from_str::<TestDeriveRenameAll>(aliased).unwrap(), data);
+
+        let error = serde_json::from_str::<TestDeriveRenameAll>(r#"{"type":1}"#).unwrap_err();
+        assert!(error.to_string().contains("missing field `user-id`"));
+
+        let camel = TestDeriveCamel {
+            parent_group_id: Some(158674),
+        };
+        let json = serde_json::to_string(&camel).unwrap();
+        assert_eq!(json, r#"{"parentGroupId":"qKknODM7Ej"}"#);
+        assert_eq!(serde_json::from_str::<TestDeriveCamel>(&json).unwrap(), camel);
+    }
+
+    // Test the hashable alias with serde's own container attributes
+    #[serde_hash::hashable]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    pub struct TestHashable {
+        #[hash]
+        pub user_id: u64,
+        #[hash]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub group_id: Option<u64>,
+    }
+
+    #[test]
+    fn test_hashable() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let data = TestHashable {
+            user_id: 158674,
+            group_id: None,
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(json, r#"{"userId":"qKknODM7Ej"}"#);
+        assert_eq!(serde_json::from_str::<TestHashable>(&json).unwrap(), data);
+        assert!(serde_json::from_str::<TestHashable>(r#"{"userId":"qKknODM7Ej","x":1}"#).is_err());
+    }
+
+    // Test the legacy derive on structs without fields
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestUnit;
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestEmpty {}
+
+    #[test]
+    fn test_derive_fieldless() {
+        assert_eq!(serde_json::to_string(&TestUnit).unwrap(), "null");
+        assert_eq!(serde_json::from_str::<TestUnit>("null").unwrap(), TestUnit);
+        assert_eq!(serde_json::to_string(&TestEmpty {}).unwrap(), "{}");
+        assert_eq!(
+            serde_json::from_str::<TestEmpty>(r#"{"ignored":1}"#).unwrap(),
+            TestEmpty {}
+        );
+    }
+
+    // Test that decoded values too wide for the field are rejected, not truncated
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestNarrow {
+        #[hash]
+        pub age: u16,
+        #[hash]
+        pub scores: Vec<Option<u8>>,
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TestNarrowAttribute {
+        #[serde(hash)]
+        pub age: u16,
+    }
+
+    #[test]
+    fn test_checked_width() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let wide = serde_hash::hashids::encode_single(70000);
+        let small = serde_hash::hashids::encode_single(200);
+        let too_wide = serde_hash::hashids::encode_single(300);
+
+        let json = format!(r#"{{"age":"{}","scores":[]}}"#, wide);
+        let error = serde_json::from_str::<TestNarrow>(&json).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("hash decodes to 70000 which does not fit in u16 field 'age'"),
+            "{}",
+            error
+        );
+
+        let json = format!(r#"{{"age":"{}","scores":["{}",null,"{}"]}}"#, small, small, too_wide);
+        let error = serde_json::from_str::<TestNarrow>(&json).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("hash decodes to 300 which does not fit in u8 field 'scores'"),
+            "{}",
+            error
+        );
+
+        let json = format!(r#"{{"age":"{}","scores":["{}",null]}}"#, small, small);
+        assert_eq!(
+            serde_json::from_str::<TestNarrow>(&json).unwrap(),
+            TestNarrow {
+                age: 200,
+                scores: vec![Some(200), None],
+            }
+        );
+
+        let json = format!(r#"{{"age":"{}"}}"#, wide);
+        let error = serde_json::from_str::<TestNarrowAttribute>(&json).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("hash decodes to 70000 which does not fit in u16"),
+            "{}",
+            error
+        );
+    }
+
+    // Test the legacy derive inside enums that buffer their content
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestTagged {
+        #[hash]
+        pub id: u64,
+        #[hash]
+        pub friends: Vec<u32>,
+        #[hash]
+        pub boss: Option<u16>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(tag = "type")]
+    pub enum TestInternallyTagged {
+        Created(TestTagged),
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(tag = "type", content = "data")]
+    pub enum TestAdjacentlyTagged {
+        Created(TestTagged),
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(untagged)]
+    pub enum TestUntagged {
+        User(TestTagged),
+    }
+
+    #[test]
+    fn test_derive_in_buffered_enums() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let user = || TestTagged {
+            id: 158674,
+            friends: vec![1, 2],
+            boss: Some(3),
+        };
+
+        let event = TestInternallyTagged::Created(user());
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.starts_with(r#"{"type":"Created","id":"qKknODM7Ej","#), "{}", json);
+        assert_eq!(serde_json::from_str::<TestInternallyTagged>(&json).unwrap(), event);
+
+        let event = TestAdjacentlyTagged::Created(user());
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(serde_json::from_str::<TestAdjacentlyTagged>(&json).unwrap(), event);
+
+        let value = TestUntagged::User(user());
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(serde_json::from_str::<TestUntagged>(&json).unwrap(), value);
+    }
+
+    // Test the HasHashId impl generated by the derive
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestEntity {
+        #[hash]
+        pub friends: Vec<u64>,
+        #[hash]
+        pub id: u32,
+        #[hash]
+        pub manager: u64,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestPrimaryEntity {
+        #[hash]
+        pub manager: u64,
+        #[hash(primary, min_length = 16)]
+        pub id: u64,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestCustomEntity {
+        #[hash(encode_with = "to_base36", decode_with = "from_base36")]
+        pub id: u64,
+    }
+
+    #[test]
+    fn test_has_hash_id() {
+        use serde_hash::HasHashId;
+
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let entity = TestEntity {
+            friends: vec![1],
+            id: 158674,
+            manager: 2,
+        };
+        assert_eq!(entity.hash_id(), "qKknODM7Ej");
+        assert_eq!(TestEntity::from_hash_id("qKknODM7Ej").unwrap(), 158674);
+        assert!(TestEntity::from_hash_id("!!").is_err());
+
+        let entity = TestPrimaryEntity {
+            manager: 2,
+            id: 158674,
+        };
+        let json = serde_json::to_string(&entity).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(entity.hash_id(), value["id"].as_str().unwrap());
+        assert_eq!(entity.hash_id().len(), 16);
+        assert_eq!(TestPrimaryEntity::from_hash_id(&entity.hash_id()).unwrap(), 158674);
+
+        let entity = TestCustomEntity { id: 158674 };
+        assert_eq!(entity.hash_id(), "3efm");
+        assert_eq!(TestCustomEntity::from_hash_id("3efm").unwrap(), 158674);
+        assert!(TestCustomEntity::from_hash_id("!").is_err());
+    }
+
+    #[test]
+    fn test_public_id() {
+        use serde_hash::{HashDisplay, PublicId};
+
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let user = TestUser { id: 158674 };
+        assert_eq!(user.as_public_id(), "usr_qKknODM7Ej");
+        assert_eq!(TestUser::parse_public_id("usr_qKknODM7Ej").unwrap(), 158674);
+        let entity = TestCustomEntity { id: 158674 };
+        assert_eq!(entity.as_public_id(), "3efm");
+
+        assert_eq!(HashDisplay(158674).as_public_id(), "qKknODM7Ej");
+        assert_eq!(HashDisplay::parse_public_id("qKknODM7Ej").unwrap(), HashDisplay(158674));
+        #[cfg(feature = "cursor")]
+        {
+            use serde_hash::cursor::Cursor;
+            let cursor = Cursor::forward(158674u64);
+            assert_eq!(Cursor::parse_public_id(&cursor.as_public_id()).unwrap(), cursor);
+        }
+    }
+
+    #[test]
+    fn test_hashed_id() {
+        use serde_hash::{HashedId, PublicId};
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Invoice {
+            id: HashedId<u32>,
+            total: HashedId<u128>,
+        }
+
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let id = HashedId(158674u32);
+        assert_eq!(id.as_public_id(), "qKknODM7Ej");
+        assert_eq!(id.to_string(), "qKknODM7Ej");
+        assert_eq!(HashedId::<u32>::parse_public_id("qKknODM7Ej").unwrap(), id);
+        assert_eq!("qKknODM7Ej".parse::<HashedId<u32>>().unwrap(), id);
+        // Out-of-range hashes are rejected rather than truncated
+        assert!(HashedId::<u16>::parse_public_id("qKknODM7Ej").is_err());
+        assert!(HashedId::<u32>::parse_public_id("not a hash").is_err());
+
+        let invoice = Invoice { id, total: HashedId(u128::MAX) };
+        let json = serde_json::to_string(&invoice).unwrap();
+        assert!(json.starts_with(r#"{"id":"qKknODM7Ej","total":""#), "{}", json);
+        assert_eq!(serde_json::from_str::<Invoice>(&json).unwrap(), invoice);
+        let wide = HashedId(u128::MAX);
+        assert_eq!(HashedId::parse_public_id(&wide.as_public_id()).unwrap(), wide);
+    }
+
+    // Test type prefixes and the GlobalIdRegistry
+    #[derive(HashIds, Debug, PartialEq)]
+    #[hash(prefix = "usr_")]
+    pub struct TestUser {
+        #[hash]
+        pub id: u64,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    #[hash(prefix = "usr_org_")]
+    pub struct TestOrganization {
+        #[hash]
+        pub id: u64,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    #[hash(prefix = "usr_")]
+    pub struct TestOtherUser {
+        #[hash]
+        pub id: u64,
+    }
+
+    #[test]
+    fn test_global_id_registry() {
+        use serde_hash::HasHashId;
+        use serde_hash::entity::GlobalIdRegistry;
+
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let user = TestUser { id: 158674 };
+        assert_eq!(user.hash_id(), "usr_qKknODM7Ej");
+        assert_eq!(TestUser::from_hash_id("usr_qKknODM7Ej").unwrap(), 158674);
+        assert!(TestUser::from_hash_id("qKknODM7Ej").is_err());
+        assert_eq!(
+            serde_json::to_string(&user).unwrap(),
+            r#"{"id":"qKknODM7Ej"}"#
+        );
+
+        let mut registry = GlobalIdRegistry::new();
+        registry.register::<TestUser>().unwrap();
+        registry.register::<TestOrganization>().unwrap();
+        assert!(registry.register::<TestOtherUser>().is_err());
+        assert!(registry.register::<TestEntity>().is_err());
+
+        assert_eq!(
+            registry.resolve("usr_qKknODM7Ej").unwrap(),
+            ("TestUser", 158674)
+        );
+        let organization = TestOrganization { id: 7 }.hash_id();
+        assert_eq!(
+            registry.resolve(&organization).unwrap(),
+            ("TestOrganization", 7)
+        );
+        assert!(registry.resolve("inv_qKknODM7Ej").is_err());
+        assert!(registry.resolve("usr_!!").is_err());
+    }
+
+    // Test masked fields
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TestMasked {
+        #[serde(hash)]
+        pub id: u64,
+        #[hash(mask = "last4")]
+        pub account: u64,
+        #[hash(mask = "first2")]
+        pub routing: Option<u32>,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestDeriveMasked {
+        #[hash(mask = "last4")]
+        pub account: u64,
+        #[hash]
+        pub id: u64,
+    }
+
+    #[test]
+    fn test_masked_fields() {
+        use serde_hash::HasHashId;
+
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let data = TestMasked {
+            id: 158674,
+            account: 98761234,
+            routing: Some(12345),
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(json, r#"{"id":"qKknODM7Ej","account":"****1234","routing":"12***"}"#);
+        assert!(serde_json::from_str::<TestMasked>(&json).is_err());
+
+        let raw = r#"{"id":"qKknODM7Ej","account":98761234,"routing":12345}"#;
+        assert_eq!(serde_json::from_str::<TestMasked>(raw).unwrap(), data);
+
+        let short = TestMasked {
+            id: 1,
+            account: 12,
+            routing: None,
+        };
+        let json = serde_json::to_string(&short).unwrap();
+        assert!(json.contains(r#""account":"**""#), "{}", json);
+
+        let derived = TestDeriveMasked {
+            account: 98761234,
+            id: 158674,
+        };
+        let json = serde_json::to_string(&derived).unwrap();
+        assert_eq!(json, r#"{"account":"****1234","id":"qKknODM7Ej"}"#);
+        assert_eq!(derived.hash_id(), "qKknODM7Ej");
+        let raw = r#"{"account":98761234,"id":"qKknODM7Ej"}"#;
+        assert_eq!(serde_json::from_str::<TestDeriveMasked>(raw).unwrap(), derived);
+    }
+
+    // Test recursive types with boxed and nested children
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestCategory {
+        #[hash]
+        pub id: u64,
+        pub children: Vec<TestCategory>,
+        pub parent: Option<Box<TestCategory>>,
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TestTreeNode {
+        #[serde(hash)]
+        pub id: u64,
+        pub left: Option<Box<TestTreeNode>>,
+        pub right: Option<Box<TestTreeNode>>,
+    }
+
+    #[test]
+    fn test_recursive_types() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let leaf = |id| TestCategory {
+            id,
+            children: vec![],
+            parent: None,
+        };
+        let category = TestCategory {
+            id: 158674,
+            children: vec![
+                TestCategory {
+                    id: 2,
+                    children: vec![leaf(3)],
+                    parent: None,
+                },
+                leaf(4),
+            ],
+            parent: Some(Box::new(TestCategory {
+                id: 1,
+                children: vec![],
+                parent: Some(Box::new(leaf(0))),
+            })),
+        };
+        let json = serde_json::to_string(&category).unwrap();
+        assert!(json.starts_with(r#"{"id":"qKknODM7Ej","children":[{"id":""#), "{}", json);
+        assert_eq!(serde_json::from_str::<TestCategory>(&json).unwrap(), category);
+
+        let tree = TestTreeNode {
+            id: 1,
+            left: Some(Box::new(TestTreeNode {
+                id: 2,
+                left: None,
+                right: Some(Box::new(TestTreeNode {
+                    id: 158674,
+                    left: None,
+                    right: None,
+                })),
+            })),
+            right: None,
+        };
+        let json = serde_json::to_string(&tree).unwrap();
+        assert!(json.contains(r#""id":"qKknODM7Ej""#), "{}", json);
+        assert_eq!(serde_json::from_str::<TestTreeNode>(&json).unwrap(), tree);
+    }
+
+    // Test hashes written under a suffixed key
+    #[derive(HashIds, Debug, PartialEq)]
+    #[hash(rename_all = "camelCase")]
+    pub struct TestSuffixed {
+        #[hash(suffix = "Hash")]
+        pub user_id: u64,
+        #[hash(suffix = "Hash", omit_raw)]
+        pub group_ids: Vec<u32>,
+        pub name: String,
+    }
+
+    #[test]
+    fn test_suffixed_fields() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let data = TestSuffixed {
+            user_id: 158674,
+            group_ids: vec![158674],
+            name: "Dan".to_string(),
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(
+            json,
+            r#"{"userId":158674,"userIdHash":"qKknODM7Ej","groupIdsHash":["qKknODM7Ej"],"name":"Dan"}"#
+        );
+        assert_eq!(serde_json::from_str::<TestSuffixed>(&json).unwrap(), data);
+
+        // Clients can send either key while they migrate.
+        let old = r#"{"userId":158674,"groupIdsHash":["qKknODM7Ej"],"name":"Dan"}"#;
+        assert_eq!(serde_json::from_str::<TestSuffixed>(old).unwrap(), data);
+        let new = r#"{"userIdHash":"qKknODM7Ej","groupIdsHash":["qKknODM7Ej"],"name":"Dan"}"#;
+        assert_eq!(serde_json::from_str::<TestSuffixed>(new).unwrap(), data);
+
+        let missing = r#"{"userId":158674,"name":"Dan"}"#;
+        let err = serde_json::from_str::<TestSuffixed>(missing).unwrap_err();
+        assert!(err.to_string().contains("missing field `groupIdsHash`"), "{}", err);
+
+        // Positional formats read both entries and keep the hash.
+        {
+            let bytes = bincode::serialize(&data).unwrap();
+            assert_eq!(bincode::deserialize::<TestSuffixed>(&bytes).unwrap(), data);
+        }
+    }
+
+    // Test two fields packed into one hash
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestPacked {
+        #[hash(pack_with = "line")]
+        pub order: u64,
+        pub line: u16,
+        pub note: String,
+    }
+
+    #[test]
+    fn test_packed_fields() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let data = TestPacked {
+            order: 158674,
+            line: 3,
+            note: "gift".to_string(),
+        };
+        let key = serde_hash::hashids::encode(&[158674, 3]);
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(json, format!(r#"{{"order":"{}","note":"gift"}}"#, key));
+        assert_eq!(serde_json::from_str::<TestPacked>(&json).unwrap(), data);
+
+        // The key must hold exactly the two packed values.
+        let single = r#"{"order":"qKknODM7Ej","note":"gift"}"#;
+        let err = serde_json::from_str::<TestPacked>(single).unwrap_err();
+        assert!(err.to_string().contains("expected 2 values in packed field 'order'"), "{}", err);
+        let wide = serde_hash::hashids::encode(&[1, 70000]);
+        let wide = format!(r#"{{"order":"{}","note":"gift"}}"#, wide);
+        let err = serde_json::from_str::<TestPacked>(&wide).unwrap_err();
+        assert!(err.to_string().contains("does not fit in u16"), "{}", err);
+
+        let missing = r#"{"note":"gift"}"#;
+        let err = serde_json::from_str::<TestPacked>(missing).unwrap_err();
+        assert!(err.to_string().contains("missing field `order`"), "{}", err);
+
+        {
+            let bytes = bincode::serialize(&data).unwrap();
+            assert_eq!(bincode::deserialize::<TestPacked>(&bytes).unwrap(), data);
+        }
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestPackedWide {
+        #[hash(pack_with = "line")]
+        pub order: u128,
+        pub line: u16,
+    }
+
+    #[test]
+    fn test_packed_wide_fields() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        // Small values hash like any other packed pair.
+        let small = TestPackedWide { order: 158674, line: 3 };
+        let json = serde_json::to_string(&small).unwrap();
+        let key = serde_hash::hashids::encode(&[158674, 3]);
+        assert_eq!(json, format!(r#"{{"order":"{}"}}"#, key));
+        assert_eq!(serde_json::from_str::<TestPackedWide>(&json).unwrap(), small);
+
+        // Values above u64::MAX keep both limbs instead of being truncated.
+        let wide = TestPackedWide { order: u128::MAX, line: 3 };
+        let json = serde_json::to_string(&wide).unwrap();
+        let key = serde_hash::hashids::encode(&[u64::MAX, u64::MAX, 0, 3]);
+        assert_eq!(json, format!(r#"{{"order":"{}"}}"#, key));
+        assert_eq!(serde_json::from_str::<TestPackedWide>(&json).unwrap(), wide);
+
+        // The narrow field still rejects limbs that don't fit it.
+        let key = serde_hash::hashids::encode(&[0, 1, 1, 0]);
+        let err = serde_json::from_str::<TestPacked>(&format!(r#"{{"order":"{}","note":""}}"#, key))
+            .unwrap_err();
+        assert!(err.to_string().contains("does not fit in u16"), "{}", err);
+    }
+
+    // Test structs written as their only field
+    #[derive(HashIds, Debug, PartialEq)]
+    #[serde(transparent)]
+    pub struct TestUserId {
+        #[hash]
+        pub id: u64,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    #[hash(transparent)]
+    pub struct TestGroupIds {
+        #[hash]
+        pub ids: Vec<u64>,
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(transparent)]
+    pub struct TestOrderId {
+        #[serde(hash)]
+        pub id: u64,
+    }
+
+    #[test]
+    fn test_transparent_structs() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let user = TestUserId { id: 158674 };
+        let json = serde_json::to_string(&user).unwrap();
+        assert_eq!(json, r#""qKknODM7Ej""#);
+        assert_eq!(serde_json::from_str::<TestUserId>(&json).unwrap(), user);
+        let err = serde_json::from_str::<TestUserId>(r#""!!""#).unwrap_err();
+        assert!(err.to_string().contains("Failed to decode hash"), "{}", err);
+
+        let groups = TestGroupIds { ids: vec![158674] };
+        let json = serde_json::to_string(&groups).unwrap();
+        assert_eq!(json, r#"["qKknODM7Ej"]"#);
+        assert_eq!(serde_json::from_str::<TestGroupIds>(&json).unwrap(), groups);
+
+        let order = TestOrderId { id: 158674 };
+        let json = serde_json::to_string(&order).unwrap();
+        assert_eq!(json, r#""qKknODM7Ej""#);
+        assert_eq!(serde_json::from_str::<TestOrderId>(&json).unwrap(), order);
+    }
+
+    fn default_level() -> u8 {
+        3
+    }
+
+    // Test serde presence attributes on derived fields
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestPresence {
+        #[hash]
+        #[serde(default)]
+        pub id: u64,
+        #[hash]
+        #[serde(skip_serializing_if = "Vec::is_empty", default)]
+        pub tags: Vec<u64>,
+        #[serde(default = "default_level")]
+        pub level: u8,
+    }
+
+    #[test]
+    fn test_presence_attributes() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let data = TestPresence {
+            id: 158674,
+            tags: vec![],
+            level: 1,
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(json, r#"{"id":"qKknODM7Ej","level":1}"#);
+        assert_eq!(serde_json::from_str::<TestPresence>(&json).unwrap(), data);
+
+        let tagged = TestPresence {
+            tags: vec![158674],
+            ..data
+        };
+        let json = serde_json::to_string(&tagged).unwrap();
+        assert_eq!(json, r#"{"id":"qKknODM7Ej","tags":["qKknODM7Ej"],"level":1}"#);
+
+        let empty = serde_json::from_str::<TestPresence>("{}").unwrap();
+        assert_eq!(
+            empty,
+            TestPresence {
+                id: 0,
+                tags: vec![],
+                level: 3,
+            }
+        );
+    }
+
+    // Test u128 values above u64::MAX
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TestWideIds {
+        #[serde(hash)]
+        pub id: u128,
+        #[serde(hash)]
+        pub related: Vec<u128>,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestDeriveWideId {
+        #[hash]
+        pub id: u128,
+        #[hash]
+        pub small: u64,
+    }
+
+    #[test]
+    fn test_u128_values() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let data = TestWideIds {
+            id: u128::MAX,
+            related: vec![158674, 1 << 100],
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        assert!(json.contains(r#"["qKknODM7Ej","#));
+        assert_eq!(serde_json::from_str::<TestWideIds>(&json).unwrap(), data);
+
+        let derived = TestDeriveWideId {
+            id: u64::MAX as u128 + 1,
+            small: 158674,
+        };
+        let json = serde_json::to_string(&derived).unwrap();
+        assert_eq!(serde_json::from_str::<TestDeriveWideId>(&json).unwrap(), derived);
+
+        // A wide hash is rejected by narrower fields instead of being truncated.
+        let hash = serde_hash::hashids::encode(&[1, 0]);
+        let json = format!(r#"{{"id":"{}","small":"{}"}}"#, hash, hash);
+        let error = serde_json::from_str::<TestDeriveWideId>(&json).unwrap_err();
+        assert!(error.to_string().contains("which does not fit in u64 field 'small'"));
+    }
+
+    // Test borrowed fields next to hashed ones
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TestBorrowed<'a> {
+        #[serde(hash)]
+        pub id: u64,
+        #[serde(borrow)]
+        pub name: Cow<'a, str>,
+        pub tag: &'a str,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestDeriveBorrowed<'a, 'b> {
+        #[hash]
+        pub id: u64,
+        #[serde(borrow)]
+        pub name: Cow<'a, str>,
+        pub tag: &'b str,
+        pub owned: Cow<'b, str>,
+        pub inner: TestBorrowed<'a>,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestDeriveBytes<'a> {
+        #[hash]
+        pub id: u64,
+        #[serde(borrow)]
+        pub bytes: Cow<'a, [u8]>,
+    }
+
+    #[test]
+    fn test_borrowed_fields() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let json = r#"{"id":"qKknODM7Ej","name":"Dan","tag":"admin"}"#;
+        let data: TestBorrowed = serde_json::from_str(json).unwrap();
+        assert_eq!(data.id, 158674);
+        assert!(matches!(data.name, Cow::Borrowed("Dan")));
+        assert_eq!(serde_json::to_string(&data).unwrap(), json);
+
+        let data = TestDeriveBorrowed {
+            id: 158674,
+            name: Cow::Borrowed("Dan"),
+            tag: "admin",
+            owned: Cow::Borrowed("copied"),
+            inner: TestBorrowed {
+                id: 158674,
+                name: Cow::Borrowed("Ann"),
+                tag: "user",
+            },
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        let decoded: TestDeriveBorrowed = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, data);
+        assert!(matches!(decoded.name, Cow::Borrowed(_)));
+        assert!(matches!(decoded.inner.name, Cow::Borrowed(_)));
+        // Without `borrow`, a `Cow` is always copied, as with serde's derive.
+        assert!(matches!(decoded.owned, Cow::Owned(_)));
+
+        // Escaped strings can't be borrowed, so they're copied instead.
+        let json = json.replace("Dan", "D\\u0061n");
+        let decoded: TestDeriveBorrowed = serde_json::from_str(&json).unwrap();
+        assert!(matches!(decoded.name, Cow::Owned(ref name) if name == "Dan"));
+
+        // Bytes borrow from formats that store them as bytes, as with serde's derive.
+        let data = TestDeriveBytes { id: 158674, bytes: Cow::Borrowed(b"raw") };
+        let encoded = bincode::serialize(&data).unwrap();
+        let decoded: TestDeriveBytes = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(decoded, data);
+        assert!(matches!(decoded.bytes, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_hashed_fields() {
+        assert_eq!(TestData::HASHED_FIELDS, ["id"]);
+        assert_eq!(TestHashable::HASHED_FIELDS, ["userId", "groupId"]);
+        assert_eq!(TestDeriveRenameAll::HASHED_FIELDS, ["user-id"]);
+        assert_eq!(TestSuffixed::HASHED_FIELDS, ["userIdHash", "groupIdsHash"]);
+        assert_eq!(TestPacked::HASHED_FIELDS, ["order"]);
+        assert!(TestUserId::HASHED_FIELDS.is_empty());
+        assert!(TestDeriveBorrowed::is_hashed("id"));
+        assert!(!TestDeriveBorrowed::is_hashed("name"));
+    }
+
+    // Test hashing every numeric field by default
+    #[derive(HashIds, Debug, PartialEq)]
+    #[hash(all)]
+    pub struct TestDeriveAll {
+        pub id: u64,
+        pub group_ids: Vec<u32>,
+        pub parent_id: Option<u64>,
+        #[hash(skip)]
+        pub age: u8,
+        #[hash(min_length = 16)]
+        pub order_id: u64,
+        #[hash(pack_with = "line")]
+        pub invoice: u64,
+        pub line: u16,
+        pub name: String,
+    }
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[hash(all)]
+    #[serde(rename_all = "camelCase")]
+    pub struct TestAll {
+        pub user_id: u64,
+        pub tag_ids: Vec<u64>,
+        #[hash(skip)]
+        pub age: u8,
+        #[serde(with = "serde_hash::serde_impl::numeric_lenient")]
+        pub legacy_id: u64,
+        pub name: String,
+    }
+
+    #[test]
+    fn test_hash_all() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let data = TestDeriveAll {
+            id: 158674,
+            group_ids: vec![158674],
+            parent_id: None,
+            age: 47,
+            order_id: 158674,
+            invoice: 158674,
+            line: 3,
+            name: "Dan".to_string(),
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["id"], "qKknODM7Ej");
+        assert_eq!(value["group_ids"], serde_json::json!(["qKknODM7Ej"]));
+        assert_eq!(value["age"], 47);
+        assert_eq!(value["order_id"].as_str().unwrap().len(), 16);
+        assert_eq!(value["invoice"], serde_hash::hashids::encode(&[158674, 3]));
+        assert!(value.get("line").is_none());
+        assert_eq!(serde_json::from_str::<TestDeriveAll>(&json).unwrap(), data);
+        assert_eq!(
+            TestDeriveAll::HASHED_FIELDS,
+            ["id", "group_ids", "parent_id", "order_id", "invoice"]
+        );
+
+        let data = TestAll {
+            user_id: 158674,
+            tag_ids: vec![],
+            age: 47,
+            legacy_id: 158674,
+            name: "Dan".to_string(),
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(
+            json,
+            r#"{"userId":"qKknODM7Ej","tagIds":[],"age":47,"legacyId":"qKknODM7Ej","name":"Dan"}"#
+        );
+        assert_eq!(serde_json::from_str::<TestAll>(&json).unwrap(), data);
+        assert_eq!(TestAll::HASHED_FIELDS, ["userId", "tagIds"]);
+    }
+
+    // Test rejecting decoded values outside a range
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TestRange {
+        #[hash(range = "1..=u32::MAX as u64")]
+        pub id: u64,
+        #[hash(range = "..1000", min_length = 16)]
+        pub tag_ids: Vec<u16>,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestDeriveRange {
+        #[hash(range = "100..")]
+        pub id: u64,
+        #[hash(range = "1..=10")]
+        pub rank: Option<u8>,
+    }
+
+    #[test]
+    fn test_range() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let data = TestRange {
+            id: 158674,
+            tag_ids: vec![1, 999],
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        assert!(json.starts_with(r#"{"id":"qKknODM7Ej","tag_ids":[""#), "{}", json);
+        assert_eq!(serde_json::from_str::<TestRange>(&json).unwrap(), data);
+
+        let zero = serde_hash::hashids::encode_single(0);
+        let json = format!(r#"{{"id":"{}","tag_ids":[]}}"#, zero);
+        let err = serde_json::from_str::<TestRange>(&json).unwrap_err();
+        assert!(
+            err.to_string().contains("decoded value 0 is outside the allowed range 1..=u32::MAX as u64"),
+            "{}",
+            err
+        );
+        let large = serde_hash::hashids::encode_with_min_length(&[1000], 16);
+        let json = format!(r#"{{"id":"qKknODM7Ej","tag_ids":["{}"]}}"#, large);
+        assert!(serde_json::from_str::<TestRange>(&json).is_err());
+
+        let data = TestDeriveRange {
+            id: 158674,
+            rank: Some(3),
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(serde_json::from_str::<TestDeriveRange>(&json).unwrap(), data);
+        let low = serde_hash::hashids::encode_single(99);
+        let json = format!(r#"{{"id":"{}","rank":null}}"#, low);
+        assert!(serde_json::from_str::<TestDeriveRange>(&json).is_err());
+        assert!(TestDeriveRange::from_hash_id(&low).is_err());
+        assert_eq!(TestDeriveRange::from_hash_id("qKknODM7Ej").unwrap(), 158674);
+    }
+
+    // Test reading hashes from a field's former name
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TestAliasDecode {
+        #[hash(alias_decode = "legacy_id", alias_decode = "oldId")]
+        pub account_id: u64,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestDeriveAliasDecode {
+        #[hash(alias_decode = "legacy_id")]
+        #[serde(alias = "accountId")]
+        pub account_id: u64,
+        #[hash(suffix = "_hash", alias_decode = "owner_token")]
+        pub owner: u32,
+    }
+
+    #[test]
+    fn test_alias_decode() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+        let data = TestAliasDecode { account_id: 158674 };
+        for json in [
+            r#"{"account_id":"qKknODM7Ej"}"#,
+            r#"{"legacy_id":"qKknODM7Ej"}"#,
+            r#"{"oldId":"qKknODM7Ej"}"#,
+        ] {
+            assert_eq!(serde_json::from_str::<TestAliasDecode>(json).unwrap(), data);
+        }
+        // Only the new name is written
+        assert_eq!(serde_json::to_string(&data).unwrap(), r#"{"account_id":"qKknODM7Ej"}"#);
+
+        let data = TestDeriveAliasDecode { account_id: 158674, owner: 158674 };
+        for json in [
+            r#"{"legacy_id":"qKknODM7Ej","owner_token":"qKknODM7Ej"}"#,
+            r#"{"accountId":"qKknODM7Ej","owner":158674,"owner_hash":"qKknODM7Ej"}"#,
+        ] {
+            assert_eq!(serde_json::from_str::<TestDeriveAliasDecode>(json).unwrap(), data);
+        }
+        assert_eq!(
+            serde_json::to_string(&data).unwrap(),
+            r#"{"account_id":"qKknODM7Ej","owner":158674,"owner_hash":"qKknODM7Ej"}"#
+        );
+    }
 }
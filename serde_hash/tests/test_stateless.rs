@@ -0,0 +1,94 @@
+mod test_stateless {
+    use serde_hash::SerdeHashError;
+    use serde_hash::hashids::{SerdeHashOptions, decode_with, encode_with};
+
+    fn options() -> SerdeHashOptions {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+    }
+
+    #[test]
+    fn test_does_not_touch_global_options() {
+        let options = options();
+        assert_eq!(encode_with(&options, &[158674]).unwrap(), "qKknODM7Ej");
+        assert_eq!(decode_with(&options, "qKknODM7Ej").unwrap(), [158674]);
+        assert!(decode_with(&options, "!!").is_err());
+
+        // The global options are still unset, so the host application can configure them.
+        SerdeHashOptions::new()
+            .with_salt("host salt")
+            .with_weak_salt_allowed(true)
+            .try_build()
+            .unwrap();
+        assert_ne!(serde_hash::hashids::encode(&[158674]), "qKknODM7Ej");
+        assert_eq!(encode_with(&options, &[158674]).unwrap(), "qKknODM7Ej");
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        let options = SerdeHashOptions::new().with_alphabet("abc");
+        assert!(encode_with(&options, &[1]).is_err());
+        assert!(decode_with(&options, "abc").is_err());
+    }
+
+    #[test]
+    fn test_try_with_min_length() {
+        let options = SerdeHashOptions::new().try_with_min_length(12i64).unwrap();
+        assert_eq!(options.min_length, 12);
+        assert_eq!(
+            SerdeHashOptions::new().try_with_min_length(-1i64),
+            Err(SerdeHashError::MinLengthOutOfRange("-1".to_string()))
+        );
+        let error = SerdeHashOptions::new().try_with_min_length(u128::MAX).unwrap_err();
+        let expected = format!("minimum length {} doesn't fit in a usize", u128::MAX);
+        assert_eq!(error.to_string(), expected);
+    }
+
+    #[test]
+    fn test_preview() {
+        let hashes = options().preview(&[158674, 0, 1]).unwrap();
+        assert_eq!(hashes.len(), 3);
+        assert_eq!(hashes[0], "qKknODM7Ej");
+        assert!(hashes.iter().all(|hash| hash.len() == 10));
+        assert_eq!(options().preview(&[]).unwrap(), Vec::<String>::new());
+        assert!(SerdeHashOptions::new().with_alphabet("abc").preview(&[1]).is_err());
+    }
+
+    #[test]
+    fn test_clone_and_compare_options() {
+        let options = options();
+        let tenant = options.clone().with_salt("tenant");
+        assert_eq!(options.clone(), options);
+        assert_ne!(tenant, options);
+        assert_eq!(tenant.min_length, 10);
+    }
+
+    #[test]
+    fn test_debug_redacts_salt() {
+        let options = SerdeHashOptions::new().with_salt("very secret salt");
+        let debug = format!("{:?}", options);
+        assert!(debug.contains("<redacted>"), "{}", debug);
+        assert!(!debug.contains("very secret salt"), "{}", debug);
+
+        let context = options.into_context().unwrap();
+        let debug = format!("{:?}", context);
+        assert!(!debug.contains("very secret salt"), "{}", debug);
+    }
+
+    #[test]
+    fn test_into_context() {
+        let context = options().into_context().unwrap();
+        assert_eq!(context.encode_single(158674), "qKknODM7Ej");
+        assert_eq!(context.decode(context.encode(&[1, 2])).unwrap(), [1, 2]);
+        assert_eq!(context.options(), &options());
+
+        let shared = context.clone();
+        assert!(std::sync::Arc::ptr_eq(shared.encoder(), context.encoder()));
+        let handle = std::thread::spawn(move || shared.decode_single("qKknODM7Ej").unwrap());
+        assert_eq!(handle.join().unwrap(), 158674);
+
+        assert!(SerdeHashOptions::new().with_alphabet("abc").into_context().is_err());
+    }
+}
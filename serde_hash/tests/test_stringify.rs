@@ -0,0 +1,90 @@
+mod test_stringify {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::profile::{Profiled, SerializationProfile};
+    use serde_hash::{HasHashId, HashIds, HashedFields, serde_hash};
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TestData {
+        #[serde(hash)]
+        pub id: u64,
+        #[hash(stringify_only)]
+        pub snowflake: u64,
+        #[hash(stringify_only, alias_decode = "ledger_ids")]
+        pub entry_ids: Vec<u128>,
+        #[hash(stringify_only)]
+        pub parent: Option<u32>,
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    pub struct TestDerive {
+        #[hash(stringify_only)]
+        pub snowflake: u64,
+        #[hash]
+        pub id: u64,
+    }
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[test]
+    fn test_serde_hash_stringify() {
+        setup();
+        let data = TestData {
+            id: 158674,
+            snowflake: 9007199254740993,
+            entry_ids: vec![1, u128::MAX],
+            parent: None,
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"id":"qKknODM7Ej","snowflake":"9007199254740993","#,
+                r#""entry_ids":["1","340282366920938463463374607431768211455"],"parent":null}"#,
+            )
+        );
+        assert_eq!(serde_json::from_str::<TestData>(&json).unwrap(), data);
+        assert_eq!(TestData::HASHED_FIELDS, ["id"]);
+
+        let legacy = r#"{"id":"qKknODM7Ej","snowflake":"7","ledger_ids":[],"parent":"3"}"#;
+        let decoded = serde_json::from_str::<TestData>(legacy).unwrap();
+        assert_eq!((decoded.snowflake, decoded.parent), (7, Some(3)));
+    }
+
+    #[test]
+    fn test_same_under_every_profile() {
+        setup();
+        let data = TestDerive { snowflake: 42, id: 158674 };
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(json, r#"{"snowflake":"42","id":"qKknODM7Ej"}"#);
+        assert_eq!(serde_json::from_str::<TestDerive>(&json).unwrap(), data);
+        assert_eq!(data.hash_id(), "qKknODM7Ej");
+        assert_eq!(TestDerive::HASHED_FIELDS, ["id"]);
+
+        let internal = Profiled::new(SerializationProfile::Internal, &data);
+        assert_eq!(serde_json::to_string(&internal).unwrap(), r#"{"snowflake":"42","id":158674}"#);
+    }
+
+    #[test]
+    fn test_invalid_strings_are_rejected() {
+        setup();
+        for snowflake in [r#""""#, r#""+7""#, r#""-7""#, r#""7a""#, "7", r#""qKknODM7Ej""#] {
+            let json = format!(
+                r#"{{"id":"qKknODM7Ej","snowflake":{},"entry_ids":[],"parent":null}}"#,
+                snowflake
+            );
+            assert!(serde_json::from_str::<TestData>(&json).is_err(), "{}", json);
+        }
+        let json = r#"{"id":"qKknODM7Ej","snowflake":"1","entry_ids":[],"parent":"4294967296"}"#;
+        let error = serde_json::from_str::<TestData>(json).unwrap_err();
+        assert!(error.to_string().contains("does not fit in u32"), "{}", error);
+    }
+}
@@ -0,0 +1,76 @@
+#![cfg(any(feature = "tera", feature = "askama"))]
+
+mod test_templates {
+    use serde_hash::hashids::SerdeHashOptions;
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[cfg(feature = "tera")]
+    #[test]
+    fn test_tera() {
+        setup();
+        let mut tera = tera::Tera::default();
+        serde_hash::templates::tera::register(&mut tera);
+        tera.add_raw_template(
+            "users",
+            "{% for id in ids %}<a href=\"/users/{{ id | hashid }}\">{% endfor %}",
+        )
+        .unwrap();
+
+        let mut context = tera::Context::new();
+        context.insert("ids", &[158674u64]);
+        assert_eq!(
+            tera.render("users", &context).unwrap(),
+            "<a href=\"/users/qKknODM7Ej\">"
+        );
+    }
+
+    #[cfg(feature = "tera")]
+    #[test]
+    fn test_tera_rejects_strings() {
+        setup();
+        let mut tera = tera::Tera::default();
+        serde_hash::templates::tera::register(&mut tera);
+        tera.add_raw_template("user", "{{ id | hashid }}").unwrap();
+
+        let mut context = tera::Context::new();
+        context.insert("id", "158674");
+        assert!(tera.render("user", &context).is_err());
+    }
+
+    #[cfg(feature = "askama")]
+    mod filters {
+        pub use serde_hash::templates::askama::hashid;
+    }
+
+    #[cfg(feature = "askama")]
+    #[derive(askama::Template)]
+    #[template(
+        source = "<a href=\"/users/{{ id|hashid }}\">{% for id in ids %} {{ id|hashid }}{% endfor %}",
+        ext = "html"
+    )]
+    struct Users {
+        id: u64,
+        ids: Vec<u32>,
+    }
+
+    #[cfg(feature = "askama")]
+    #[test]
+    fn test_askama() {
+        use askama::Template;
+
+        setup();
+        let users = Users { id: 158674, ids: vec![158674, 158674] };
+        assert_eq!(
+            users.render().unwrap(),
+            "<a href=\"/users/qKknODM7Ej\"> qKknODM7Ej qKknODM7Ej"
+        );
+    }
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "testing")]
+
+mod test_testing {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::encoder::HashIdsEncoder;
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::serde_hash;
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct User {
+        #[serde(hash)]
+        pub id: u64,
+        #[serde(hash)]
+        pub group_ids: Vec<u32>,
+        #[hash(min_length = 16)]
+        pub share_id: u64,
+    }
+
+    #[test]
+    fn test_testing_salt_is_fixed() {
+        let first = HashIdsEncoder::new(&SerdeHashOptions::for_tests()).unwrap();
+        let second = HashIdsEncoder::new(&SerdeHashOptions::for_tests()).unwrap();
+        assert_eq!(first.encode_single(158674), second.encode_single(158674));
+    }
+
+    #[test]
+    fn test_placeholders() {
+        SerdeHashOptions::new().with_placeholders(true).build();
+        let user = User {
+            id: 158674,
+            group_ids: vec![1, 2],
+            share_id: 7,
+        };
+        let json = serde_json::to_string(&user).unwrap();
+        assert_eq!(
+            json,
+            r#"{"id":"hash(158674)","group_ids":["hash(1)","hash(2)"],"share_id":"hash(7)"}"#
+        );
+        assert_eq!(serde_json::from_str::<User>(&json).unwrap(), user);
+
+        assert_eq!(serde_hash::hashids::encode(&[1, 2]), "hash(1,2)");
+        assert_eq!(serde_hash::hashids::decode("hash(1,2)").unwrap(), [1, 2]);
+        assert!(serde_hash::hashids::decode("hash(1,x)").is_err());
+        assert!(serde_hash::hashids::decode("qKknODM7Ej").is_err());
+    }
+}
@@ -0,0 +1,88 @@
+// These checks run at compile time: if a public type stops being `Send`, `Sync` or unwind
+// safe, this file fails to build.
+mod test_thread_safety {
+    use serde_hash::alphabet::Alphabet;
+    use serde_hash::context::HashContext;
+    use serde_hash::diagnostics::Explanation;
+    use serde_hash::display::HashDisplay;
+    use serde_hash::encoder::{DecodeBackend, DecodeReport, HashIdsEncoder};
+    use serde_hash::entity::GlobalIdRegistry;
+    use serde_hash::error::{SerdeHashError, WeakSaltError};
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::ordered::OrderedHash;
+    use serde_hash::patterns::FieldPatterns;
+    use serde_hash::profile::{Profiled, SerializationProfile};
+    use serde_hash::provider::GlobalOptions;
+    use serde_hash::serde_impl::{Decoded, Encoded, Hashed, MinLength, NonZero};
+    use std::panic::{RefUnwindSafe, UnwindSafe};
+
+    fn assert_thread_safe<T: Send + Sync + UnwindSafe + RefUnwindSafe>() {}
+
+    #[test]
+    fn test_options_and_contexts() {
+        assert_thread_safe::<SerdeHashOptions>();
+        assert_thread_safe::<Alphabet>();
+        assert_thread_safe::<HashContext>();
+        assert_thread_safe::<HashIdsEncoder>();
+        assert_thread_safe::<GlobalOptions>();
+        assert_thread_safe::<FieldPatterns>();
+        assert_thread_safe::<SerializationProfile>();
+    }
+
+    #[test]
+    fn test_registries() {
+        #[cfg(feature = "registry")]
+        assert_thread_safe::<serde_hash::registry::SaltRegistry<String>>();
+        #[cfg(feature = "registry")]
+        assert_thread_safe::<serde_hash::registry::SaltRegistry<u64>>();
+        assert_thread_safe::<GlobalIdRegistry>();
+    }
+
+    #[test]
+    fn test_wrappers() {
+        assert_thread_safe::<HashDisplay>();
+        #[cfg(feature = "cursor")]
+        assert_thread_safe::<serde_hash::cursor::Cursor<u64>>();
+        #[cfg(feature = "cursor")]
+        assert_thread_safe::<serde_hash::cursor::Direction>();
+        #[cfg(feature = "hmac")]
+        assert_thread_safe::<serde_hash::orderable::Orderable<u64>>();
+        assert_thread_safe::<OrderedHash<u64>>();
+        assert_thread_safe::<serde_hash::HashedId<u64>>();
+        assert_thread_safe::<Encoded<'static, u64>>();
+        assert_thread_safe::<Decoded<u64>>();
+        assert_thread_safe::<Hashed>();
+        assert_thread_safe::<NonZero>();
+        assert_thread_safe::<MinLength<10>>();
+        assert_thread_safe::<Profiled<'static, u64>>();
+    }
+
+    #[test]
+    fn test_reports_and_errors() {
+        assert_thread_safe::<SerdeHashError>();
+        assert_thread_safe::<WeakSaltError>();
+        assert_thread_safe::<DecodeReport>();
+        assert_thread_safe::<DecodeBackend>();
+        assert_thread_safe::<Explanation>();
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_bulk_decoder() {
+        assert_thread_safe::<serde_hash::bulk::BulkDecoder>();
+        assert_thread_safe::<serde_hash::bulk::BulkStats>();
+    }
+
+    #[cfg(feature = "json-patch")]
+    #[test]
+    fn test_patch_ids() {
+        assert_thread_safe::<serde_hash::json_patch::PatchIds>();
+    }
+
+    #[cfg(feature = "axum")]
+    #[test]
+    fn test_axum_extractor() {
+        assert_thread_safe::<serde_hash::axum::HashPath<u64>>();
+        assert_thread_safe::<serde_hash::axum::HashPathRejection>();
+    }
+}
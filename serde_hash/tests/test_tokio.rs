@@ -0,0 +1,106 @@
+#![cfg(feature = "tokio")]
+
+mod test_tokio {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::context::{HashContext, current_context, with_context, with_context_sync};
+    use serde_hash::hashids::{SerdeHashOptions, decode_single, encode_single};
+    use serde_hash::serde_hash;
+    use std::sync::Once;
+    use std::time::Duration;
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct User {
+        #[serde(hash)]
+        pub id: u64,
+        #[hash(min_length = 20)]
+        pub account: u64,
+    }
+
+    static INIT: Once = Once::new();
+
+    fn init() {
+        INIT.call_once(|| {
+            SerdeHashOptions::new()
+                .with_salt("hello world")
+                .with_weak_salt_allowed(true)
+                .with_min_length(10)
+                .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+                .build();
+        });
+    }
+
+    fn tenant(salt: &str) -> HashContext {
+        SerdeHashOptions::new()
+            .with_salt(salt)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .into_context()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_scoped_context() {
+        init();
+        let acme = tenant("acme");
+        let globex = tenant("globex");
+        let user = User { id: 158674, account: 42 };
+
+        let acme_json = with_context(acme.clone(), async { serde_json::to_string(&user).unwrap() }).await;
+        let globex_json = with_context(globex.clone(), async { serde_json::to_string(&user).unwrap() }).await;
+        let global_json = serde_json::to_string(&user).unwrap();
+        assert_ne!(acme_json, globex_json);
+        assert_ne!(acme_json, global_json);
+        assert!(acme_json.contains(&acme.encode_single(158674)));
+
+        let decoded = with_context(acme.clone(), async { serde_json::from_str::<User>(&acme_json).unwrap() }).await;
+        assert_eq!(decoded, user);
+
+        // Another tenant's hashes don't decode to the same user.
+        let other = with_context(globex, async { serde_json::from_str::<User>(&acme_json).ok() }).await;
+        assert_ne!(other, Some(user));
+
+        // Outside a scope, the global options apply again.
+        assert!(current_context().is_none());
+        assert_eq!(encode_single(158674), "qKknODM7Ej");
+    }
+
+    #[tokio::test]
+    async fn test_context_survives_await_points() {
+        init();
+        let acme = tenant("acme");
+        let expected = acme.encode_single(7);
+        let hash = with_context(acme, async {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            encode_single(7)
+        })
+        .await;
+        assert_eq!(hash, expected);
+    }
+
+    #[tokio::test]
+    async fn test_nested_contexts() {
+        init();
+        let outer = tenant("outer");
+        let inner = tenant("inner");
+        let (outer_hash, inner_hash) = (outer.encode_single(1), inner.encode_single(1));
+        with_context(outer, async {
+            assert_eq!(encode_single(1), outer_hash);
+            with_context(inner, async { assert_eq!(encode_single(1), inner_hash) }).await;
+            assert_eq!(encode_single(1), outer_hash);
+        })
+        .await;
+    }
+
+    #[test]
+    fn test_sync_scope() {
+        init();
+        let acme = tenant("acme");
+        let hash = acme.encode_single(158674);
+        with_context_sync(acme, || {
+            assert_eq!(encode_single(158674), hash);
+            assert_eq!(decode_single(&hash).unwrap(), 158674);
+        });
+        assert_eq!(encode_single(158674), "qKknODM7Ej");
+    }
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "trace-derive")]
+
+mod test_tracing {
+    use serde_hash::HashIds;
+    use serde_hash::hashids::SerdeHashOptions;
+    use std::fmt::{Debug, Write};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+            .build();
+    }
+
+    #[derive(HashIds, Debug, PartialEq)]
+    struct User {
+        #[hash]
+        id: u64,
+        #[hash]
+        team_ids: Vec<u32>,
+        name: String,
+    }
+
+    /// Writes every span and event as `name field=value ...` lines.
+    #[derive(Clone, Default)]
+    struct Recorder {
+        lines: Arc<Mutex<Vec<String>>>,
+        next_id: Arc<AtomicU64>,
+    }
+
+    struct Line<'a>(&'a mut String);
+
+    impl Visit for Line<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            write!(self.0, " {}={:?}", field.name(), value).unwrap();
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut line = span.metadata().name().to_string();
+            span.record(&mut Line(&mut line));
+            self.lines.lock().unwrap().push(line);
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut line = String::from("event");
+            event.record(&mut Line(&mut line));
+            self.lines.lock().unwrap().push(line);
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    fn traced(f: impl FnOnce()) -> Vec<String> {
+        let recorder = Recorder::default();
+        tracing::subscriber::with_default(recorder.clone(), f);
+        recorder.lines.lock().unwrap().clone()
+    }
+
+    #[test]
+    fn test_spans_around_derived_impls() {
+        setup();
+        let user = User { id: 158674, team_ids: vec![1], name: "Alice".to_string() };
+        let mut json = String::new();
+        let lines = traced(|| {
+            json = serde_json::to_string(&user).unwrap();
+            assert_eq!(serde_json::from_str::<User>(&json).unwrap(), user);
+        });
+        assert_eq!(
+            lines,
+            [
+                r#"serde_hash ty="User" op="serialize" hashed_fields=2"#,
+                r#"serde_hash ty="User" op="deserialize" hashed_fields=2"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_failures_are_logged_in_the_span() {
+        setup();
+        let lines = traced(|| {
+            let json = r#"{"id":"invalid","team_ids":[],"name":"Alice"}"#;
+            assert!(serde_json::from_str::<User>(json).is_err());
+        });
+        assert_eq!(lines.len(), 2, "{:?}", lines);
+        assert!(lines[1].starts_with("event message=failed error="), "{}", lines[1]);
+    }
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "unicode")]
+mod test_unicode_alphabet {
+    use serde::{Deserialize, Serialize};
+    use serde_hash::alphabet::validate_alphabet;
+    use serde_hash::encoder::HashIdsEncoder;
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::serde_hash;
+
+    const CYRILLIC: &str = "абвгдежзийклмнопрстуфхцчшщъыьэюя";
+    const FRUIT: &str = "🍎🍊🍋🍌🍉🍇🍓🍈🍒🍑🍍🥝🥥🥑🍆🥔🥕🌽🥒🥦";
+
+    #[serde_hash]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    pub struct TestData {
+        #[serde(hash)]
+        pub id: u64,
+        #[serde(hash)]
+        pub ids: Vec<u64>,
+        pub name: String,
+    }
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet(CYRILLIC)
+            .build();
+    }
+
+    fn options(alphabet: &str) -> SerdeHashOptions {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet(alphabet)
+    }
+
+    #[test]
+    fn test_serialization_round_trip() {
+        setup();
+        let data = TestData {
+            id: 158674,
+            ids: vec![1, 2, u64::MAX],
+            name: "Иван".to_string(),
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let hash = value["id"].as_str().unwrap();
+        assert!(hash.chars().all(|c| CYRILLIC.contains(c)), "{}", hash);
+        // The minimum length counts characters, not the two bytes each letter takes.
+        assert_eq!(hash.chars().count(), 10);
+        assert_eq!(hash.len(), 20);
+        assert_eq!(serde_json::from_str::<TestData>(&json).unwrap(), data);
+    }
+
+    #[test]
+    fn test_emoji_alphabet() {
+        let encoder = HashIdsEncoder::new(&options(FRUIT).with_checksum(true)).unwrap();
+        for value in [0, 1, 158674, u64::MAX] {
+            let hash = encoder.encode(&[value]);
+            assert!(hash.chars().all(|c| FRUIT.contains(c)), "{}", hash);
+            assert!(hash.chars().count() >= 11, "{}", hash);
+            assert_eq!(encoder.decode(&hash).unwrap(), [value], "{}", hash);
+        }
+        let hash = encoder.encode(&[158674]);
+        assert!(encoder.decode(hash.replacen('🍎', "🍏", 1) + "🍏").is_err());
+    }
+
+    #[test]
+    fn test_case_insensitive_cyrillic() {
+        let encoder =
+            HashIdsEncoder::new(&options(CYRILLIC).with_case_insensitive_decode(true)).unwrap();
+        let hash = encoder.encode(&[158674]);
+        assert_eq!(encoder.decode(hash.to_uppercase()).unwrap(), [158674]);
+    }
+
+    #[test]
+    fn test_max_length_counts_characters() {
+        let encoder = HashIdsEncoder::new(&options(CYRILLIC).with_max_length(10)).unwrap();
+        assert_eq!(encoder.try_encode(&[1]).unwrap().chars().count(), 10);
+        assert!(matches!(
+            encoder.try_encode(&[u64::MAX]),
+            Err(SerdeHashError::MaxLengthExceeded { max: 10, .. })
+        ));
+    }
+
+    #[test]
+    fn test_combining_characters_are_rejected() {
+        // A combining acute accent joins whichever letter comes before it.
+        let accented = format!("{}\u{301}", CYRILLIC);
+        assert_eq!(
+            validate_alphabet(&accented),
+            Err(SerdeHashError::AlphabetCombiningCharacters { first: 'а', second: '\u{301}' })
+        );
+        // Skin tone modifiers extend the emoji before them.
+        let toned = format!("{}👍🏽", FRUIT);
+        assert_eq!(
+            validate_alphabet(&toned),
+            Err(SerdeHashError::AlphabetCombiningCharacters { first: '🍎', second: '🏽' })
+        );
+        // Two regional indicators make a flag, even when apart in the alphabet.
+        let flags = format!("🇺{}🇸", FRUIT);
+        assert_eq!(
+            validate_alphabet(&flags),
+            Err(SerdeHashError::AlphabetCombiningCharacters { first: '🇺', second: '🇺' })
+        );
+        let result = options(&accented).try_build();
+        assert!(matches!(result, Err(SerdeHashError::AlphabetCombiningCharacters { .. })));
+    }
+}
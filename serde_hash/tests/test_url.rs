@@ -0,0 +1,58 @@
+mod test_url {
+    use serde_hash::alphabet::Alphabet;
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::hashids::{SerdeHashOptions, encode_single};
+    use serde_hash::url::{
+        decode_url_component, encode_url_component, percent_decode, percent_encode,
+    };
+
+    fn setup() {
+        SerdeHashOptions::new()
+            .with_salt("hello world")
+            .with_weak_salt_allowed(true)
+            .with_min_length(10)
+            .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ+/#?%")
+            .build();
+    }
+
+    #[test]
+    fn test_url_safe_validation() {
+        let options = SerdeHashOptions::new().with_url_safe(true);
+        assert!(options.clone().with_alphabet_preset(Alphabet::UrlSafe).validate().is_ok());
+        assert!(options.clone().with_alphabet("abcdefghijklmnop.~").validate().is_ok());
+        assert_eq!(
+            options.clone().with_alphabet("abcdefghijklmnop+/").validate(),
+            Err(SerdeHashError::AlphabetNotUrlSafe('+'))
+        );
+        // Without the setting, any valid alphabet is accepted.
+        assert!(SerdeHashOptions::new().with_alphabet("abcdefghijklmnop+/").validate().is_ok());
+    }
+
+    #[test]
+    fn test_percent_encoding() {
+        assert_eq!(percent_encode("aZ09-._~"), "aZ09-._~");
+        assert_eq!(percent_encode("a+b/c#d?%é"), "a%2Bb%2Fc%23d%3F%25%C3%A9");
+        assert_eq!(percent_decode("a%2Bb%2Fc%23d%3F%25%C3%A9").unwrap(), "a+b/c#d?%é");
+        assert!(percent_decode("abc%2").is_err());
+        assert!(percent_decode("abc%zz").is_err());
+        assert!(percent_decode("%C3").is_err());
+        // `from_str_radix` alone would accept a sign in front of a single digit
+        assert!(percent_decode("a%+Ab").is_err());
+        assert!(percent_decode("a%-1b").is_err());
+    }
+
+    #[test]
+    fn test_url_components_round_trip() {
+        setup();
+        for value in [0, 1, 158674, u64::MAX] {
+            let component = encode_url_component(value);
+            assert!(
+                component.chars().all(|c| c.is_ascii_alphanumeric() || c == '%'),
+                "{}",
+                component
+            );
+            assert_eq!(percent_decode(&component).unwrap(), encode_single(value));
+            assert_eq!(decode_url_component(&component).unwrap(), value);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "json")]
+
+mod test_value {
+    use serde_hash::error::SerdeHashError;
+    use serde_hash::hashids::SerdeHashOptions;
+    use serde_hash::patterns::FieldPatterns;
+    use serde_hash::value::{decode_ids, encode_ids};
+    use serde_json::json;
+    use std::sync::Once;
+
+    static INIT: Once = Once::new();
+
+    fn init() {
+        INIT.call_once(|| {
+            SerdeHashOptions::new()
+                .with_salt("hello world")
+                .with_weak_salt_allowed(true)
+                .with_min_length(10)
+                .with_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890")
+                .with_field_patterns(["id", "*_id", "*_ids"])
+                .build();
+        });
+    }
+
+    #[test]
+    fn test_round_trip() {
+        init();
+        let original = json!({
+            "id": 158674,
+            "name": "Dan Smith",
+            "manager_id": null,
+            "friend_ids": [158674, [1, 2]],
+            "orders": [{ "order_id": 158674, "total": 12 }],
+            "identity": 5,
+        });
+        let mut payload = original.clone();
+        encode_ids(&mut payload).unwrap();
+        assert_eq!(payload["id"], "qKknODM7Ej");
+        assert_eq!(payload["manager_id"], json!(null));
+        assert_eq!(payload["friend_ids"][0], "qKknODM7Ej");
+        assert!(payload["friend_ids"][1][1].is_string());
+        assert_eq!(payload["orders"][0]["order_id"], "qKknODM7Ej");
+        assert_eq!(payload["orders"][0]["total"], 12);
+        assert_eq!(payload["identity"], 5);
+
+        decode_ids(&mut payload).unwrap();
+        assert_eq!(payload, original);
+    }
+
+    #[test]
+    fn test_unexpected_values() {
+        init();
+        let error = encode_ids(&mut json!({ "user_id": "already a string" })).unwrap_err();
+        assert!(format!("{:#}", error).contains("in field 'user_id'"));
+        assert!(encode_ids(&mut json!({ "id": -1 })).is_err());
+        assert!(decode_ids(&mut json!({ "id": 158674 })).is_err());
+        assert!(decode_ids(&mut json!({ "id": "not-a-hash" })).is_err());
+    }
+
+    #[test]
+    fn test_glob_patterns() {
+        let patterns = FieldPatterns::new(["id", "*_id", "user?", "a*b*c"]).unwrap();
+        for key in ["id", "user_id", "_id", "user1", "abc", "aXbYc", "abbbc"] {
+            assert!(patterns.matches(key), "{} should match", key);
+        }
+        for key in ["ID", "identity", "user_ids", "user", "user12", "acb"] {
+            assert!(!patterns.matches(key), "{} should not match", key);
+        }
+        assert!(!FieldPatterns::new([""; 0]).unwrap().matches("id"));
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        let error = SerdeHashOptions::new().with_field_patterns([""]).validate().unwrap_err();
+        assert!(matches!(error, SerdeHashError::InvalidFieldPattern { .. }));
+        #[cfg(not(feature = "regex"))]
+        assert!(FieldPatterns::new(["/^id$/"]).is_err());
+        #[cfg(feature = "regex")]
+        assert!(FieldPatterns::new(["/^(id/"]).is_err());
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_regex_patterns() {
+        let patterns = FieldPatterns::new(["/^(user|org)_id$/", "ref"]).unwrap();
+        assert!(patterns.matches("user_id"));
+        assert!(patterns.matches("org_id"));
+        assert!(!patterns.matches("team_id"));
+        assert!(patterns.matches("ref"));
+    }
+}
@@ -0,0 +1,9 @@
+use serde_hash::HashIds;
+
+#[derive(HashIds)]
+enum Id {
+    User(u64),
+    Team(u64),
+}
+
+fn main() {}
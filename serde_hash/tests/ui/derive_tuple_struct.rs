@@ -0,0 +1,6 @@
+use serde_hash::HashIds;
+
+#[derive(HashIds)]
+struct UserId(#[hash] u64);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use serde_hash::HashIds;
+
+#[derive(HashIds)]
+union Id {
+    number: u64,
+    bytes: [u8; 8],
+}
+
+fn main() {}
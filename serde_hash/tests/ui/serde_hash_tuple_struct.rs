@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use serde_hash::serde_hash;
+
+#[serde_hash]
+#[derive(Serialize, Deserialize)]
+struct UserId(#[serde(hash)] u64);
+
+fn main() {}
@@ -1,6 +1,6 @@
 #![doc = include_str!("../README.MD")]
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{quote, ToTokens};
 use syn::{GenericArgument, PathArguments, Type};
 
 // --- Shared type detection helpers ---
@@ -22,59 +22,114 @@ fn is_numeric_type(ty: &Type) -> bool {
 }
 
 fn is_vector_of_numeric(ty: &Type) -> bool {
-    if let Type::Path(type_path) = ty {
-        if type_path.qself.is_none() && type_path.path.segments.len() == 1 {
-            let segment = type_path.path.segments.first().unwrap();
-            if segment.ident == "Vec" {
-                if let PathArguments::AngleBracketed(ref args) = segment.arguments {
-                    if args.args.len() == 1 {
-                        if let Some(GenericArgument::Type(inner_ty)) = args.args.first() {
-                            return is_numeric_type(inner_ty);
-                        }
-                    }
-                }
-            }
+    if let Type::Path(type_path) = ty
+        && type_path.qself.is_none()
+        && type_path.path.segments.len() == 1
+    {
+        let segment = type_path.path.segments.first().unwrap();
+        if segment.ident == "Vec"
+            && let PathArguments::AngleBracketed(ref args) = segment.arguments
+            && args.args.len() == 1
+            && let Some(GenericArgument::Type(inner_ty)) = args.args.first()
+        {
+            return is_numeric_type(inner_ty);
         }
     }
     false
 }
 
+fn is_option(ty: &Type) -> bool {
+    if let Type::Path(type_path) = ty
+        && type_path.qself.is_none()
+        && type_path.path.segments.len() == 1
+    {
+        return type_path.path.segments[0].ident == "Option";
+    }
+    false
+}
+
 fn is_option_of_numeric(ty: &Type) -> bool {
-    if let Type::Path(type_path) = ty {
-        if type_path.qself.is_none() && type_path.path.segments.len() == 1 {
-            let segment = type_path.path.segments.first().unwrap();
-            if segment.ident == "Option" {
-                if let PathArguments::AngleBracketed(ref args) = segment.arguments {
-                    if args.args.len() == 1 {
-                        if let Some(GenericArgument::Type(inner_ty)) = args.args.first() {
-                            return is_numeric_type(inner_ty);
-                        }
-                    }
-                }
-            }
+    if let Type::Path(type_path) = ty
+        && type_path.qself.is_none()
+        && type_path.path.segments.len() == 1
+    {
+        let segment = type_path.path.segments.first().unwrap();
+        if segment.ident == "Option"
+            && let PathArguments::AngleBracketed(ref args) = segment.arguments
+            && args.args.len() == 1
+            && let Some(GenericArgument::Type(inner_ty)) = args.args.first()
+        {
+            return is_numeric_type(inner_ty);
         }
     }
     false
 }
 
 fn is_option_of_vector_of_numeric(ty: &Type) -> bool {
-    if let Type::Path(type_path) = ty {
-        if type_path.qself.is_none() && type_path.path.segments.len() == 1 {
-            let segment = type_path.path.segments.first().unwrap();
-            if segment.ident == "Option" {
-                if let PathArguments::AngleBracketed(ref args) = segment.arguments {
-                    if args.args.len() == 1 {
-                        if let Some(GenericArgument::Type(inner_ty)) = args.args.first() {
-                            return is_vector_of_numeric(inner_ty);
-                        }
-                    }
-                }
-            }
+    if let Type::Path(type_path) = ty
+        && type_path.qself.is_none()
+        && type_path.path.segments.len() == 1
+    {
+        let segment = type_path.path.segments.first().unwrap();
+        if segment.ident == "Option"
+            && let PathArguments::AngleBracketed(ref args) = segment.arguments
+            && args.args.len() == 1
+            && let Some(GenericArgument::Type(inner_ty)) = args.args.first()
+        {
+            return is_vector_of_numeric(inner_ty);
         }
     }
     false
 }
 
+/// The `T` of a single-segment `Wrapper<T>` type, such as `Vec<T>`.
+fn wrapped_type<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    if let Type::Path(type_path) = ty
+        && type_path.qself.is_none()
+        && type_path.path.segments.len() == 1
+    {
+        let segment = type_path.path.segments.first().unwrap();
+        if segment.ident == wrapper
+            && let PathArguments::AngleBracketed(ref args) = segment.arguments
+            && args.args.len() == 1
+            && let Some(GenericArgument::Type(inner_ty)) = args.args.first()
+        {
+            return Some(inner_ty);
+        }
+    }
+    None
+}
+
+/// A numeric type wrapped in any combination of `Vec` and `Option`, e.g. `Vec<Vec<u64>>`.
+fn is_nested_numeric(ty: &Type) -> bool {
+    is_numeric_type(ty)
+        || wrapped_type(ty, "Vec").is_some_and(is_nested_numeric)
+        || wrapped_type(ty, "Option").is_some_and(is_nested_numeric)
+}
+
+/// An `f32` or `f64` wrapped in any combination of `Vec` and `Option`, as accepted by
+/// `#[hash(scale = N)]`.
+fn is_nested_float(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.qself.is_none()
+        && (path.path.is_ident("f32") || path.path.is_ident("f64")))
+        || wrapped_type(ty, "Vec").is_some_and(is_nested_float)
+        || wrapped_type(ty, "Option").is_some_and(is_nested_float)
+}
+
+/// The numeric type inside any `Vec`/`Option` nesting, e.g. `u32` for `Option<Vec<u32>>`.
+fn numeric_element(ty: &Type) -> &Type {
+    match wrapped_type(ty, "Vec").or_else(|| wrapped_type(ty, "Option")) {
+        Some(inner) => numeric_element(inner),
+        None => ty,
+    }
+}
+
+/// Whether the numeric type inside any `Vec`/`Option` nesting is `u128`, whose values may
+/// be hashed as two limbs.
+fn is_wide_numeric(ty: &Type) -> bool {
+    matches!(numeric_element(ty), Type::Path(path) if path.qself.is_none() && path.path.is_ident("u128"))
+}
+
 fn determine_with_path(ty: &Type) -> Option<&'static str> {
     if is_numeric_type(ty) {
         Some("serde_hash::serde_impl::numeric")
@@ -84,11 +139,1243 @@ fn determine_with_path(ty: &Type) -> Option<&'static str> {
         Some("serde_hash::serde_impl::option_numeric")
     } else if is_option_of_vector_of_numeric(ty) {
         Some("serde_hash::serde_impl::option_vec_numeric")
+    } else if wrapped_type(ty, "Vec").is_some_and(is_vector_of_numeric) {
+        Some("serde_hash::serde_impl::vec_vec_numeric")
+    } else if is_nested_numeric(ty) {
+        Some("serde_hash::serde_impl::nested")
     } else {
         None
     }
 }
 
+/// The shape of a hashed field, as far as the generated code is concerned.
+#[derive(Clone, Copy, PartialEq)]
+enum HashKind {
+    Numeric,
+    Vec,
+    Option,
+    OptionVec,
+    /// Any deeper nesting, such as `Vec<Vec<T>>`.
+    Nested,
+    /// A float field hashed as fixed-point with `#[hash(scale = N)]`, in any nesting.
+    Scaled,
+}
+
+/// Whether `#[hash(all)]` hashes a field without a `#[hash]` attribute of its own: any
+/// type `#[hash]` accepts, unless the field's serde attributes already set how it is
+/// serialized.
+fn hashed_by_default(field: &syn::Field) -> bool {
+    is_nested_numeric(&field.ty) && reject_serde_with(&field.attrs).is_ok()
+}
+
+fn hash_kind(ty: &Type) -> Option<HashKind> {
+    if is_numeric_type(ty) {
+        Some(HashKind::Numeric)
+    } else if is_vector_of_numeric(ty) {
+        Some(HashKind::Vec)
+    } else if is_option_of_numeric(ty) {
+        Some(HashKind::Option)
+    } else if is_option_of_vector_of_numeric(ty) {
+        Some(HashKind::OptionVec)
+    } else if is_nested_numeric(ty) {
+        Some(HashKind::Nested)
+    } else {
+        None
+    }
+}
+
+/// Options accepted inside `#[hash(...)]`.
+#[derive(Default)]
+struct HashOptions {
+    /// Emit a salted one-way digest instead of a reversible hash.
+    redact: bool,
+    /// Accept the raw numeric value when deserializing a redacted field.
+    accept_raw: bool,
+    /// Custom `fn(u64) -> String` used instead of the configured encoder.
+    encode_with: Option<syn::ExprPath>,
+    /// Custom `fn(&str) -> Result<u64, E>` used instead of the configured decoder.
+    decode_with: Option<syn::ExprPath>,
+    /// Minimum hash length for this field, overriding the global setting.
+    min_length: Option<usize>,
+    /// Use this field for the struct's `HasHashId` impl.
+    primary: bool,
+    /// Serialize the digits partially masked instead of hashed, e.g. `mask = "last4"`.
+    mask: Option<MaskRule>,
+    /// Write the hash under the field's key plus this suffix, next to the raw value.
+    suffix: Option<syn::LitStr>,
+    /// With `suffix`, leave the raw value out.
+    omit_raw: bool,
+    /// Encode this field and the named one into a single hash under this field's key.
+    pack_with: Option<syn::Ident>,
+    /// Reject decoded values outside this range, e.g. `range = "1..=1000"`, along with the
+    /// range as written for error messages.
+    range: Option<(syn::Expr, String)>,
+    /// Leave the field unhashed despite `#[hash(all)]` on the struct.
+    skip: bool,
+    /// Set for fields hashed by `#[hash(all)]` rather than their own attribute.
+    implicit: bool,
+    /// Former names of the hashed field, read when deserializing but never written, e.g.
+    /// `alias_decode = "legacy_id"`.
+    alias_decode: Vec<String>,
+    /// Hash an `f32`/`f64` field as fixed-point, multiplied by this and rounded.
+    scale: Option<u64>,
+    /// Fail serialization when the field holds 0, a common "unset" sentinel.
+    nonzero: bool,
+    /// Only hash the field when this predicate holds, e.g. `cfg(feature = "public-api")`;
+    /// otherwise it is written as the raw value.
+    cfg: Option<syn::Meta>,
+    /// Write the number as a string of its digits instead of hashing it.
+    stringify_only: bool,
+}
+
+/// A `mask = "..."` rule: how many digits stay visible, and at which end.
+struct MaskRule {
+    keep: usize,
+    from_end: bool,
+}
+
+impl MaskRule {
+    fn from_lit(lit: &syn::LitStr) -> syn::Result<Self> {
+        let value = lit.value();
+        let (from_end, keep) = if let Some(keep) = value.strip_prefix("last") {
+            (true, keep)
+        } else if let Some(keep) = value.strip_prefix("first") {
+            (false, keep)
+        } else {
+            (false, "")
+        };
+        match keep.parse() {
+            Ok(keep) => Ok(Self { keep, from_end }),
+            Err(_) => Err(syn::Error::new_spanned(
+                lit,
+                "expected a mask like \"last4\" or \"first2\"",
+            )),
+        }
+    }
+}
+
+impl HashOptions {
+    /// Parses a `#[hash]` or `#[hash(...)]` attribute.
+    fn from_attr(attr: &syn::Attribute) -> syn::Result<Self> {
+        let mut options = Self::default();
+        if let syn::Meta::List(_) = attr.meta {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("redact") {
+                    options.redact = true;
+                } else if meta.path.is_ident("accept_raw") {
+                    options.accept_raw = true;
+                } else if meta.path.is_ident("encode_with") {
+                    options.encode_with = Some(meta.value()?.parse::<syn::LitStr>()?.parse()?);
+                } else if meta.path.is_ident("decode_with") {
+                    options.decode_with = Some(meta.value()?.parse::<syn::LitStr>()?.parse()?);
+                } else if meta.path.is_ident("min_length") {
+                    options.min_length =
+                        Some(meta.value()?.parse::<syn::LitInt>()?.base10_parse()?);
+                } else if meta.path.is_ident("primary") {
+                    options.primary = true;
+                } else if meta.path.is_ident("mask") {
+                    options.mask = Some(MaskRule::from_lit(&meta.value()?.parse()?)?);
+                } else if meta.path.is_ident("suffix") {
+                    options.suffix = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("omit_raw") {
+                    options.omit_raw = true;
+                } else if meta.path.is_ident("pack_with") {
+                    options.pack_with = Some(meta.value()?.parse::<syn::LitStr>()?.parse()?);
+                } else if meta.path.is_ident("range") {
+                    let lit: syn::LitStr = meta.value()?.parse()?;
+                    options.range = Some((lit.parse()?, lit.value()));
+                } else if meta.path.is_ident("skip") {
+                    options.skip = true;
+                } else if meta.path.is_ident("scale") {
+                    let lit: syn::LitInt = meta.value()?.parse()?;
+                    match lit.base10_parse()? {
+                        0 => return Err(syn::Error::new_spanned(lit, "`scale` must be positive")),
+                        scale => options.scale = Some(scale),
+                    }
+                } else if meta.path.is_ident("nonzero") {
+                    options.nonzero = true;
+                } else if meta.path.is_ident("stringify_only") {
+                    options.stringify_only = true;
+                } else if meta.path.is_ident("alias_decode") {
+                    options.alias_decode.push(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if meta.path.is_ident("cfg") {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    options.cfg = Some(content.parse()?);
+                    if !content.is_empty() {
+                        return Err(content.error("expected a single `cfg` predicate"));
+                    }
+                } else {
+                    return Err(meta.error("unsupported `hash` option"));
+                }
+                Ok(())
+            })?;
+        }
+        if options.skip
+            && (options.redact
+                || options.encode_with.is_some()
+                || options.decode_with.is_some()
+                || options.min_length.is_some()
+                || options.primary
+                || options.mask.is_some()
+                || options.suffix.is_some()
+                || options.pack_with.is_some()
+                || options.range.is_some()
+                || options.nonzero
+                || options.cfg.is_some()
+                || options.stringify_only
+                || !options.alias_decode.is_empty())
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`skip` cannot be combined with other `hash` options",
+            ));
+        }
+        if options.scale.is_some()
+            && (options.redact
+                || options.encode_with.is_some()
+                || options.decode_with.is_some()
+                || options.min_length.is_some()
+                || options.primary
+                || options.mask.is_some()
+                || options.pack_with.is_some()
+                || options.range.is_some()
+                || options.skip)
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`scale` can only be combined with `suffix`, `omit_raw` and `alias_decode`",
+            ));
+        }
+        if options.stringify_only
+            && (options.redact
+                || options.encode_with.is_some()
+                || options.decode_with.is_some()
+                || options.min_length.is_some()
+                || options.primary
+                || options.mask.is_some()
+                || options.suffix.is_some()
+                || options.pack_with.is_some()
+                || options.range.is_some()
+                || options.scale.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`stringify_only` can only be combined with `alias_decode`, `nonzero` and `cfg`",
+            ));
+        }
+        if options.accept_raw && !options.redact {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`accept_raw` can only be used together with `redact`",
+            ));
+        }
+        if options.omit_raw && options.suffix.is_none() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`omit_raw` can only be used together with `suffix`",
+            ));
+        }
+        if options.suffix.is_some() && (options.redact || options.mask.is_some()) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`suffix` cannot be combined with `redact` or `mask`",
+            ));
+        }
+        let custom = options.encode_with.is_some() || options.decode_with.is_some();
+        if options.pack_with.is_some()
+            && (options.redact
+                || custom
+                || options.min_length.is_some()
+                || options.primary
+                || options.mask.is_some()
+                || options.suffix.is_some()
+                || options.range.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`pack_with` cannot be combined with other `hash` options",
+            ));
+        }
+        if options.redact && custom {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`redact` cannot be combined with `encode_with` or `decode_with`",
+            ));
+        }
+        if options.primary && options.redact {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "a redacted field can't be the primary id",
+            ));
+        }
+        if options.mask.is_some()
+            && (options.redact || custom || options.min_length.is_some() || options.primary)
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`mask` cannot be combined with other `hash` options",
+            ));
+        }
+        if options.range.is_some() && (options.redact || options.mask.is_some()) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`range` cannot be combined with `redact` or `mask`",
+            ));
+        }
+        if options.nonzero
+            && (options.redact
+                || options.mask.is_some()
+                || options.scale.is_some()
+                || options.pack_with.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`nonzero` cannot be combined with `redact`, `mask`, `scale` or `pack_with`",
+            ));
+        }
+        if options.cfg.is_some() && (options.suffix.is_some() || options.pack_with.is_some()) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`cfg` cannot be combined with `suffix` or `pack_with`",
+            ));
+        }
+        if options.min_length.is_some() && (options.redact || custom) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`min_length` only applies to the built-in encoder",
+            ));
+        }
+        Ok(options)
+    }
+
+    /// `#[meta]`, or with `cfg`, `#[cfg_attr(cfg, meta)]`, so the attribute only applies
+    /// when the field is hashed.
+    fn gate_attr(&self, meta: &syn::Meta) -> syn::Attribute {
+        match &self.cfg {
+            Some(cfg) => syn::parse_quote!(#[cfg_attr(#cfg, #meta)]),
+            None => syn::parse_quote!(#[#meta]),
+        }
+    }
+
+    /// `items` with `#[cfg(...)]` on each of them, so the glue of a field that isn't hashed
+    /// isn't compiled either.
+    fn gate_items(&self, items: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let Some(cfg) = &self.cfg else {
+            return items;
+        };
+        match syn::parse2::<syn::File>(items.clone()) {
+            Ok(file) => file.items.iter().map(|item| quote!(#[cfg(#cfg)] #item)).collect(),
+            Err(_) => items,
+        }
+    }
+}
+
+/// Rejects `encode_with`/`decode_with` and `range` on `u128` fields: custom codecs and
+/// range checks handle one `u64`, but `u128` values above `u64::MAX` are hashed as two
+/// limbs.
+fn check_custom_codec(attr: &syn::Attribute, ty: &Type, options: &HashOptions) -> syn::Result<()> {
+    if (options.encode_with.is_some() || options.decode_with.is_some()) && is_wide_numeric(ty) {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "`encode_with` and `decode_with` don't support u128 fields",
+        ));
+    }
+    if options.range.is_some() && is_wide_numeric(ty) {
+        return Err(syn::Error::new_spanned(attr, "`range` doesn't support u128 fields"));
+    }
+    Ok(())
+}
+
+/// Picks the `serde_hash::serde_impl::FieldWith` implementation for a field whose `hash`
+/// options can't be expressed with one of the built-in `with` modules.
+///
+/// Returns the implementing type, plus the definition of a hidden type when one has to be
+/// generated for this field. Returns `None` when a built-in module applies.
+fn field_with(
+    container: &syn::Ident,
+    field: &syn::Ident,
+    ty: &Type,
+    options: &HashOptions,
+) -> Option<(Type, Option<proc_macro2::TokenStream>)> {
+    let with = hashing_with(container, field, ty, options);
+    if !options.nonzero {
+        return with;
+    }
+    let (inner, tokens) =
+        with.unwrap_or_else(|| (syn::parse_quote!(serde_hash::serde_impl::Hashed), None));
+    Some((syn::parse_quote!(serde_hash::serde_impl::NonZero<#inner>), tokens))
+}
+
+/// The [`field_with`] implementation for everything but `nonzero`, which wraps it.
+fn hashing_with(
+    container: &syn::Ident,
+    field: &syn::Ident,
+    ty: &Type,
+    options: &HashOptions,
+) -> Option<(Type, Option<proc_macro2::TokenStream>)> {
+    if let Some(scale) = options.scale {
+        return Some((syn::parse_quote!(serde_hash::scaled::Scaled<#scale>), None));
+    }
+    if options.stringify_only {
+        return Some((syn::parse_quote!(serde_hash::serde_impl::Stringified), None));
+    }
+    if let Some(MaskRule { keep, from_end }) = options.mask {
+        return Some((
+            syn::parse_quote!(serde_hash::serde_impl::Masked<#keep, #from_end>),
+            None,
+        ));
+    }
+    if let Some(min_length) = options.min_length
+        && options.range.is_none()
+    {
+        return Some((
+            syn::parse_quote!(serde_hash::serde_impl::MinLength<#min_length>),
+            None,
+        ));
+    }
+    if options.encode_with.is_none() && options.decode_with.is_none() && options.range.is_none() {
+        return None;
+    }
+    let with_ident = quote::format_ident!("__SerdeHash_{}_{}", container, field);
+    let encode = match (&options.encode_with, options.min_length) {
+        (Some(path), _) => quote!(|values: &[u64]| #path(values[0])),
+        (None, Some(min_length)) => quote! {
+            |values: &[u64]| serde_hash::hashids::encode_with_min_length(values, #min_length)
+        },
+        (None, None) => quote!(serde_hash::hashids::encode),
+    };
+    let mut decode = match (&options.decode_with, options.min_length) {
+        (Some(path), _) => quote!(#path(s).map(|value| vec![value])),
+        (None, Some(min_length)) => {
+            quote!(serde_hash::hashids::decode_with_min_length(s, #min_length))
+        }
+        (None, None) => quote!(serde_hash::hashids::decode(s)),
+    };
+    if let Some((range, text)) = &options.range {
+        let element = numeric_element(ty);
+        decode = quote! {
+            serde_hash::serde_impl::check_range::<#element, _>(#decode, #range, #text)
+        };
+    }
+    let tokens = quote! {
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        struct #with_ident;
+
+        impl serde_hash::serde_impl::FieldWith<#ty> for #with_ident {
+            fn serialize<S: serde_hash::serde::Serializer>(value: &#ty, serializer: S) -> Result<S::Ok, S::Error> {
+                serde_hash::serde_impl::HashShape::serialize_with(value, #encode, serializer)
+            }
+
+            fn deserialize<'de, D: serde_hash::serde::Deserializer<'de>>(deserializer: D) -> Result<#ty, D::Error> {
+                <#ty as serde_hash::serde_impl::HashShape>::deserialize_with(deserializer, |s| {
+                    serde_hash::hook::report_decode_error(s, #decode)
+                })
+            }
+        }
+    };
+    Some((syn::parse_quote!(#with_ident), Some(tokens)))
+}
+
+/// Casing applied to every key by `#[hash(rename_all = "...")]`, mirroring serde's.
+#[derive(Clone, Copy, PartialEq)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    const NAMES: &'static [(&'static str, RenameRule)] = &[
+        ("lowercase", RenameRule::Lower),
+        ("UPPERCASE", RenameRule::Upper),
+        ("PascalCase", RenameRule::Pascal),
+        ("camelCase", RenameRule::Camel),
+        ("snake_case", RenameRule::Snake),
+        ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnake),
+        ("kebab-case", RenameRule::Kebab),
+        ("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebab),
+    ];
+
+    fn from_lit(lit: &syn::LitStr) -> syn::Result<Self> {
+        let value = lit.value();
+        Self::NAMES
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, rule)| *rule)
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::NAMES.iter().map(|(name, _)| *name).collect();
+                syn::Error::new_spanned(
+                    lit,
+                    format!(
+                        "unknown rename rule `{}`, expected one of {}",
+                        value,
+                        names.join(", ")
+                    ),
+                )
+            })
+    }
+
+    /// The rule as written in serde's `rename_all`.
+    fn name(self) -> &'static str {
+        Self::NAMES.iter().find(|(_, rule)| *rule == self).map(|(name, _)| *name).unwrap()
+    }
+
+    /// Applies the rule to a snake_case field name.
+    fn apply(self, field: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => field.to_string(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal => field
+                .split('_')
+                .map(|word| {
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                        None => String::new(),
+                    }
+                })
+                .collect(),
+            RenameRule::Camel => {
+                let pascal = RenameRule::Pascal.apply(field);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            }
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.replace('_', "-").to_ascii_uppercase(),
+        }
+    }
+}
+
+/// Options accepted by a container-level `#[hash(...)]` on a struct deriving `HashIds`.
+#[derive(Default)]
+struct ContainerOptions {
+    rename_all: Option<RenameRule>,
+    /// Type prefix of the struct's public id, e.g. `usr_`.
+    prefix: Option<syn::LitStr>,
+    /// Generate `to_public_json`/`from_public_json`, which need the `json` feature.
+    helpers: bool,
+    /// Serialize the struct as its only field, from `#[hash(transparent)]` or
+    /// `#[serde(transparent)]`.
+    transparent: bool,
+    /// Hash every numeric field that isn't marked `#[hash(skip)]`.
+    all: bool,
+}
+
+impl ContainerOptions {
+    fn from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut options = ContainerOptions::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("hash")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename_all") {
+                    let lit: syn::LitStr = meta.value()?.parse()?;
+                    options.rename_all = Some(RenameRule::from_lit(&lit)?);
+                    Ok(())
+                } else if meta.path.is_ident("prefix") {
+                    options.prefix = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("helpers") {
+                    options.helpers = true;
+                    Ok(())
+                } else if meta.path.is_ident("transparent") {
+                    options.transparent = true;
+                    Ok(())
+                } else if meta.path.is_ident("all") {
+                    options.all = true;
+                    Ok(())
+                } else {
+                    Err(meta.error(
+                        "unknown container option, expected `rename_all`, `prefix`, `helpers`, \
+                         `transparent` or `all`",
+                    ))
+                }
+            })?;
+        }
+        options.transparent |= find_serde_meta(attrs, "transparent").is_some();
+        Ok(options)
+    }
+}
+
+/// A named field of a struct deriving `HashIds`.
+struct DeriveField<'a> {
+    ident: &'a syn::Ident,
+    /// The key the field is written under, after serde's `rename` or either `rename_all`.
+    key: String,
+    ty: &'a Type,
+    /// The field's attributes, whose serde and `cfg` attributes carry over to the shadow
+    /// structs.
+    attrs: &'a [syn::Attribute],
+    /// Set when the field carries a `#[hash]` attribute.
+    hash: Option<(HashKind, HashOptions)>,
+    /// The `FieldWith` implementation, for fields that need one.
+    with: Option<Type>,
+    /// Alternative names accepted when deserializing, from `#[serde(alias = "...")]`.
+    aliases: Vec<String>,
+    /// The field packed into this one's hash by `#[hash(pack_with = "...")]`.
+    partner: Option<(&'a syn::Ident, &'a Type)>,
+    /// For a field named by another's `pack_with`, the key of that field. Such a field
+    /// has no key of its own.
+    packed_into: Option<String>,
+    /// The value used when the field is missing, from `#[serde(default)]`.
+    default: Option<FieldDefault>,
+    /// The lifetimes this field borrows from the input, from `#[serde(borrow)]` or
+    /// implied by a `&str` or `&[u8]` type, as with serde's derive.
+    borrowed: Vec<syn::Lifetime>,
+}
+
+/// A field's `#[serde(default)]` or `#[serde(default = "path")]`.
+enum FieldDefault {
+    Trait,
+    Path(syn::ExprPath),
+}
+
+/// Parses the string value of a `name = "path"` serde item as a path.
+fn serde_meta_path(meta: &syn::Meta) -> syn::Result<syn::ExprPath> {
+    use syn::{Expr, ExprLit, Lit, Meta};
+
+    match meta {
+        Meta::NameValue(syn::MetaNameValue {
+            value:
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(path),
+                    ..
+                }),
+            ..
+        }) => path.parse(),
+        other => Err(syn::Error::new_spanned(other, "expected a string literal path")),
+    }
+}
+
+/// Reads a field's `#[serde(default)]`, and rejects the serde attributes that conflict
+/// with `#[hash]`.
+///
+/// `#[hash]` decides how a field is encoded, while serde's attributes still decide
+/// whether it is present. `with`, `serialize_with` and `deserialize_with` would replace
+/// the encoding, so they can't be combined with `#[hash]`.
+fn serde_default(attrs: &[syn::Attribute], hashed: bool) -> syn::Result<Option<FieldDefault>> {
+    if hashed {
+        reject_serde_with(attrs)?;
+    }
+    match find_serde_meta(attrs, "default") {
+        None => Ok(None),
+        Some(syn::Meta::Path(_)) => Ok(Some(FieldDefault::Trait)),
+        Some(meta) => Ok(Some(FieldDefault::Path(serde_meta_path(&meta)?))),
+    }
+}
+
+/// Fails if a hashed field also sets its own serialization functions.
+fn reject_serde_with(attrs: &[syn::Attribute]) -> syn::Result<()> {
+    for name in ["with", "serialize_with", "deserialize_with"] {
+        if let Some(meta) = find_serde_meta(attrs, name) {
+            return Err(syn::Error::new_spanned(
+                meta,
+                format!(
+                    "`{}` can't be combined with `hash`, which already sets how the field \
+                     is serialized; use `hash(encode_with = ..., decode_with = ...)` instead",
+                    name
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Collects the `alias = "..."` entries of a field's `#[serde(...)]` attributes.
+fn serde_aliases(attrs: &[syn::Attribute]) -> syn::Result<Vec<String>> {
+    use syn::{Expr, ExprLit, Lit, Meta, Token, punctuated::Punctuated};
+
+    let mut aliases = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        for meta in metas {
+            if let Meta::NameValue(name_value) = meta
+                && name_value.path.is_ident("alias")
+            {
+                match name_value.value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(alias),
+                        ..
+                    }) => aliases.push(alias.value()),
+                    other => {
+                        return Err(syn::Error::new_spanned(other, "expected a string literal"));
+                    }
+                }
+            }
+        }
+    }
+    Ok(aliases)
+}
+
+/// Finds the `name` item among a field's `#[serde(...)]` attributes, e.g. `default`.
+fn find_serde_meta(attrs: &[syn::Attribute], name: &str) -> Option<syn::Meta> {
+    use syn::{Meta, Token, punctuated::Punctuated};
+
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .find(|meta| meta.path().is_ident(name))
+}
+
+/// The key serde writes a field under, from `#[serde(rename = "...")]` or the
+/// `serialize` half of `#[serde(rename(serialize = "..."))]`.
+fn serde_rename(attrs: &[syn::Attribute]) -> syn::Result<Option<String>> {
+    use syn::{Expr, ExprLit, Lit, Meta};
+
+    match find_serde_meta(attrs, "rename") {
+        None => Ok(None),
+        Some(Meta::NameValue(syn::MetaNameValue {
+            value:
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(rename),
+                    ..
+                }),
+            ..
+        })) => Ok(Some(rename.value())),
+        Some(Meta::List(list)) => {
+            let mut rename = None;
+            list.parse_nested_meta(|meta| {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                if meta.path.is_ident("serialize") {
+                    rename = Some(value.value());
+                }
+                Ok(())
+            })?;
+            Ok(rename)
+        }
+        Some(other) => Err(syn::Error::new_spanned(other, "expected a string literal")),
+    }
+}
+
+/// The rule of a struct's `#[serde(rename_all = "...")]`. The `serialize`/`deserialize`
+/// form is left to serde.
+fn serde_rename_all(attrs: &[syn::Attribute]) -> syn::Result<Option<RenameRule>> {
+    match find_serde_meta(attrs, "rename_all") {
+        Some(syn::Meta::NameValue(syn::MetaNameValue {
+            value:
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(rule),
+                    ..
+                }),
+            ..
+        })) => RenameRule::from_lit(&rule).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// The `HashedFields` impl listing `keys`, the serialized keys of the hashed fields, each
+/// along with the `cfg` predicate it is hashed under, if any.
+fn hashed_fields_impl(
+    name: &syn::Ident,
+    generics: &syn::Generics,
+    keys: &[(String, Option<syn::Meta>)],
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let keys = keys.iter().map(|(key, cfg)| match cfg {
+        Some(cfg) => quote!(#[cfg(#cfg)] #key),
+        None => quote!(#key),
+    });
+    quote! {
+        impl #impl_generics serde_hash::HashedFields for #name #ty_generics #where_clause {
+            const HASHED_FIELDS: &'static [&'static str] = &[#(#keys),*];
+        }
+    }
+}
+
+/// The lifetimes of `ty`, other than `'static`, in order of appearance.
+fn type_lifetimes(ty: &Type) -> Vec<syn::Lifetime> {
+    fn collect(tokens: proc_macro2::TokenStream, lifetimes: &mut Vec<syn::Lifetime>) {
+        use proc_macro2::TokenTree;
+
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(token) = tokens.next() {
+            match token {
+                TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                    if let Some(TokenTree::Ident(ident)) = tokens.next() {
+                        let lifetime = syn::Lifetime::new(&format!("'{}", ident), ident.span());
+                        if ident != "static" && !lifetimes.contains(&lifetime) {
+                            lifetimes.push(lifetime);
+                        }
+                    }
+                }
+                TokenTree::Group(group) => collect(group.stream(), lifetimes),
+                _ => {}
+            }
+        }
+    }
+
+    let mut lifetimes = Vec::new();
+    collect(ty.to_token_stream(), &mut lifetimes);
+    lifetimes
+}
+
+/// Whether `ty` is `&str` or `&[u8]`, which serde borrows from the input without being told.
+fn is_implicitly_borrowed(ty: &Type) -> bool {
+    let Type::Reference(reference) = ty else {
+        return false;
+    };
+    if reference.mutability.is_some() {
+        return false;
+    }
+    match &*reference.elem {
+        Type::Path(path) => path.qself.is_none() && path.path.is_ident("str"),
+        Type::Slice(slice) => {
+            matches!(&*slice.elem, Type::Path(path) if path.qself.is_none() && path.path.is_ident("u8"))
+        }
+        _ => false,
+    }
+}
+
+/// The lifetimes a field borrows from the input: those named by `#[serde(borrow = "'a")]`,
+/// every lifetime of its type for a bare `#[serde(borrow)]`, or the reference's lifetime
+/// for `&str` and `&[u8]`.
+fn field_borrows(field: &syn::Field) -> syn::Result<Vec<syn::Lifetime>> {
+    use syn::{Expr, ExprLit, Lit, Meta, Token, punctuated::Punctuated};
+
+    match find_serde_meta(&field.attrs, "borrow") {
+        Some(Meta::Path(_)) => {
+            let lifetimes = type_lifetimes(&field.ty);
+            if lifetimes.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    &field.ty,
+                    "field has no lifetimes to borrow",
+                ));
+            }
+            Ok(lifetimes)
+        }
+        Some(Meta::NameValue(syn::MetaNameValue {
+            value: Expr::Lit(ExprLit {
+                lit: Lit::Str(lifetimes),
+                ..
+            }),
+            ..
+        })) => {
+            let lifetimes = lifetimes.parse_with(
+                Punctuated::<syn::Lifetime, Token![+]>::parse_separated_nonempty,
+            )?;
+            Ok(lifetimes.into_iter().collect())
+        }
+        Some(other) => Err(syn::Error::new_spanned(
+            other,
+            "expected `borrow` or `borrow = \"'a + 'b\"`",
+        )),
+        None if is_implicitly_borrowed(&field.ty) => Ok(type_lifetimes(&field.ty)),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// The generics of the `Deserialize` impl: the struct's own, after a `'de` that outlives
+/// every lifetime borrowed by a field.
+fn deserialize_generics(generics: &syn::Generics, fields: &[DeriveField]) -> syn::Generics {
+    let mut borrowed: Vec<&syn::Lifetime> = Vec::new();
+    for lifetime in fields.iter().flat_map(|field| &field.borrowed) {
+        if !borrowed.contains(&lifetime) {
+            borrowed.push(lifetime);
+        }
+    }
+    let mut de_generics = generics.clone();
+    let de: syn::LifetimeParam = if borrowed.is_empty() {
+        syn::parse_quote!('de)
+    } else {
+        syn::parse_quote!('de: #(#borrowed)+*)
+    };
+    de_generics.params.insert(0, syn::GenericParam::Lifetime(de));
+    de_generics
+}
+
+/// Fails for type and const parameters; lifetimes are the only generics supported.
+fn check_generics(generics: &syn::Generics) -> syn::Result<()> {
+    match generics.type_params().next().map(|param| param.ident.to_token_stream()).or_else(|| {
+        generics.const_params().next().map(|param| param.ident.to_token_stream())
+    }) {
+        Some(param) => Err(syn::Error::new_spanned(
+            param,
+            "#[derive(HashIds)] only supports lifetime parameters",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Fails for anything but a struct with named fields, pointing at the `enum` or `union`
+/// keyword or the tuple fields, since the generated impls only know how to walk named
+/// fields. `macro_name` is the attribute or derive the error is reported for.
+fn check_named_struct(macro_name: &str, data: &syn::Data) -> syn::Result<()> {
+    let (span, construct) = match data {
+        syn::Data::Struct(data) => match &data.fields {
+            syn::Fields::Named(_) => return Ok(()),
+            syn::Fields::Unnamed(fields) => (fields.to_token_stream(), "tuple structs"),
+            syn::Fields::Unit => (data.struct_token.to_token_stream(), "unit structs"),
+        },
+        syn::Data::Enum(data) => (data.enum_token.to_token_stream(), "enums"),
+        syn::Data::Union(data) => (data.union_token.to_token_stream(), "unions"),
+    };
+    Err(syn::Error::new_spanned(
+        span,
+        format!("{} doesn't support {}, only structs with named fields", macro_name, construct),
+    ))
+}
+
+/// The attributes a field or struct passes on to the shadow structs: its serde attributes,
+/// and `cfg` so a field that is compiled out leaves the shadows, too.
+fn forwarded_attrs(attrs: &[syn::Attribute]) -> Vec<syn::Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde") || attr.path().is_ident("cfg"))
+        .cloned()
+        .collect()
+}
+
+/// `attrs` without the serde items named in `names`, e.g. a field's own `rename` where the
+/// shadow structs write keys of their own.
+fn without_serde_items(
+    attrs: &[syn::Attribute],
+    names: &[&str],
+) -> syn::Result<Vec<syn::Attribute>> {
+    use syn::{Meta, Token, punctuated::Punctuated};
+
+    let mut kept = Vec::new();
+    for attr in attrs {
+        if !attr.path().is_ident("serde") {
+            kept.push(attr.clone());
+            continue;
+        }
+        let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        let remaining: Vec<_> = metas
+            .iter()
+            .filter(|meta| !names.iter().any(|name| meta.path().is_ident(name)))
+            .collect();
+        if !remaining.is_empty() {
+            kept.push(syn::parse_quote!(#[serde(#(#remaining),*)]));
+        }
+    }
+    Ok(kept)
+}
+
+/// What one field contributes to the shadow structs generated by [`shadow_impls`].
+#[derive(Default)]
+struct ShadowField {
+    /// Its fields in the `Serialize` shadow.
+    ser: proc_macro2::TokenStream,
+    /// Its fields in the `Deserialize` shadow.
+    de: proc_macro2::TokenStream,
+    /// The `with` functions and getters its shadow fields point serde at.
+    fns: proc_macro2::TokenStream,
+    /// Its initializers in the struct built from a deserialized `shadow`.
+    init: proc_macro2::TokenStream,
+    /// Its initializers in the `Deserialize` shadow built from a struct `value`, for a
+    /// container `#[serde(default)]`.
+    default_init: proc_macro2::TokenStream,
+}
+
+impl DeriveField<'_> {
+    /// The key the hash is written under: the field's key, plus the suffix if it has one.
+    fn hashed_key(&self) -> String {
+        match &self.hash {
+            Some((_, HashOptions { suffix: Some(suffix), .. })) => {
+                format!("{}{}", self.key, suffix.value())
+            }
+            _ => self.key.clone(),
+        }
+    }
+
+    /// The shadow fields and glue for this field.
+    ///
+    /// A field without `#[hash]` is copied with its serde attributes, so serde's derive
+    /// handles it exactly as it would on the struct itself. A hashed field gets
+    /// `serialize_with`/`deserialize_with` functions on top. A field with `suffix` maps to
+    /// two shadow fields, the raw value and the hash, either of which may be missing
+    /// when deserializing, and a `pack_with` field maps to one holding both values.
+    ///
+    /// With a container `#[serde(default)]`, missing fields take the struct's default
+    /// values, so no field defaults are added in their place.
+    fn shadow(
+        &self,
+        name: &syn::Ident,
+        generics: &syn::Generics,
+        container_default: bool,
+    ) -> syn::Result<ShadowField> {
+        let ident = self.ident;
+        let ty = self.ty;
+        let cfgs: Vec<_> = self.attrs.iter().filter(|attr| attr.path().is_ident("cfg")).collect();
+        let attrs = forwarded_attrs(self.attrs);
+        let Some((_, options)) = &self.hash else {
+            // A field packed into another's hash is written and read by that field.
+            if self.packed_into.is_some() {
+                return Ok(ShadowField::default());
+            }
+            return Ok(ShadowField {
+                ser: quote!(#(#attrs)* #ident: #ty,),
+                de: quote!(#(#attrs)* #ident: #ty,),
+                fns: quote!(),
+                init: quote!(#(#cfgs)* #ident: shadow.#ident,),
+                default_init: quote!(#(#cfgs)* #ident: value.#ident,),
+            });
+        };
+
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let base = syn::ext::IdentExt::unraw(ident).to_string();
+        let serialize_fn = quote::format_ident!("__serialize_{}", base);
+        let deserialize_fn = quote::format_ident!("__deserialize_{}", base);
+        let serialize_with = serialize_fn.to_string();
+        let deserialize_with = deserialize_fn.to_string();
+        let alias_decode = &options.alias_decode;
+        let hashed_key = self.hashed_key();
+        let read = self.read_value(
+            |ty| quote!(<#ty as serde_hash::serde::Deserialize>::deserialize(deserializer)?),
+            |seed| quote!(de::DeserializeSeed::deserialize(#seed, deserializer)?),
+        );
+
+        if let Some((partner, partner_ty)) = self.partner {
+            let getter = quote::format_ident!("__pack_{}", base);
+            let getter_name = getter.to_string();
+            return Ok(ShadowField {
+                ser: quote! {
+                    #(#attrs)*
+                    #[serde(getter = #getter_name, serialize_with = #serialize_with)]
+                    #ident: (#ty, #partner_ty),
+                },
+                de: quote! {
+                    #(#attrs)*
+                    #[serde(deserialize_with = #deserialize_with)]
+                    #ident: (#ty, #partner_ty),
+                },
+                fns: quote! {
+                    #(#cfgs)*
+                    fn #getter #impl_generics (value: &#name #ty_generics) -> (#ty, #partner_ty) #where_clause {
+                        (value.#ident, value.#partner)
+                    }
+
+                    #(#cfgs)*
+                    fn #serialize_fn<S: serde_hash::serde::Serializer>(
+                        value: &(#ty, #partner_ty),
+                        serializer: S,
+                    ) -> Result<S::Ok, S::Error> {
+                        serde_hash::serde::Serialize::serialize(
+                            &serde_hash::serde_impl::Packed(&value.0, &value.1),
+                            serializer,
+                        )
+                    }
+
+                    #(#cfgs)*
+                    fn #deserialize_fn<'de, D: serde_hash::serde::Deserializer<'de>>(
+                        deserializer: D,
+                    ) -> Result<(#ty, #partner_ty), D::Error> {
+                        use serde_hash::serde::de;
+
+                        let value = #read;
+                        Ok(value)
+                    }
+                },
+                init: quote!(#(#cfgs)* #ident: shadow.#ident.0, #partner: shadow.#ident.1,),
+                default_init: quote!(#(#cfgs)* #ident: (value.#ident, value.#partner),),
+            });
+        }
+
+        // A field hashed under `cfg` is copied as it is when the predicate doesn't hold.
+        let gate = options.cfg.as_ref().map(|cfg| quote!(#[cfg(#cfg)]));
+        let serialize_value = self.serialize_value(quote!(value));
+        let serialize = quote! {
+            #(#cfgs)*
+            #gate
+            fn #serialize_fn<S: serde_hash::serde::Serializer>(value: &#ty, serializer: S) -> Result<S::Ok, S::Error> {
+                serde_hash::serde::Serialize::serialize(&#serialize_value, serializer)
+            }
+        };
+        let two_entries = options.suffix.is_some() && !options.omit_raw;
+        let decoded_ty = if two_entries { quote!(Option<#ty>) } else { quote!(#ty) };
+        let decoded = if two_entries { quote!(Some(value)) } else { quote!(value) };
+        let deserialize = quote! {
+            #(#cfgs)*
+            #gate
+            fn #deserialize_fn<'de, D: serde_hash::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<#decoded_ty, D::Error> {
+                #[allow(unused_imports)]
+                use serde_hash::serde::de;
+
+                let value = #read;
+                Ok(#decoded)
+            }
+        };
+
+        if !two_entries {
+            // A suffixed field only writes the hash, under the suffixed key.
+            let attrs = if options.suffix.is_some() {
+                let mut attrs = without_serde_items(&attrs, &["rename"])?;
+                attrs.push(syn::parse_quote!(#[serde(rename = #hashed_key)]));
+                attrs
+            } else {
+                attrs
+            };
+            // serde only treats a missing `Option` as `None` without a `with` attribute.
+            let default = if is_option(ty) && self.default.is_none() && !container_default {
+                quote!(#[serde(default)])
+            } else {
+                quote!()
+            };
+            let serialize_attr = options.gate_attr(&syn::parse_quote!(
+                serde(serialize_with = #serialize_with)
+            ));
+            let deserialize_attr = options.gate_attr(&syn::parse_quote!(
+                serde(deserialize_with = #deserialize_with #(, alias = #alias_decode)*)
+            ));
+            return Ok(ShadowField {
+                ser: quote! {
+                    #(#attrs)*
+                    #serialize_attr
+                    #ident: #ty,
+                },
+                de: quote! {
+                    #(#attrs)*
+                    #deserialize_attr
+                    #default
+                    #ident: #ty,
+                },
+                fns: quote!(#serialize #deserialize),
+                init: quote!(#(#cfgs)* #ident: shadow.#ident,),
+                default_init: quote!(#(#cfgs)* #ident: value.#ident,),
+            });
+        }
+
+        // The raw value goes under the field's key along with its serde aliases, and the
+        // hash under the suffixed key along with `alias_decode`. Either one is enough to
+        // deserialize, so both are optional in the shadow and the hash wins if both are
+        // present.
+        let key = &self.key;
+        let aliases = &self.aliases;
+        let raw = quote::format_ident!("__raw_{}", base);
+        let raw_getter = quote::format_ident!("__get_raw_{}", base);
+        let raw_deserialize = quote::format_ident!("__deserialize_raw_{}", base);
+        let raw_getter_name = raw_getter.to_string();
+        let raw_deserialize_with = raw_deserialize.to_string();
+        let skip = find_serde_meta(self.attrs, "skip_serializing_if")
+            .map(|meta| quote!(#[serde(#meta)]));
+        let hashed_attrs = without_serde_items(&attrs, &["rename", "alias", "default"])?;
+        let default = if container_default && self.default.is_none() {
+            quote!()
+        } else {
+            quote!(default,)
+        };
+        let missing = match &self.default {
+            Some(FieldDefault::Trait) => quote!(Default::default()),
+            Some(FieldDefault::Path(path)) => quote!(#path()),
+            None if is_option(ty) => quote!(None),
+            None => quote!(return Err(de::Error::missing_field(#hashed_key))),
+        };
+        Ok(ShadowField {
+            ser: quote! {
+                #(#cfgs)*
+                #[serde(rename = #key, getter = #raw_getter_name)]
+                #skip
+                #raw: #ty,
+                #(#hashed_attrs)*
+                #[serde(rename = #hashed_key, serialize_with = #serialize_with)]
+                #ident: #ty,
+            },
+            de: quote! {
+                #(#cfgs)*
+                #[serde(rename = #key, #default deserialize_with = #raw_deserialize_with #(, alias = #aliases)*)]
+                #raw: Option<#ty>,
+                #(#hashed_attrs)*
+                #[serde(rename = #hashed_key, #default deserialize_with = #deserialize_with #(, alias = #alias_decode)*)]
+                #ident: Option<#ty>,
+            },
+            fns: quote! {
+                #(#cfgs)*
+                fn #raw_getter #impl_generics (value: &#name #ty_generics) -> #ty #where_clause {
+                    Clone::clone(&value.#ident)
+                }
+
+                #(#cfgs)*
+                fn #raw_deserialize<'de, D: serde_hash::serde::Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Option<#ty>, D::Error> {
+                    <#ty as serde_hash::serde::Deserialize>::deserialize(deserializer).map(Some)
+                }
+
+                #serialize
+                #deserialize
+            },
+            init: quote! {
+                #(#cfgs)*
+                #ident: match (shadow.#ident, shadow.#raw) {
+                    (Some(value), _) | (None, Some(value)) => value,
+                    (None, None) => #missing,
+                },
+            },
+            // The default counts as the raw value, so any key in the input overrides it.
+            default_init: quote!(#(#cfgs)* #ident: None, #raw: Some(value.#ident),),
+        })
+    }
+}
+
+impl DeriveField<'_> {
+    /// The value serialized for this hashed field, given an expression borrowing the
+    /// field's value.
+    fn serialize_value(&self, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        if let Some(with) = &self.with {
+            return quote!(serde_hash::serde_impl::SerializeWith::<_, #with>::new(#value));
+        }
+        match &self.hash {
+            Some((_, options)) if options.redact => {
+                quote!(serde_hash::serde_impl::Redacted(#value))
+            }
+            _ => quote!(serde_hash::serde_impl::Encoded(#value)),
+        }
+    }
+
+    /// The expression that reads this hashed field's value, given how to read a value of a
+    /// given type, or through a given `DeserializeSeed`, from the deserializer.
+    fn read_value(
+        &self,
+        next: impl Fn(proc_macro2::TokenStream) -> proc_macro2::TokenStream,
+        next_seed: impl Fn(proc_macro2::TokenStream) -> proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let key = &self.key;
+        let ty = self.ty;
+        if let Some((_, partner_ty)) = self.partner {
+            let decoded = next_seed(quote! {
+                serde_hash::serde_impl::PackedField::<#ty, #partner_ty>::new(#key)
+            });
+            return decoded;
+        }
+        if let Some(with) = &self.with {
+            let decoded = next(quote!(serde_hash::serde_impl::DeserializeWith<#ty, #with>));
+            return quote!(#decoded.into_inner());
+        }
+        match &self.hash {
+            None => next(quote!(#ty)),
+            Some((_, options)) if options.redact && options.accept_raw => next(quote!(#ty)),
+            Some((_, options)) if options.redact => {
+                let skip = next(quote!(de::IgnoredAny));
+                quote! {{
+                    let _ = #skip;
+                    serde_hash::serde_impl::redacted_field(#key)?
+                }}
+            }
+            Some(_) => {
+                let decoded = next_seed(quote! {
+                    serde_hash::serde_impl::DecodedField::<#ty>::new(Some(#key))
+                });
+                quote!(#decoded.0)
+            }
+        }
+    }
+}
+
 // --- New #[serde_hash] attribute macro ---
 
 /// Attribute macro that enables `#[serde(hash)]` on struct fields.
@@ -99,11 +1386,30 @@ fn determine_with_path(ty: &Type) -> Option<&'static str> {
 /// during deserialization. All other serde attributes (`rename`, `alias`, `default`,
 /// `skip`, etc.) work normally alongside `hash`.
 ///
+/// Use `#[hash(redact)]` to serialize a salted one-way digest instead; such fields
+/// can't be deserialized unless `#[hash(redact, accept_raw)]` is used, which accepts
+/// the raw numeric value. `#[hash(encode_with = "path", decode_with = "path")]` swaps
+/// in custom `fn(u64) -> String` / `fn(&str) -> Result<u64, E>` transforms, and
+/// `#[hash(min_length = N)]` overrides the global minimum length for one field.
+/// `#[hash(mask = "last4")]` (or `"firstN"`) serializes the digits partially masked,
+/// e.g. `"****1234"`, and deserializes only the raw numeric value.
+/// `#[hash(range = "1..=1000")]` rejects decoded values outside the range, which is
+/// written in the field's numeric type. `#[hash(scale = 100)]` hashes an `f32`/`f64`
+/// field (or a `Vec`/`Option` of one) as a fixed-point integer at that scale.
+/// `#[hash(alias_decode = "legacy_id")]` also reads
+/// the hash from a field's former name, for APIs migrating to a new one.
+/// `#[hash(nonzero)]` fails serialization when the field holds 0, a common "unset" id.
+/// `#[hash(cfg(feature = "public-api"))]` only hashes the field when the predicate holds
+/// and writes the raw value otherwise.
+/// `#[hash(stringify_only)]` writes the number as a string of its digits, e.g. for ids
+/// above 2^53 read by JavaScript, and parses it back without hashing.
+///
 /// # Supported field types
 /// - `u8`, `u16`, `u32`, `u64`, `u128`, `usize`
 /// - `Vec<T>` where `T` is one of the above
 /// - `Option<T>` where `T` is one of the above
 /// - `Option<Vec<T>>` where `T` is one of the above
+/// - Deeper nestings of `Vec` and `Option`, such as `Vec<Vec<T>>`
 ///
 /// # Example
 /// ```ignore
@@ -127,37 +1433,96 @@ pub fn serde_hash(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
     let mut input = parse_macro_input!(item as DeriveInput);
 
+    // `#[hash(all)]` on the struct is ours; serde's own container attributes stay as they are.
+    let mut all = false;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("hash")) {
+        let parsed = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("all") {
+                all = true;
+                Ok(())
+            } else {
+                Err(meta.error(
+                    "#[serde_hash] only supports `all` on the struct; use serde's container \
+                     attributes for everything else",
+                ))
+            }
+        });
+        if let Err(e) = parsed {
+            return e.to_compile_error().into();
+        }
+    }
+    input.attrs.retain(|attr| !attr.path().is_ident("hash"));
+    // serde's derive names serde through serde_hash's re-export, so the crate using the
+    // macro doesn't need serde as a dependency of its own.
+    if find_serde_meta(&input.attrs, "crate").is_none() {
+        input.attrs.push(syn::parse_quote!(#[serde(crate = "serde_hash::serde")]));
+    }
+
+    if let Err(e) = check_named_struct("#[serde_hash]", &input.data) {
+        return e.to_compile_error().into();
+    }
     let fields = match &mut input.data {
         Data::Struct(data) => match &mut data.fields {
             Fields::Named(fields) => &mut fields.named,
-            _ => {
-                return syn::Error::new_spanned(
-                    &input.ident,
-                    "#[serde_hash] only supports structs with named fields",
-                )
-                .to_compile_error()
-                .into();
-            }
+            _ => unreachable!("checked by check_named_struct"),
         },
-        _ => {
-            return syn::Error::new_spanned(
-                &input.ident,
-                "#[serde_hash] can only be applied to structs",
-            )
-            .to_compile_error()
-            .into();
-        }
+        _ => unreachable!("checked by check_named_struct"),
     };
 
+    let container = input.ident.clone();
+    let rename_all = match serde_rename_all(&input.attrs) {
+        Ok(rule) => rule,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let mut helpers = Vec::new();
+    let mut hashed_keys = Vec::new();
     for field in fields.iter_mut() {
         let mut needs_hash = false;
+        let mut skip = false;
+        let mut options = HashOptions::default();
         let field_ty = field.ty.clone();
 
         let mut new_attrs = Vec::new();
         for attr in &field.attrs {
-            // Standalone #[hash] attribute
+            // Standalone #[hash] or #[hash(...)] attribute
             if attr.path().is_ident("hash") {
                 needs_hash = true;
+                match HashOptions::from_attr(attr) {
+                    Ok(parsed) if parsed.skip && !all => {
+                        return syn::Error::new_spanned(
+                            attr,
+                            "`skip` only applies with #[hash(all)] on the struct",
+                        )
+                        .to_compile_error()
+                        .into();
+                    }
+                    Ok(parsed) if parsed.skip => {
+                        needs_hash = false;
+                        skip = true;
+                    }
+                    Ok(parsed)
+                        if parsed.primary || parsed.suffix.is_some() || parsed.pack_with.is_some() =>
+                    {
+                        let option = if parsed.primary {
+                            "primary"
+                        } else if parsed.suffix.is_some() {
+                            "suffix"
+                        } else {
+                            "pack_with"
+                        };
+                        return syn::Error::new_spanned(
+                            attr,
+                            format!("`{}` only applies to #[derive(HashIds)]", option),
+                        )
+                        .to_compile_error()
+                        .into();
+                    }
+                    Ok(parsed) => match check_custom_codec(attr, &field_ty, &parsed) {
+                        Ok(()) => options = parsed,
+                        Err(e) => return e.to_compile_error().into(),
+                    },
+                    Err(e) => return e.to_compile_error().into(),
+                }
                 continue;
             }
 
@@ -194,10 +1559,77 @@ pub fn serde_hash(_attr: TokenStream, item: TokenStream) -> TokenStream {
             }
         }
 
+        needs_hash |= all && !skip && hashed_by_default(field);
         if needs_hash {
-            match determine_with_path(&field_ty) {
+            if let Err(e) = reject_serde_with(&new_attrs) {
+                return e.to_compile_error().into();
+            }
+            // Everything pushed from here on is how the field is hashed.
+            let unhashed = new_attrs.len();
+            // serde reads former names through its own aliases.
+            for alias in &options.alias_decode {
+                new_attrs.push(syn::parse_quote!(#[serde(alias = #alias)]));
+            }
+            // A stringified field holds no hash, so it isn't listed.
+            match serde_rename(&new_attrs) {
+                Ok(_) if options.stringify_only => {}
+                Ok(Some(key)) => hashed_keys.push((key, options.cfg.clone())),
+                Ok(None) => {
+                    let key = syn::ext::IdentExt::unraw(field.ident.as_ref().unwrap()).to_string();
+                    let key = match rename_all {
+                        Some(rule) => rule.apply(&key),
+                        None => key,
+                    };
+                    hashed_keys.push((key, options.cfg.clone()));
+                }
+                Err(e) => return e.to_compile_error().into(),
+            }
+            let with_path = match (options.scale, is_nested_float(&field_ty)) {
+                // Scaled fields always go through `field_with`; the path is never used.
+                (Some(_), true) => Some("serde_hash::scaled"),
+                (Some(_), false) => {
+                    return syn::Error::new_spanned(
+                        &field.ty,
+                        "`scale` requires an f32 or f64 field, or a Vec or Option of one",
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+                (None, _) => determine_with_path(&field_ty),
+            };
+            match with_path {
                 Some(path) => {
-                    new_attrs.push(syn::parse_quote!(#[serde(with = #path)]));
+                    let field_name = field.ident.as_ref().unwrap();
+                    if let Some((with, tokens)) =
+                        field_with(&container, field_name, &field_ty, &options)
+                    {
+                        let with = with.to_token_stream();
+                        let ty = field_ty.to_token_stream();
+                        let serialize_with = format!(
+                            "<{} as serde_hash::serde_impl::FieldWith<{}>>::serialize",
+                            with, ty
+                        );
+                        let deserialize_with = format!(
+                            "<{} as serde_hash::serde_impl::FieldWith<{}>>::deserialize",
+                            with, ty
+                        );
+                        new_attrs.push(syn::parse_quote!(
+                            #[serde(serialize_with = #serialize_with, deserialize_with = #deserialize_with)]
+                        ));
+                        helpers.extend(tokens.map(|tokens| options.gate_items(tokens)));
+                    } else {
+                        let path = match (options.redact, options.accept_raw) {
+                            (true, true) => "serde_hash::serde_impl::redact_accept_raw",
+                            (true, false) => "serde_hash::serde_impl::redact",
+                            _ => path,
+                        };
+                        new_attrs.push(syn::parse_quote!(#[serde(with = #path)]));
+                    }
+                    // serde only treats a missing `Option` as `None` without a `with`
+                    // attribute; formats like TOML omit `None` fields entirely.
+                    if is_option(&field_ty) && find_serde_meta(&new_attrs, "default").is_none() {
+                        new_attrs.push(syn::parse_quote!(#[serde(default)]));
+                    }
                 }
                 None => {
                     let field_name = field.ident.as_ref().unwrap();
@@ -205,8 +1637,8 @@ pub fn serde_hash(_attr: TokenStream, item: TokenStream) -> TokenStream {
                         &field.ty,
                         format!(
                             "The `hash` attribute on field '{}' requires a numeric type \
-                             (u8, u16, u32, u64, u128, usize), Vec<numeric>, \
-                             Option<numeric>, or Option<Vec<numeric>>",
+                             (u8, u16, u32, u64, u128, usize), or a Vec or Option \
+                             of one, nested to any depth (e.g. Vec<Vec<numeric>>)",
                             field_name
                         ),
                     )
@@ -214,12 +1646,105 @@ pub fn serde_hash(_attr: TokenStream, item: TokenStream) -> TokenStream {
                     .into();
                 }
             }
+            for attr in &mut new_attrs[unhashed..] {
+                *attr = options.gate_attr(&attr.meta);
+            }
         }
 
         field.attrs = new_attrs;
     }
 
-    quote!(#input).into()
+    let hashed_fields = hashed_fields_impl(&input.ident, &input.generics, &hashed_keys);
+    quote!(#input #(#helpers)* #hashed_fields).into()
+}
+
+/// Alias of [`macro@serde_hash`] for code that marks fields with `#[hash]`.
+///
+/// Rewrites every `#[hash]` / `#[hash(...)]` field into the matching `#[serde(with = ...)]`
+/// attribute and leaves the impls to serde's own derive, so all serde attributes keep
+/// working. This is the drop-in replacement for `#[derive(HashIds)]`: swap the derive for
+/// `#[hashable]` plus `#[derive(Serialize, Deserialize)]` and the field attributes stay as
+/// they are.
+///
+/// # Example
+/// ```ignore
+/// use serde::{Deserialize, Serialize};
+///
+/// #[serde_hash::hashable]
+/// #[derive(Serialize, Deserialize)]
+/// #[serde(rename_all = "camelCase")]
+/// pub struct User {
+///     #[hash]
+///     pub user_id: u64,
+///     #[hash(min_length = 16)]
+///     pub group_id: Option<u64>,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn hashable(attr: TokenStream, item: TokenStream) -> TokenStream {
+    serde_hash(attr, item)
+}
+
+/// Checks a custom alphabet at compile time and expands to a
+/// `&'static serde_hash::alphabet::Alphabet`.
+///
+/// The rules are the same as `serde_hash::alphabet::validate_alphabet`: at least 16
+/// characters, no duplicates, no spaces and no characters that combine into a single
+/// grapheme. A bad alphabet fails the build instead of panicking at startup.
+///
+/// # Example
+/// ```ignore
+/// use serde_hash::hashids::SerdeHashOptions;
+///
+/// const ALPHABET: &serde_hash::alphabet::Alphabet = serde_hash::alphabet!("0123456789abcdef");
+///
+/// SerdeHashOptions::new().with_alphabet(ALPHABET).build();
+/// ```
+#[cfg(feature = "alphabet-macro")]
+#[proc_macro]
+pub fn alphabet(input: TokenStream) -> TokenStream {
+    let lit = syn::parse_macro_input!(input as syn::LitStr);
+    let alphabet = lit.value();
+    let mut seen = Vec::new();
+    for c in alphabet.chars() {
+        let error = if c == ' ' {
+            "alphabet may not contain spaces".to_string()
+        } else if seen.contains(&c) {
+            format!("alphabet contains the character '{}' more than once", c)
+        } else {
+            seen.push(c);
+            continue;
+        };
+        return syn::Error::new_spanned(&lit, error).to_compile_error().into();
+    }
+    if seen.len() < 16 {
+        return syn::Error::new_spanned(
+            &lit,
+            format!(
+                "alphabet must contain at least 16 unique characters, but has {}",
+                seen.len()
+            ),
+        )
+        .to_compile_error()
+        .into();
+    }
+    for &first in &seen {
+        for &second in &seen {
+            let pair: String = [first, second].into_iter().collect();
+            if unicode_segmentation::UnicodeSegmentation::graphemes(pair.as_str(), true)
+                .nth(1)
+                .is_none()
+            {
+                let error = format!(
+                    "alphabet characters '{}' (U+{:04X}) and '{}' (U+{:04X}) combine into a \
+                     single grapheme",
+                    first, first as u32, second, second as u32
+                );
+                return syn::Error::new_spanned(&lit, error).to_compile_error().into();
+            }
+        }
+    }
+    quote!(&serde_hash::alphabet::Alphabet::Custom(#lit)).into()
 }
 
 // --- Legacy #[hash] passthrough attribute (kept for backward compatibility) ---
@@ -231,381 +1756,580 @@ pub fn hash(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
 // --- Legacy #[derive(HashIds)] (kept for backward compatibility) ---
 
-#[proc_macro_derive(HashIds, attributes(hash))]
+#[proc_macro_derive(HashIds, attributes(hash, serde))]
 pub fn hash_id_derive(input: TokenStream) -> TokenStream {
-    use syn::{parse_macro_input, Data, DeriveInput, Fields};
+    use syn::{ext::IdentExt, parse_macro_input, Data, DeriveInput, Fields};
 
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
-    let mut errors = Vec::new();
-
-    // Validate #[hash] fields.
-    if let Data::Struct(data) = &input.data {
-        if let Fields::Named(fields) = &data.fields {
-            for field in fields.named.iter() {
-                let has_hash = field.attrs.iter().any(|attr| attr.path().is_ident("hash"));
-                if has_hash {
-                    if let Some(field_name) = &field.ident {
-                        if !is_numeric_type(&field.ty)
-                            && !is_vector_of_numeric(&field.ty)
-                            && !is_option_of_numeric(&field.ty)
-                            && !is_option_of_vector_of_numeric(&field.ty)
-                        {
-                            errors.push(quote! {
-                                compile_error!(concat!("The #[hash] attribute can only be applied to numeric fields, vectors of numeric fields, or Option types of these, but field '",
-                                    stringify!(#field_name),
-                                    "' has type '",
-                                    stringify!(#field.ty), "'"));
-                            });
+    if let Err(e) = check_generics(&input.generics) {
+        return e.to_compile_error().into();
+    }
+    let generics = &input.generics;
+    let container = match ContainerOptions::from_attrs(&input.attrs) {
+        Ok(container) => container,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let serde_rename_all = match serde_rename_all(&input.attrs) {
+        Ok(rule) => rule,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let helper_methods = if container.helpers {
+        json_helpers(name, generics, &input.vis)
+    } else {
+        quote!()
+    };
+
+    // Unit structs have nothing to hash; serialize them the way serde's derive does.
+    if let Data::Struct(data) = &input.data
+        && let Fields::Unit = data.fields
+    {
+        let impls = unit_struct_impls(name);
+        let hashed_fields = hashed_fields_impl(name, generics, &[]);
+        return quote!(#impls #hashed_fields #helper_methods).into();
+    }
+    if let Err(e) = check_named_struct("#[derive(HashIds)]", &input.data) {
+        return e.to_compile_error().into();
+    }
+
+    // Collect the named fields along with their #[hash] settings.
+    let mut fields = Vec::new();
+    let mut helpers = Vec::new();
+    if let Data::Struct(data) = &input.data
+        && let Fields::Named(named) = &data.fields
+    {
+        for field in named.named.iter() {
+            let hash = match field.attrs.iter().find(|attr| attr.path().is_ident("hash")) {
+                None if container.all && hashed_by_default(field) => {
+                    let options = HashOptions {
+                        implicit: true,
+                        ..HashOptions::default()
+                    };
+                    hash_kind(&field.ty).map(|kind| (kind, options))
+                }
+                None => None,
+                Some(attr) => {
+                    let options = match HashOptions::from_attr(attr)
+                        .and_then(|options| check_custom_codec(attr, &field.ty, &options).map(|()| options))
+                    {
+                        Ok(options) => options,
+                        Err(e) => return e.to_compile_error().into(),
+                    };
+                    if options.skip && !container.all {
+                        return syn::Error::new_spanned(
+                            attr,
+                            "`skip` only applies with #[hash(all)] on the struct",
+                        )
+                        .to_compile_error()
+                        .into();
+                    }
+                    match hash_kind(&field.ty) {
+                        _ if options.skip => None,
+                        _ if options.scale.is_some() && is_nested_float(&field.ty) => {
+                            Some((HashKind::Scaled, options))
+                        }
+                        _ if options.scale.is_some() => {
+                            return syn::Error::new_spanned(
+                                &field.ty,
+                                format!(
+                                    "`scale` requires an f32 or f64 field, or a Vec or Option of \
+                                     one, but field '{}' has type '{}'",
+                                    field.ident.as_ref().unwrap(),
+                                    field.ty.to_token_stream()
+                                ),
+                            )
+                            .to_compile_error()
+                            .into();
+                        }
+                        Some(kind) if options.primary && kind != HashKind::Numeric => {
+                            return syn::Error::new_spanned(
+                                attr,
+                                "the primary id must be a plain numeric field",
+                            )
+                            .to_compile_error()
+                            .into();
+                        }
+                        Some(_) if options.primary && is_wide_numeric(&field.ty) => {
+                            return syn::Error::new_spanned(
+                                attr,
+                                "the primary id can't be a u128 field, since hash ids decode to u64",
+                            )
+                            .to_compile_error()
+                            .into();
+                        }
+                        Some(kind) => Some((kind, options)),
+                        None => {
+                            return syn::Error::new_spanned(
+                                &field.ty,
+                                format!(
+                                    "The #[hash] attribute can only be applied to numeric fields, \
+                                     or vectors and Option types of these nested to any depth, \
+                                     but field '{}' has type '{}'",
+                                    field.ident.as_ref().unwrap(),
+                                    field.ty.to_token_stream()
+                                ),
+                            )
+                            .to_compile_error()
+                            .into();
                         }
                     }
                 }
-            }
+            };
+            let ident = field.ident.as_ref().unwrap();
+            let with = match &hash {
+                Some((_, options)) => {
+                    field_with(name, ident, &field.ty, options).map(|(with, tokens)| {
+                        helpers.extend(tokens.map(|tokens| options.gate_items(tokens)));
+                        with
+                    })
+                }
+                None => None,
+            };
+            let aliases = match serde_aliases(&field.attrs) {
+                Ok(aliases) => aliases,
+                Err(e) => return e.to_compile_error().into(),
+            };
+            let default = match serde_default(&field.attrs, hash.is_some()) {
+                Ok(default) => default,
+                Err(e) => return e.to_compile_error().into(),
+            };
+            let borrowed = match field_borrows(field) {
+                Ok(borrowed) => borrowed,
+                Err(e) => return e.to_compile_error().into(),
+            };
+            let key = match serde_rename(&field.attrs) {
+                Ok(Some(key)) => key,
+                Ok(None) => {
+                    let field_name = ident.unraw().to_string();
+                    match container.rename_all.or(serde_rename_all) {
+                        Some(rule) => rule.apply(&field_name),
+                        None => field_name,
+                    }
+                }
+                Err(e) => return e.to_compile_error().into(),
+            };
+            fields.push(DeriveField {
+                ident,
+                key,
+                ty: &field.ty,
+                attrs: &field.attrs,
+                hash,
+                with,
+                aliases,
+                partner: None,
+                packed_into: None,
+                default,
+                borrowed,
+            });
         }
     }
 
-    // Separate fields into different categories based on their types.
-    let numeric_hash_fields = if let Data::Struct(data) = &input.data {
-        if let Fields::Named(fields) = &data.fields {
-            fields
-                .named
-                .iter()
-                .filter_map(|field| {
-                    let has_hash = field.attrs.iter().any(|attr| attr.path().is_ident("hash"));
-                    if has_hash && is_numeric_type(&field.ty) {
-                        field.ident.as_ref()
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>()
-        } else {
-            Vec::new()
-        }
-    } else {
-        Vec::new()
+    if let Err(e) = pair_packed_fields(&mut fields) {
+        return e.to_compile_error().into();
+    }
+
+    let has_hash_id = match has_hash_id_impl(name, generics, &fields, container.prefix.as_ref()) {
+        Ok(tokens) => tokens,
+        Err(e) => return e.to_compile_error().into(),
     };
 
-    let vector_hash_fields = if let Data::Struct(data) = &input.data {
-        if let Fields::Named(fields) = &data.fields {
-            fields
-                .named
-                .iter()
-                .filter_map(|field| {
-                    let has_hash = field.attrs.iter().any(|attr| attr.path().is_ident("hash"));
-                    if has_hash && is_vector_of_numeric(&field.ty) {
-                        field.ident.as_ref()
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>()
-        } else {
-            Vec::new()
-        }
-    } else {
+    // A transparent struct has no keys of its own.
+    let hashed_keys: Vec<_> = if container.transparent {
         Vec::new()
+    } else {
+        fields
+            .iter()
+            .filter(|field| field.packed_into.is_none())
+            .filter_map(|field| {
+                let (_, options) = field.hash.as_ref()?;
+                let listed = !options.stringify_only;
+                listed.then(|| (field.hashed_key(), options.cfg.clone()))
+            })
+            .collect()
     };
+    let hashed_fields = hashed_fields_impl(name, generics, &hashed_keys);
 
-    let option_numeric_hash_fields = if let Data::Struct(data) = &input.data {
-        if let Fields::Named(fields) = &data.fields {
-            fields
-                .named
-                .iter()
-                .filter_map(|field| {
-                    let has_hash = field.attrs.iter().any(|attr| attr.path().is_ident("hash"));
-                    if has_hash && is_option_of_numeric(&field.ty) {
-                        field.ident.as_ref()
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>()
-        } else {
-            Vec::new()
-        }
-    } else {
-        Vec::new()
+    let impls = match shadow_impls(&input, &container, &fields) {
+        Ok(impls) => impls,
+        Err(e) => return e.to_compile_error().into(),
     };
 
-    let option_vector_hash_fields = if let Data::Struct(data) = &input.data {
-        if let Fields::Named(fields) = &data.fields {
-            fields
-                .named
-                .iter()
-                .filter_map(|field| {
-                    let has_hash = field.attrs.iter().any(|attr| attr.path().is_ident("hash"));
-                    if has_hash && is_option_of_vector_of_numeric(&field.ty) {
-                        field.ident.as_ref()
-                    } else {
-                        None
+    quote! {
+        #(#helpers)*
+
+        #has_hash_id
+
+        #hashed_fields
+
+        #helper_methods
+
+        #impls
+    }
+    .into()
+}
+
+/// `Serialize`/`Deserialize` impls that leave the work to serde's own derive.
+///
+/// Two hidden shadow structs mirror the fields along with their serde attributes, so
+/// every serde attribute works as it does with serde's derive, and hashed fields get
+/// `with` functions on top (see [`DeriveField::shadow`]). One derives `Serialize` with
+/// `#[serde(remote = "...")]`, which serializes the struct in place; the other derives
+/// `Deserialize` and is converted into the struct.
+fn shadow_impls(
+    input: &syn::DeriveInput,
+    container: &ContainerOptions,
+    fields: &[DeriveField],
+) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let generics = &input.generics;
+    if container.transparent {
+        let [field] = fields else {
+            return Err(syn::Error::new_spanned(
+                name,
+                "a transparent struct must have exactly one field",
+            ));
+        };
+        if let Some((_, options)) = &field.hash
+            && (options.suffix.is_some() || options.pack_with.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                field.ident,
+                "`suffix` and `pack_with` can't be used on a transparent struct's field",
+            ));
+        }
+    }
+    if let Some(rename_all) = find_serde_meta(&input.attrs, "rename_all")
+        && container.rename_all.is_some()
+    {
+        return Err(syn::Error::new_spanned(
+            rename_all,
+            "use either #[hash(rename_all)] or #[serde(rename_all)], not both",
+        ));
+    }
+
+    // The struct's serde attributes carry over, and ours are translated to serde's.
+    let serde_attrs: Vec<_> =
+        input.attrs.iter().filter(|attr| attr.path().is_ident("serde")).cloned().collect();
+    // The shadows name serde through serde_hash's re-export, so a `crate` of the struct's
+    // own doesn't apply to them.
+    let mut attrs = without_serde_items(&serde_attrs, &["default", "crate"])?;
+    if find_serde_meta(&serde_attrs, "rename").is_none() {
+        let rename = name.to_string();
+        attrs.push(syn::parse_quote!(#[serde(rename = #rename)]));
+    }
+    if let Some(rule) = container.rename_all {
+        let rule = rule.name();
+        attrs.push(syn::parse_quote!(#[serde(rename_all = #rule)]));
+    }
+    if container.transparent && find_serde_meta(&serde_attrs, "transparent").is_none() {
+        attrs.push(syn::parse_quote!(#[serde(transparent)]));
+    }
+
+    let container_default = find_serde_meta(&serde_attrs, "default");
+    let shadows = fields
+        .iter()
+        .map(|field| field.shadow(name, generics, container_default.is_some()))
+        .collect::<syn::Result<Vec<_>>>()?;
+    let ser_fields = shadows.iter().map(|shadow| &shadow.ser);
+    let de_fields = shadows.iter().map(|shadow| &shadow.de);
+    let fns = shadows.iter().map(|shadow| &shadow.fns);
+    let inits = shadows.iter().map(|shadow| &shadow.init);
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let de_generics = deserialize_generics(generics, fields);
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
+    let remote = name.to_string();
+    let hashed_fields = fields.iter().filter(|field| field.hash.is_some()).count();
+
+    // A container default is the struct's own `Default`, carried over to the shadow.
+    let mut de_attrs = attrs.clone();
+    let default = match container_default {
+        None => quote!(),
+        Some(meta) => {
+            let value = match meta {
+                syn::Meta::Path(_) => quote!(Default::default()),
+                meta => {
+                    let path = serde_meta_path(&meta)?;
+                    quote!(#path())
+                }
+            };
+            let default_inits = shadows.iter().map(|shadow| &shadow.default_init);
+            de_attrs.push(syn::parse_quote!(#[serde(default)]));
+            quote! {
+                impl #impl_generics Default for __DeserializeShadow #ty_generics #where_clause {
+                    fn default() -> Self {
+                        let value: #name #ty_generics = #value;
+                        Self { #(#default_inits)* }
                     }
-                })
-                .collect::<Vec<_>>()
-        } else {
-            Vec::new()
+                }
+            }
         }
-    } else {
-        Vec::new()
     };
+    Ok(quote! {
+        const _: () = {
+            #[derive(serde_hash::serde::Serialize)]
+            #[serde(crate = "serde_hash::serde")]
+            #[serde(remote = #remote)]
+            #(#attrs)*
+            #[allow(dead_code)]
+            struct __SerializeShadow #generics #where_clause {
+                #(#ser_fields)*
+            }
 
-    let non_hash_fields = if let Data::Struct(data) = &input.data {
-        if let Fields::Named(fields) = &data.fields {
-            fields
-                .named
-                .iter()
-                .filter_map(|field| {
-                    let has_hash = field.attrs.iter().any(|attr| attr.path().is_ident("hash"));
-                    if !has_hash {
-                        field.ident.as_ref()
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>()
-        } else {
-            Vec::new()
+            #[derive(serde_hash::serde::Deserialize)]
+            #[serde(crate = "serde_hash::serde")]
+            #(#de_attrs)*
+            struct __DeserializeShadow #generics #where_clause {
+                #(#de_fields)*
+            }
+
+            #default
+
+            #(#fns)*
+
+            impl #impl_generics serde_hash::serde::Serialize for #name #ty_generics #where_clause {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where S: serde_hash::serde::Serializer {
+                    let span = serde_hash::__private::DeriveSpan::enter(
+                        #remote,
+                        "serialize",
+                        #hashed_fields,
+                    );
+                    span.finish(__SerializeShadow::serialize(self, serializer))
+                }
+            }
+
+            impl #de_impl_generics serde_hash::serde::Deserialize<'de>
+            for #name #ty_generics #where_clause {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: serde_hash::serde::Deserializer<'de> {
+                    #[allow(unused_imports)]
+                    use serde_hash::serde::de;
+
+                    let span = serde_hash::__private::DeriveSpan::enter(
+                        #remote,
+                        "deserialize",
+                        #hashed_fields,
+                    );
+                    span.finish((move || -> Result<Self, D::Error> {
+                        let shadow =
+                            <__DeserializeShadow #ty_generics
+                                as serde_hash::serde::Deserialize<'de>>::deserialize(deserializer)?;
+                        Ok(#name { #(#inits)* })
+                    })())
+                }
+            }
+        };
+    })
+}
+
+/// Links each `#[hash(pack_with = "...")]` field to the field it names.
+fn pair_packed_fields(fields: &mut [DeriveField]) -> syn::Result<()> {
+    for index in 0..fields.len() {
+        let Some((kind, HashOptions { pack_with: Some(name), .. })) = &fields[index].hash else {
+            continue;
+        };
+        let name = name.clone();
+        if *kind != HashKind::Numeric {
+            return Err(syn::Error::new_spanned(
+                fields[index].ty,
+                "`pack_with` only applies to plain numeric fields",
+            ));
         }
-    } else {
-        Vec::new()
+        let Some(partner) = fields.iter().position(|field| *field.ident == name) else {
+            return Err(syn::Error::new_spanned(
+                &name,
+                format!("no field named `{}` to pack with", name),
+            ));
+        };
+        // `#[hash(all)]` doesn't stop a field from being packed into another's hash.
+        if fields[partner].hash.as_ref().is_some_and(|(_, options)| options.implicit) {
+            fields[partner].hash = None;
+        }
+        let other = &fields[partner];
+        if partner == index || other.hash.is_some() || other.packed_into.is_some() {
+            return Err(syn::Error::new_spanned(
+                &name,
+                "`pack_with` must name another field without a #[hash] attribute",
+            ));
+        }
+        if !is_numeric_type(other.ty) {
+            return Err(syn::Error::new_spanned(
+                other.ty,
+                "`pack_with` only applies to plain numeric fields",
+            ));
+        }
+        let (ident, ty) = (other.ident, other.ty);
+        fields[partner].packed_into = Some(fields[index].key.clone());
+        fields[index].partner = Some((ident, ty));
+    }
+    Ok(())
+}
+
+/// The `HasHashId` and `PublicId` impls for the `#[hash(primary)]` field, or the first
+/// plain numeric `#[hash]` field. Nothing is generated if there is no such field.
+fn has_hash_id_impl(
+    name: &syn::Ident,
+    generics: &syn::Generics,
+    fields: &[DeriveField],
+    prefix: Option<&syn::LitStr>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut primaries = fields
+        .iter()
+        .filter(|field| field.hash.as_ref().is_some_and(|(_, options)| options.primary));
+    let primary = match (primaries.next(), primaries.next()) {
+        (_, Some(second)) => {
+            return Err(syn::Error::new_spanned(
+                second.ident,
+                "only one field can be marked #[hash(primary)]",
+            ));
+        }
+        (Some(primary), None) => primary,
+        (None, None) => match fields.iter().find(|field| {
+            field.hash.as_ref().is_some_and(|(kind, options)| {
+                *kind == HashKind::Numeric
+                    && !is_wide_numeric(field.ty)
+                    && !options.redact
+                    && options.mask.is_none()
+                    && !options.stringify_only
+                    && options.pack_with.is_none()
+            })
+        }) {
+            Some(field) => field,
+            None if prefix.is_some() => {
+                return Err(syn::Error::new_spanned(
+                    prefix,
+                    "`prefix` needs a plain numeric #[hash] field to use as the id",
+                ));
+            }
+            None => return Ok(quote!()),
+        },
     };
 
-    // Get the total number of fields.
-    let field_count = if let Data::Struct(data) = &input.data {
-        if let Fields::Named(fields) = &data.fields {
-            fields.named.len()
-        } else {
-            0
+    let ident = primary.ident;
+    let (_, options) = primary.hash.as_ref().unwrap();
+    let (encode, decode) = match options.min_length {
+        Some(min_length) => (
+            quote!(serde_hash::hashids::encode_single_with_min_length(value, #min_length)),
+            quote!(serde_hash::hashids::decode_single_with_min_length(hash, #min_length)),
+        ),
+        None => {
+            let encode = match &options.encode_with {
+                Some(path) => quote!(#path(value)),
+                None => quote!(serde_hash::hashids::encode_single(value)),
+            };
+            let decode = match &options.decode_with {
+                Some(path) => quote! {
+                    #path(hash).map_err(|e| serde_hash::SerdeHashError::InvalidHash(e.to_string()))
+                },
+                None => quote!(serde_hash::hashids::decode_single(hash)),
+            };
+            (encode, decode)
         }
-    } else {
-        0
     };
+    let decode = match &options.range {
+        Some((range, text)) => {
+            let ty = primary.ty;
+            quote! {
+                #decode.and_then(|value| {
+                    serde_hash::serde_impl::check_range::<#ty, _>(Ok::<_, String>(vec![value]), #range, #text)
+                        .map(|_| value)
+                        .map_err(serde_hash::SerdeHashError::InvalidHash)
+                })
+            }
+        }
+        None => decode,
+    };
+    let prefix = prefix.map(syn::LitStr::value).unwrap_or_default();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics serde_hash::HasHashId for #name #ty_generics #where_clause {
+            const PREFIX: &'static str = #prefix;
 
-    if !errors.is_empty() {
-        return TokenStream::from(quote! {
-            #(#errors)*
-        });
-    }
+            fn type_name() -> &'static str {
+                stringify!(#name)
+            }
 
-    // Generate code for Serialize and Deserialize.
-    let output = quote! {
-        impl serde::Serialize for #name {
-            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-            where S: serde::Serializer {
-                use serde::ser::SerializeStruct;
-                use serde_hash::hashids::encode_single;
+            fn hash_id(&self) -> String {
+                let value = serde_hash::serde_impl::HashNumeric::to_u64(self.#ident);
+                format!("{}{}", Self::PREFIX, #encode)
+            }
 
-                let mut s = serializer.serialize_struct(stringify!(#name), #field_count)?;
+            fn from_hash_id(hash: &str) -> serde_hash::Result<u64> {
+                let hash = serde_hash::entity::strip_prefix(hash, Self::PREFIX)?;
+                #decode
+            }
+        }
 
-                #(
-                    s.serialize_field(
-                        stringify!(#numeric_hash_fields),
-                        &encode_single(self.#numeric_hash_fields as u64)
-                    )?;
-                )*
+        impl #impl_generics serde_hash::entity::PublicId for #name #ty_generics #where_clause {
+            type Id = u64;
 
-                #(
-                    {
-                        let mut tmp_vec = Vec::new();
-                        for v in &self.#vector_hash_fields {
-                            tmp_vec.push(encode_single(*v as u64));
-                        }
-                        s.serialize_field(
-                            stringify!(#vector_hash_fields),
-                            &tmp_vec
-                        )?;
-                    }
-                )*
+            fn as_public_id(&self) -> String {
+                serde_hash::HasHashId::hash_id(self)
+            }
 
-                #(
-                    {
-                        if let Some(value) = self.#option_numeric_hash_fields {
-                            s.serialize_field(
-                                stringify!(#option_numeric_hash_fields),
-                                &Some(encode_single(value as u64))
-                            )?;
-                        } else {
-                            s.serialize_field(
-                                stringify!(#option_numeric_hash_fields),
-                                &Option::<String>::None
-                            )?;
-                        }
-                    }
-                )*
+            fn parse_public_id(public_id: &str) -> serde_hash::Result<u64> {
+                <Self as serde_hash::HasHashId>::from_hash_id(public_id)
+            }
+        }
+    })
+}
 
-                #(
-                    {
-                        if let Some(vec_value) = &self.#option_vector_hash_fields {
-                            let mut tmp_vec = Vec::new();
-                            for v in vec_value {
-                                tmp_vec.push(encode_single(*v as u64));
-                            }
-                            s.serialize_field(
-                                stringify!(#option_vector_hash_fields),
-                                &Some(tmp_vec)
-                            )?;
-                        } else {
-                            s.serialize_field(
-                                stringify!(#option_vector_hash_fields),
-                                &Option::<Vec<String>>::None
-                            )?;
-                        }
-                    }
-                )*
+/// The inherent JSON helpers generated for `#[hash(helpers)]`.
+fn json_helpers(
+    name: &syn::Ident,
+    generics: &syn::Generics,
+    vis: &syn::Visibility,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Serializes the struct to JSON with its `#[hash]` fields encoded.
+            #vis fn to_public_json(&self) -> serde_hash::__private::serde_json::Result<String> {
+                serde_hash::__private::serde_json::to_string(self)
+            }
 
-                #(
-                    s.serialize_field(stringify!(#non_hash_fields), &self.#non_hash_fields)?;
-                )*
+            /// Deserializes the struct from JSON written by `to_public_json`.
+            #vis fn from_public_json<'de>(json: &'de str) -> serde_hash::__private::serde_json::Result<Self>
+            where Self: serde_hash::serde::Deserialize<'de> {
+                serde_hash::__private::serde_json::from_str(json)
+            }
+        }
+    }
+}
 
-                s.end()
+/// `Serialize`/`Deserialize` impls for a unit struct, matching serde's derive.
+fn unit_struct_impls(name: &syn::Ident) -> proc_macro2::TokenStream {
+    quote! {
+        impl serde_hash::serde::Serialize for #name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where S: serde_hash::serde::Serializer {
+                serializer.serialize_unit_struct(stringify!(#name))
             }
         }
 
-        impl<'de> serde::Deserialize<'de> for #name {
+        impl<'de> serde_hash::serde::Deserialize<'de> for #name {
             fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-            where D: serde::Deserializer<'de> {
-                use serde::de::{self, MapAccess, Visitor};
+            where D: serde_hash::serde::Deserializer<'de> {
+                use serde_hash::serde::de::{self, Visitor};
                 use std::fmt;
-                use serde_hash::hashids::decode_single;
 
-                struct StructVisitor;
+                struct UnitVisitor;
 
-                impl<'de> Visitor<'de> for StructVisitor {
+                impl<'de> Visitor<'de> for UnitVisitor {
                     type Value = #name;
 
                     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                        formatter.write_str(concat!("struct ", stringify!(#name)))
+                        formatter.write_str(concat!("unit struct ", stringify!(#name)))
                     }
 
-                    fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
-                    where V: MapAccess<'de> {
-                        #(
-                            let mut #numeric_hash_fields = None;
-                        )*
-                        #(
-                            let mut #vector_hash_fields = None;
-                        )*
-                        #(
-                            let mut #option_numeric_hash_fields = None;
-                        )*
-                        #(
-                            let mut #option_vector_hash_fields = None;
-                        )*
-                        #(
-                            let mut #non_hash_fields = None;
-                        )*
-
-                        while let Some(key) = map.next_key::<String>()? {
-                            match key.as_str() {
-                                #(
-                                    stringify!(#numeric_hash_fields) => {
-                                        let hash_str = map.next_value::<String>()?;
-                                        let decoded = decode_single(hash_str)
-                                            .map_err(|e| de::Error::custom(format!("Failed to decode hash: {}", e)))?;
-                                        #numeric_hash_fields = Some(decoded as _);
-                                    },
-                                )*
-                                #(
-                                    stringify!(#vector_hash_fields) => {
-                                        let hash_vec = map.next_value::<Vec<String>>()?;
-                                        let mut decoded_vec = Vec::new();
-                                        for hash in hash_vec {
-                                            let decoded = decode_single(hash)
-                                                .map_err(|e| de::Error::custom(format!("Failed to decode hash: {}", e)))?;
-                                            decoded_vec.push(decoded as _);
-                                        }
-                                        #vector_hash_fields = Some(decoded_vec);
-                                    },
-                                )*
-                                #(
-                                    stringify!(#option_numeric_hash_fields) => {
-                                        let option_hash = map.next_value::<Option<String>>()?;
-                                        if let Some(hash_str) = option_hash {
-                                            let decoded = decode_single(hash_str)
-                                                .map_err(|e| de::Error::custom(format!("Failed to decode hash: {}", e)))?;
-                                            #option_numeric_hash_fields = Some(Some(decoded as _));
-                                        } else {
-                                            #option_numeric_hash_fields = Some(None);
-                                        }
-                                    },
-                                )*
-                                #(
-                                    stringify!(#option_vector_hash_fields) => {
-                                        let option_hash_vec = map.next_value::<Option<Vec<String>>>()?;
-                                        if let Some(hash_vec) = option_hash_vec {
-                                            let mut decoded_vec = Vec::new();
-                                            for hash in hash_vec {
-                                                let decoded = decode_single(hash)
-                                                    .map_err(|e| de::Error::custom(format!("Failed to decode hash: {}", e)))?;
-                                                decoded_vec.push(decoded as _);
-                                            }
-                                            #option_vector_hash_fields = Some(Some(decoded_vec));
-                                        } else {
-                                            #option_vector_hash_fields = Some(None);
-                                        }
-                                    },
-                                )*
-                                #(
-                                    stringify!(#non_hash_fields) => {
-                                        #non_hash_fields = Some(map.next_value()?);
-                                    },
-                                )*
-                                _ => {
-                                    let _ = map.next_value::<de::IgnoredAny>()?;
-                                }
-                            }
-                        }
-
-                        #(
-                            let #numeric_hash_fields = #numeric_hash_fields.ok_or_else(||
-                                de::Error::missing_field(stringify!(#numeric_hash_fields))
-                            )?;
-                        )*
-                        #(
-                            let #vector_hash_fields = #vector_hash_fields.ok_or_else(||
-                                de::Error::missing_field(stringify!(#vector_hash_fields))
-                            )?;
-                        )*
-                        #(
-                            let #option_numeric_hash_fields = #option_numeric_hash_fields.ok_or_else(||
-                                de::Error::missing_field(stringify!(#option_numeric_hash_fields))
-                            )?;
-                        )*
-                        #(
-                            let #option_vector_hash_fields = #option_vector_hash_fields.ok_or_else(||
-                                de::Error::missing_field(stringify!(#option_vector_hash_fields))
-                            )?;
-                        )*
-                        #(
-                            let #non_hash_fields = #non_hash_fields.ok_or_else(||
-                                de::Error::missing_field(stringify!(#non_hash_fields))
-                            )?;
-                        )*
-
-                        Ok(#name {
-                            #(
-                                #numeric_hash_fields,
-                            )*
-                            #(
-                                #vector_hash_fields,
-                            )*
-                            #(
-                                #option_numeric_hash_fields,
-                            )*
-                            #(
-                                #option_vector_hash_fields,
-                            )*
-                            #(
-                                #non_hash_fields,
-                            )*
-                        })
+                    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+                        Ok(#name)
                     }
                 }
 
-                deserializer.deserialize_map(StructVisitor)
+                deserializer.deserialize_unit_struct(stringify!(#name), UnitVisitor)
             }
         }
-    };
-
-    output.into()
+    }
 }
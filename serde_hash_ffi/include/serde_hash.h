@@ -0,0 +1,60 @@
+/*
+ * C bindings for serde_hash.
+ *
+ * Link against libserde_hash_ffi (built by `cargo build -p serde_hash_ffi`). Services that
+ * configure the same salt, minimum length and alphabet produce the same hashes as the
+ * Rust services using serde_hash.
+ */
+#ifndef SERDE_HASH_H
+#define SERDE_HASH_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define SERDE_HASH_OK 0
+#define SERDE_HASH_ERR_NULL (-1)
+#define SERDE_HASH_ERR_UTF8 (-2)
+#define SERDE_HASH_ERR_INVALID_OPTIONS (-3)
+#define SERDE_HASH_ERR_ALREADY_CONFIGURED (-4)
+#define SERDE_HASH_ERR_INVALID_HASH (-5)
+
+/* Opaque set of options; create with serde_hash_options_new. */
+typedef struct SerdeHashOptions SerdeHashOptions;
+
+/* Creates options with the library defaults. Pass them to serde_hash_options_build or
+ * serde_hash_options_free exactly once. */
+SerdeHashOptions *serde_hash_options_new(void);
+
+int32_t serde_hash_options_set_salt(SerdeHashOptions *options, const char *salt);
+int32_t serde_hash_options_set_min_length(SerdeHashOptions *options, size_t min_length);
+int32_t serde_hash_options_set_alphabet(SerdeHashOptions *options, const char *alphabet);
+int32_t serde_hash_options_set_case_insensitive_decode(SerdeHashOptions *options, bool enabled);
+int32_t serde_hash_options_set_constant_time_decode(SerdeHashOptions *options, bool enabled);
+int32_t serde_hash_options_set_checksum(SerdeHashOptions *options, bool enabled);
+int32_t serde_hash_options_set_weak_salt_allowed(SerdeHashOptions *options, bool allowed);
+
+/* Validates the options and installs them globally. Always takes ownership of options. */
+int32_t serde_hash_options_build(SerdeHashOptions *options);
+
+/* Frees options that were never built. */
+void serde_hash_options_free(SerdeHashOptions *options);
+
+/* Encodes value. Release the result with serde_hash_string_free. */
+char *serde_hash_encode_single(uint64_t value);
+
+/* Decodes hash into *out. Returns SERDE_HASH_OK or an error code. */
+int32_t serde_hash_decode_single(const char *hash, uint64_t *out);
+
+/* Frees a string returned by serde_hash_encode_single. */
+void serde_hash_string_free(char *value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* SERDE_HASH_H */
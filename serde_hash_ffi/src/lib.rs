@@ -0,0 +1,250 @@
+#![doc = include_str!("../README.MD")]
+
+use serde_hash::error::SerdeHashError;
+use serde_hash::hashids::{self, SerdeHashOptions};
+use std::ffi::{CStr, CString, c_char};
+use std::ptr;
+
+/// The call succeeded.
+pub const SERDE_HASH_OK: i32 = 0;
+/// A required pointer argument was null.
+pub const SERDE_HASH_ERR_NULL: i32 = -1;
+/// A string argument wasn't valid UTF-8.
+pub const SERDE_HASH_ERR_UTF8: i32 = -2;
+/// The options were rejected, e.g. because of an invalid alphabet.
+pub const SERDE_HASH_ERR_INVALID_OPTIONS: i32 = -3;
+/// The global options were already configured.
+pub const SERDE_HASH_ERR_ALREADY_CONFIGURED: i32 = -4;
+/// The hash could not be decoded.
+pub const SERDE_HASH_ERR_INVALID_HASH: i32 = -5;
+
+/// Reads a C string argument as UTF-8.
+///
+/// # Safety
+///
+/// `value` must be null or point to a NUL-terminated string.
+unsafe fn read_str<'a>(value: *const c_char) -> Result<&'a str, i32> {
+    if value.is_null() {
+        return Err(SERDE_HASH_ERR_NULL);
+    }
+    unsafe { CStr::from_ptr(value) }
+        .to_str()
+        .map_err(|_| SERDE_HASH_ERR_UTF8)
+}
+
+/// Applies `update` to the options behind `options`.
+///
+/// # Safety
+///
+/// `options` must be null or a pointer returned by [`serde_hash_options_new`].
+unsafe fn update_options(
+    options: *mut SerdeHashOptions,
+    update: impl FnOnce(SerdeHashOptions) -> SerdeHashOptions,
+) -> i32 {
+    if options.is_null() {
+        return SERDE_HASH_ERR_NULL;
+    }
+    unsafe {
+        let current = ptr::read(options);
+        ptr::write(options, update(current));
+    }
+    SERDE_HASH_OK
+}
+
+/// Creates a set of options with the library defaults.
+///
+/// The options must be passed to [`serde_hash_options_build`] or
+/// [`serde_hash_options_free`] exactly once.
+#[unsafe(no_mangle)]
+pub extern "C" fn serde_hash_options_new() -> *mut SerdeHashOptions {
+    Box::into_raw(Box::new(SerdeHashOptions::new()))
+}
+
+/// Sets the salt.
+///
+/// # Safety
+///
+/// `options` must come from [`serde_hash_options_new`] and `salt` must be a
+/// NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn serde_hash_options_set_salt(
+    options: *mut SerdeHashOptions,
+    salt: *const c_char,
+) -> i32 {
+    let salt = match unsafe { read_str(salt) } {
+        Ok(salt) => salt,
+        Err(code) => return code,
+    };
+    unsafe { update_options(options, |options| options.with_salt(salt)) }
+}
+
+/// Sets the minimum hash length.
+///
+/// # Safety
+///
+/// `options` must come from [`serde_hash_options_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn serde_hash_options_set_min_length(
+    options: *mut SerdeHashOptions,
+    min_length: usize,
+) -> i32 {
+    unsafe { update_options(options, |options| options.with_min_length(min_length)) }
+}
+
+/// Sets the alphabet.
+///
+/// # Safety
+///
+/// `options` must come from [`serde_hash_options_new`] and `alphabet` must be a
+/// NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn serde_hash_options_set_alphabet(
+    options: *mut SerdeHashOptions,
+    alphabet: *const c_char,
+) -> i32 {
+    let alphabet = match unsafe { read_str(alphabet) } {
+        Ok(alphabet) => alphabet,
+        Err(code) => return code,
+    };
+    unsafe { update_options(options, |options| options.with_alphabet(alphabet)) }
+}
+
+/// Enables or disables case-insensitive decoding.
+///
+/// # Safety
+///
+/// `options` must come from [`serde_hash_options_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn serde_hash_options_set_case_insensitive_decode(
+    options: *mut SerdeHashOptions,
+    enabled: bool,
+) -> i32 {
+    unsafe {
+        update_options(options, |options| {
+            options.with_case_insensitive_decode(enabled)
+        })
+    }
+}
+
+/// Enables or disables the hardened, constant-work decoder.
+///
+/// # Safety
+///
+/// `options` must come from [`serde_hash_options_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn serde_hash_options_set_constant_time_decode(
+    options: *mut SerdeHashOptions,
+    enabled: bool,
+) -> i32 {
+    unsafe {
+        update_options(options, |options| {
+            options.with_constant_time_decode(enabled)
+        })
+    }
+}
+
+/// Enables or disables the check character appended to every hash.
+///
+/// # Safety
+///
+/// `options` must come from [`serde_hash_options_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn serde_hash_options_set_checksum(
+    options: *mut SerdeHashOptions,
+    enabled: bool,
+) -> i32 {
+    unsafe { update_options(options, |options| options.with_checksum(enabled)) }
+}
+
+/// Lets [`serde_hash_options_build`] accept a salt that is too short or too repetitive.
+///
+/// # Safety
+///
+/// `options` must come from [`serde_hash_options_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn serde_hash_options_set_weak_salt_allowed(
+    options: *mut SerdeHashOptions,
+    allowed: bool,
+) -> i32 {
+    unsafe { update_options(options, |options| options.with_weak_salt_allowed(allowed)) }
+}
+
+/// Validates the options and installs them as the global configuration.
+///
+/// Takes ownership of `options`, which must not be used afterwards, whether or not the
+/// call succeeds.
+///
+/// # Safety
+///
+/// `options` must come from [`serde_hash_options_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn serde_hash_options_build(options: *mut SerdeHashOptions) -> i32 {
+    if options.is_null() {
+        return SERDE_HASH_ERR_NULL;
+    }
+    let options = unsafe { Box::from_raw(options) };
+    match options.try_build() {
+        Ok(()) => SERDE_HASH_OK,
+        Err(SerdeHashError::AlreadyConfigured) => SERDE_HASH_ERR_ALREADY_CONFIGURED,
+        Err(_) => SERDE_HASH_ERR_INVALID_OPTIONS,
+    }
+}
+
+/// Frees options that were never built.
+///
+/// # Safety
+///
+/// `options` must be null or come from [`serde_hash_options_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn serde_hash_options_free(options: *mut SerdeHashOptions) {
+    if !options.is_null() {
+        drop(unsafe { Box::from_raw(options) });
+    }
+}
+
+/// Encodes `value` with the global options.
+///
+/// Returns a NUL-terminated string that must be released with [`serde_hash_string_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn serde_hash_encode_single(value: u64) -> *mut c_char {
+    CString::new(hashids::encode_single(value))
+        .expect("hashes never contain NUL")
+        .into_raw()
+}
+
+/// Decodes `hash` with the global options, writing the value to `out`.
+///
+/// # Safety
+///
+/// `hash` must be a NUL-terminated string and `out` must point to writable memory for a
+/// `uint64_t`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn serde_hash_decode_single(hash: *const c_char, out: *mut u64) -> i32 {
+    if out.is_null() {
+        return SERDE_HASH_ERR_NULL;
+    }
+    let hash = match unsafe { read_str(hash) } {
+        Ok(hash) => hash,
+        Err(code) => return code,
+    };
+    match hashids::decode_single(hash) {
+        Ok(value) => {
+            unsafe { out.write(value) };
+            SERDE_HASH_OK
+        }
+        Err(_) => SERDE_HASH_ERR_INVALID_HASH,
+    }
+}
+
+/// Frees a string returned by this library.
+///
+/// # Safety
+///
+/// `value` must be null or a string returned by [`serde_hash_encode_single`] that
+/// hasn't been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn serde_hash_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(unsafe { CString::from_raw(value) });
+    }
+}
@@ -0,0 +1,63 @@
+mod test_ffi {
+    use serde_hash_ffi::*;
+    use std::ffi::{CStr, CString};
+
+    #[test]
+    fn test_round_trip() {
+        let salt = CString::new("hello world").unwrap();
+        let alphabet =
+            CString::new("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890").unwrap();
+        unsafe {
+            let invalid = serde_hash_options_new();
+            let short = CString::new("abc").unwrap();
+            assert_eq!(serde_hash_options_set_alphabet(invalid, short.as_ptr()), 0);
+            assert_eq!(
+                serde_hash_options_build(invalid),
+                SERDE_HASH_ERR_INVALID_OPTIONS
+            );
+
+            let options = serde_hash_options_new();
+            assert_eq!(
+                serde_hash_options_set_salt(options, salt.as_ptr()),
+                SERDE_HASH_OK
+            );
+            assert_eq!(
+                serde_hash_options_set_min_length(options, 10),
+                SERDE_HASH_OK
+            );
+            assert_eq!(
+                serde_hash_options_set_alphabet(options, alphabet.as_ptr()),
+                SERDE_HASH_OK
+            );
+            assert_eq!(
+                serde_hash_options_set_weak_salt_allowed(options, true),
+                SERDE_HASH_OK
+            );
+            assert_eq!(serde_hash_options_build(options), SERDE_HASH_OK);
+
+            let again = serde_hash_options_new();
+            assert_eq!(
+                serde_hash_options_build(again),
+                SERDE_HASH_ERR_ALREADY_CONFIGURED
+            );
+
+            let hash = serde_hash_encode_single(158674);
+            assert_eq!(CStr::from_ptr(hash).to_str().unwrap(), "qKknODM7Ej");
+
+            let mut value = 0;
+            assert_eq!(serde_hash_decode_single(hash, &mut value), SERDE_HASH_OK);
+            assert_eq!(value, 158674);
+            serde_hash_string_free(hash);
+
+            let bogus = CString::new("bogus!").unwrap();
+            assert_eq!(
+                serde_hash_decode_single(bogus.as_ptr(), &mut value),
+                SERDE_HASH_ERR_INVALID_HASH
+            );
+            assert_eq!(
+                serde_hash_decode_single(std::ptr::null(), &mut value),
+                SERDE_HASH_ERR_NULL
+            );
+        }
+    }
+}